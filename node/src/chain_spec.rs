@@ -1,11 +1,15 @@
+use std::path::Path;
+
 use cumulus_primitives_core::ParaId;
 use kine_runtime::{
-	AccountId, AuraId, Signature, EXISTENTIAL_DEPOSIT,
+	AccountId, AuraId, Balance, Signature, EVM_CHAIN_ID, EXISTENTIAL_DEPOSIT, SS58_PREFIX, TOKEN_DECIMALS,
+	TOKEN_SYMBOL,
 };
 use sc_chain_spec::{ChainSpecExtension, ChainSpecGroup};
-use sc_service::ChainType;
+use sc_service::{config::MultiaddrWithPeerId, ChainType};
+use sc_telemetry::TelemetryEndpoints;
 use serde::{Deserialize, Serialize};
-use sp_core::{sr25519, Pair, Public};
+use sp_core::{crypto::Ss58Codec, sr25519, Pair};
 use sp_runtime::{
 	traits::{IdentifyAccount, Verify, BlockNumberProvider},
 	BoundedVec,
@@ -24,13 +28,6 @@ pub type ChainSpec = sc_service::GenericChainSpec<(), Extensions>;
 /// The default XCM version to set in genesis config.
 const SAFE_XCM_VERSION: u32 = xcm::prelude::XCM_VERSION;
 
-/// Helper function to generate a crypto pair from seed
-pub fn get_from_seed<TPublic: Public>(seed: &str) -> <TPublic::Pair as Pair>::Public {
-	TPublic::Pair::from_string(&format!("//{}", seed), None)
-		.expect("static values are valid; qed")
-		.public()
-}
-
 /// The extensions for the [`ChainSpec`].
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ChainSpecGroup, ChainSpecExtension)]
 #[serde(deny_unknown_fields)]
@@ -50,137 +47,213 @@ impl Extensions {
 
 type AccountPublic = <Signature as Verify>::Signer;
 
-/// Generate collator keys from seed.
+/// Generate the session keys from individual elements.
 ///
-/// This function's return type must always match the session keys of the chain in tuple format.
-pub fn get_collator_keys_from_seed(seed: &str) -> AuraId {
-	get_from_seed::<AuraId>(seed)
+/// The input must be a tuple of individual keys (a single arg for now since we have just one key).
+pub fn template_session_keys(keys: AuraId) -> kine_runtime::SessionKeys {
+	kine_runtime::SessionKeys { aura: keys }
 }
 
-/// Helper function to generate an account ID from seed
-pub fn get_account_id_from_seed<TPublic: Public>(seed: &str) -> AccountId
-where
-	AccountPublic: From<<TPublic::Pair as Pair>::Public>,
-{
-	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
+/// The token symbol, decimals and address format shown by wallets and explorers.
+pub fn chain_properties() -> sc_chain_spec::Properties {
+	let mut properties = sc_chain_spec::Properties::new();
+	properties.insert("tokenSymbol".into(), TOKEN_SYMBOL.into());
+	properties.insert("tokenDecimals".into(), TOKEN_DECIMALS.into());
+	properties.insert("ss58Format".into(), SS58_PREFIX.into());
+	properties
 }
 
-/// Generate the session keys from individual elements.
+
+
+
+/// A testnet genesis, read from a TOML or JSON file by `load_spec` so that new testnets need no
+/// new build of the node. Genesis files are told from chain specs by their extension, `.toml`
+/// or `.genesis.json`.
 ///
-/// The input must be a tuple of individual keys (a single arg for now since we have just one key).
-pub fn template_session_keys(keys: AuraId) -> kine_runtime::SessionKeys {
-	kine_runtime::SessionKeys { aura: keys }
+/// Accounts and Aura keys are SS58 addresses, hex public keys or development seeds such as
+/// `//Alice`. Invulnerables and sudo are endowed along with `endowed`, once each. The token
+/// and address format are the runtime's, see [`chain_properties`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct GenesisFile {
+	pub name: String,
+	pub id: String,
+	#[serde(default = "GenesisFile::default_chain_type")]
+	pub chain_type: ChainType,
+	#[serde(default)]
+	pub protocol_id: Option<String>,
+	/// Multiaddresses with peer ids, such as `/dns/boot.example.org/tcp/30333/p2p/12D3KooW...`.
+	#[serde(default)]
+	pub boot_nodes: Vec<String>,
+	/// Telemetry URLs with their verbosity.
+	#[serde(default)]
+	pub telemetry_endpoints: Vec<(String, u8)>,
+	pub relay_chain: String,
+	pub para_id: u32,
+	#[serde(default)]
+	pub sudo: Option<String>,
+	#[serde(default)]
+	pub invulnerables: Vec<GenesisInvulnerable>,
+	#[serde(default)]
+	pub endowed: Vec<String>,
+	/// The balance of every endowed account.
+	#[serde(default = "GenesisFile::default_endowment")]
+	pub endowment: Balance,
 }
 
+/// A collator of the genesis, with its session keys.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisInvulnerable {
+	pub account: String,
+	pub aura: String,
+}
 
+impl GenesisFile {
+	fn default_chain_type() -> ChainType {
+		ChainType::Local
+	}
 
+	fn default_endowment() -> Balance {
+		1 << 60
+	}
 
-pub fn development_config() -> ChainSpec {
-	// Give your base currency a unit name and decimal places
-	let mut properties = sc_chain_spec::Properties::new();
-	properties.insert("tokenSymbol".into(), "UNIT".into());
-	properties.insert("tokenDecimals".into(), 12.into());
-	properties.insert("ss58Format".into(), 42.into());
-
-	ChainSpec::builder(
-		kine_runtime::WASM_BINARY
-			.expect("WASM binary was not built, please build it!"),
-		Extensions {
+	/// Alice and Bob collating on para 1000 of `rococo-local`, with sudo Alice and the well
+	/// known development accounts endowed.
+	fn well_known(name: &str, id: &str, chain_type: ChainType) -> Self {
+		let seeds = ["Alice", "Bob", "Charlie", "Dave", "Eve", "Ferdie"];
+		Self {
+			name: name.into(),
+			id: id.into(),
+			chain_type,
+			protocol_id: None,
+			boot_nodes: Vec::new(),
+			telemetry_endpoints: Vec::new(),
 			relay_chain: "rococo-local".into(),
-			// You MUST set this to the correct network!
 			para_id: 1000,
-		},
-	)
-	.with_name("Development")
-	.with_id("dev")
-	.with_chain_type(ChainType::Development)
-	.with_genesis_config_patch(testnet_genesis(
-		// initial collators.
-		vec![
-			(
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
-				get_collator_keys_from_seed("Alice"),
-			),
-			(
-				get_account_id_from_seed::<sr25519::Public>("Bob"),
-				get_collator_keys_from_seed("Bob"),
-			),
-		],
-		vec![
-			get_account_id_from_seed::<sr25519::Public>("Alice"),
-			get_account_id_from_seed::<sr25519::Public>("Bob"),
-			get_account_id_from_seed::<sr25519::Public>("Charlie"),
-			get_account_id_from_seed::<sr25519::Public>("Dave"),
-			get_account_id_from_seed::<sr25519::Public>("Eve"),
-			get_account_id_from_seed::<sr25519::Public>("Ferdie"),
-			get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-		],
-		get_account_id_from_seed::<sr25519::Public>("Alice"),
-		1000.into(),
-	))
-	.build()
+			sudo: Some("//Alice".into()),
+			invulnerables: ["Alice", "Bob"]
+				.into_iter()
+				.map(|seed| GenesisInvulnerable { account: format!("//{seed}"), aura: format!("//{seed}") })
+				.collect(),
+			endowed: seeds
+				.iter()
+				.map(|seed| format!("//{seed}"))
+				.chain(seeds.iter().map(|seed| format!("//{seed}//stash")))
+				.collect(),
+			endowment: Self::default_endowment(),
+		}
+	}
+
+	/// Whether `path` names a genesis file rather than a chain spec: a `.toml` or
+	/// `.genesis.json` file.
+	pub fn is_genesis_file(path: &Path) -> bool {
+		let name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+		name.ends_with(".toml") || name.ends_with(".genesis.json")
+	}
+
+	/// Read a genesis file, TOML or JSON by its extension.
+	pub fn from_file(path: &Path) -> Result<Self, String> {
+		let contents = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+		let parsed = if path.extension().is_some_and(|extension| extension == "toml") {
+			toml::from_str(&contents).map_err(|e| e.to_string())
+		} else {
+			serde_json::from_str(&contents).map_err(|e| e.to_string())
+		};
+		parsed.map_err(|e| format!("Invalid genesis file {}: {e}", path.display()))
+	}
+
+	/// Build the chain spec of the genesis.
+	pub fn build(self) -> Result<ChainSpec, String> {
+		let invulnerables = self
+			.invulnerables
+			.iter()
+			.map(|invulnerable| {
+				Ok((parse_account(&invulnerable.account)?, AuraId::from(parse_public(&invulnerable.aura)?)))
+			})
+			.collect::<Result<Vec<_>, String>>()?;
+		let sudo = self.sudo.as_deref().map(parse_account).transpose()?;
+
+		let mut endowed = Vec::new();
+		let accounts = invulnerables.iter().map(|(account, _)| Ok(account.clone()));
+		let accounts = accounts.chain(sudo.clone().map(Ok)).chain(self.endowed.iter().map(|account| parse_account(account)));
+		for account in accounts {
+			let account = account?;
+			if !endowed.contains(&account) {
+				endowed.push(account);
+			}
+		}
+
+		let boot_nodes = self
+			.boot_nodes
+			.iter()
+			.map(|node| node.parse().map_err(|e| format!("Invalid boot node {node}: {e}")))
+			.collect::<Result<Vec<MultiaddrWithPeerId>, String>>()?;
+		let telemetry_endpoints = (!self.telemetry_endpoints.is_empty())
+			.then(|| TelemetryEndpoints::new(self.telemetry_endpoints.clone()))
+			.transpose()
+			.map_err(|e| format!("Invalid telemetry endpoint: {e}"))?;
+
+		let mut builder = ChainSpec::builder(
+			kine_runtime::WASM_BINARY.ok_or("WASM binary was not built, please build it!")?,
+			Extensions { relay_chain: self.relay_chain, para_id: self.para_id },
+		)
+		.with_name(&self.name)
+		.with_id(&self.id)
+		.with_chain_type(self.chain_type)
+		.with_genesis_config_patch(testnet_genesis(
+			invulnerables,
+			endowed,
+			self.endowment,
+			sudo,
+			self.para_id.into(),
+		))
+		.with_properties(chain_properties())
+		.with_boot_nodes(boot_nodes);
+		if let Some(protocol_id) = &self.protocol_id {
+			builder = builder.with_protocol_id(protocol_id);
+		}
+		if let Some(telemetry_endpoints) = telemetry_endpoints {
+			builder = builder.with_telemetry_endpoints(telemetry_endpoints);
+		}
+		Ok(builder.build())
+	}
+}
+
+/// A public key given by SS58 address, hex or development seed.
+fn parse_public(key: &str) -> Result<sr25519::Public, String> {
+	if key.starts_with("//") {
+		sr25519::Pair::from_string(key, None)
+			.map(|pair| pair.public())
+			.map_err(|e| format!("Invalid seed {key}: {e:?}"))
+	} else {
+		sr25519::Public::from_string(key).map_err(|e| format!("Invalid key {key}: {e:?}"))
+	}
+}
+
+fn parse_account(account: &str) -> Result<AccountId, String> {
+	parse_public(account).map(|public| AccountPublic::from(public).into_account())
+}
+
+
+
+
+pub fn development_config() -> ChainSpec {
+	GenesisFile::well_known("Development", "dev", ChainType::Development)
+		.build()
+		.expect("the well known accounts are valid; qed")
 }
 
 
 
 
 pub fn local_testnet_config() -> ChainSpec {
-	// Give your base currency a unit name and decimal places
-	let mut properties = sc_chain_spec::Properties::new();
-	properties.insert("tokenSymbol".into(), "UNIT".into());
-	properties.insert("tokenDecimals".into(), 12.into());
-	properties.insert("ss58Format".into(), 42.into());
-
-	#[allow(deprecated)]
-	ChainSpec::builder(
-		kine_runtime::WASM_BINARY
-			.expect("WASM binary was not built, please build it!"),
-		Extensions {
-			relay_chain: "rococo-local".into(),
-			// You MUST set this to the correct network!
-			para_id: 1000,
-		},
-	)
-	.with_name("Local Testnet")
-	.with_id("local_testnet")
-	.with_chain_type(ChainType::Local)
-	.with_genesis_config_patch(testnet_genesis(
-		// initial collators.
-		vec![
-			(
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
-				get_collator_keys_from_seed("Alice"),
-			),
-			(
-				get_account_id_from_seed::<sr25519::Public>("Bob"),
-				get_collator_keys_from_seed("Bob"),
-			),
-		],
-		vec![
-			get_account_id_from_seed::<sr25519::Public>("Alice"),
-			get_account_id_from_seed::<sr25519::Public>("Bob"),
-			get_account_id_from_seed::<sr25519::Public>("Charlie"),
-			get_account_id_from_seed::<sr25519::Public>("Dave"),
-			get_account_id_from_seed::<sr25519::Public>("Eve"),
-			get_account_id_from_seed::<sr25519::Public>("Ferdie"),
-			get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
-			get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-		],
-		get_account_id_from_seed::<sr25519::Public>("Alice"),
-		1000.into(),
-	))
-	.with_protocol_id("template-local")
-	.with_properties(properties)
+	GenesisFile {
+		protocol_id: Some("template-local".into()),
+		..GenesisFile::well_known("Local Testnet", "local_testnet", ChainType::Local)
+	}
 	.build()
+	.expect("the well known accounts are valid; qed")
 }
 
 
@@ -189,13 +262,14 @@ pub fn local_testnet_config() -> ChainSpec {
 fn testnet_genesis(
 	invulnerables: Vec<(AccountId, AuraId)>,
 	endowed_accounts: Vec<AccountId>,
-	root: AccountId,
+	endowment: Balance,
+	root: Option<AccountId>,
 	id: ParaId,
 ) -> serde_json::Value {
 
 	serde_json::json!({
 		"balances": {
-			"balances": endowed_accounts.iter().cloned().map(|k| (k, 1u64 << 60)).collect::<Vec<_>>(),
+			"balances": endowed_accounts.iter().cloned().map(|k| (k, endowment)).collect::<Vec<_>>(),
 		},
 		"parachainInfo": {
 			"parachainId": id,
@@ -204,6 +278,9 @@ fn testnet_genesis(
 			"invulnerables": invulnerables.iter().cloned().map(|(acc, _)| acc).collect::<Vec<_>>(),
 			"candidacyBond": EXISTENTIAL_DEPOSIT * 16,
 		},
+		"technicalCommittee": {
+			"members": invulnerables.iter().cloned().map(|(acc, _)| acc).collect::<Vec<_>>(),
+		},
 		"session": {
 			"keys": invulnerables
 				.into_iter()
@@ -219,6 +296,52 @@ fn testnet_genesis(
 		"polkadotXcm": {
 			"safeXcmVersion": Some(SAFE_XCM_VERSION),
 		},
-		"sudo": { "key": Some(root) },
+		"sudo": { "key": root },
+		"evmChainId": {
+			"chainId": EVM_CHAIN_ID,
+		},
 	})
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sc_chain_spec::ChainSpec as _;
+	use sp_core::Get;
+
+	/// Wallets and explorers take the address format and the token of the chain from the
+	/// properties of its chain spec, which must match what the runtime uses.
+	#[test]
+	fn chain_properties_match_the_runtime() {
+		let properties = chain_properties();
+		assert_eq!(properties["ss58Format"], <kine_runtime::Runtime as frame_system::Config>::SS58Prefix::get());
+		assert_eq!(properties["tokenDecimals"], kine_runtime::UNIT.ilog10());
+		assert_eq!(properties["tokenSymbol"], kine_runtime::TOKEN_SYMBOL);
+
+		for spec in [development_config(), local_testnet_config()] {
+			assert_eq!(spec.properties(), properties, "{}", spec.id());
+		}
+	}
+
+	/// Genesis files can't override the properties the runtime sets.
+	#[test]
+	fn genesis_files_have_no_chain_properties() {
+		let genesis = |properties: &str| {
+			toml::from_str::<GenesisFile>(&format!(
+				"name = \"Test\"\nid = \"test\"\nrelay_chain = \"rococo-local\"\npara_id = 2000\n{properties}"
+			))
+		};
+
+		assert!(genesis("").is_ok());
+		assert!(genesis("token_symbol = \"FAKE\"").is_err());
+		assert!(genesis("ss58_format = 42").is_err());
+	}
+
+	#[test]
+	fn genesis_files_are_told_apart_by_extension() {
+		assert!(GenesisFile::is_genesis_file(Path::new("res/testnet.toml")));
+		assert!(GenesisFile::is_genesis_file(Path::new("testnet.genesis.json")));
+		assert!(!GenesisFile::is_genesis_file(Path::new("testnet.json")));
+		assert!(!GenesisFile::is_genesis_file(Path::new("testnet-raw.json")));
+	}
+}
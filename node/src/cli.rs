@@ -33,6 +33,22 @@ pub enum Subcommand {
 	/// Export the genesis wasm of the parachain.
 	ExportGenesisWasm(cumulus_client_cli::ExportGenesisWasmCommand),
 
+	/// Dry run a runtime upgrade against the state of a block in the local database.
+	CheckUpgrade(crate::upgrade::CheckUpgradeCmd),
+
+	/// Check a chain spec for inconsistencies, or compare it with another one.
+	ValidateSpec(crate::validate_spec::ValidateSpecCmd),
+
+	/// Export the metadata of the runtime, as SCALE or JSON.
+	ExportMetadata(crate::metadata::ExportMetadataCmd),
+
+	/// Print the versions, APIs and pallets of the runtime.
+	RuntimeInfo(crate::metadata::RuntimeInfoCmd),
+
+	/// Generate, insert and register the session keys of a collator.
+	#[command(subcommand)]
+	SessionKeys(crate::keys::SessionKeysCmd),
+
 	/// Sub-commands concerned with benchmarking.
 	/// The pallet benchmarking moved to the `pallet` sub-command.
 	#[command(subcommand)]
@@ -54,6 +70,10 @@ const AFTER_HELP_EXAMPLE: &str = color_print::cstr!(
            Launch a full node with default parachain <italic>local-testnet</> and relay chain <italic>rococo-local</>.
    <bold>kine-node --collator</>
            Launch a collator with default parachain <italic>local-testnet</> and relay chain <italic>rococo-local</>.
+   <bold>kine-node export-metadata --format json -o metadata.json</>
+           Export the metadata of the bundled runtime for client code generation.
+   <bold>kine-node session-keys rotate --base-path ./data --nonce 0 --genesis-hash 0x.. -o set-keys.hex</>
+           Generate a new Aura key in the keystore and sign the transaction registering it.
  "#
 );
 #[derive(Debug, clap::Parser)]
@@ -80,6 +100,19 @@ pub struct Cli {
 	#[arg(long)]
 	pub no_hardware_benchmarks: bool,
 
+	#[allow(missing_docs)]
+	#[command(flatten)]
+	pub eth: crate::eth::EthConfiguration,
+
+	#[allow(missing_docs)]
+	#[command(flatten)]
+	pub indexer: crate::indexer::IndexerConfiguration,
+
+	/// The IPFS gateway the offchain workers of content availability attesters probe, such as
+	/// `http://127.0.0.1:8080`. Offchain workers may only make HTTP requests when it is set.
+	#[arg(long, value_name = "URL")]
+	pub ipfs_gateway: Option<String>,
+
 	/// Relay chain arguments
 	#[arg(raw = true)]
 	pub relay_chain_args: Vec<String>,
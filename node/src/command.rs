@@ -1,14 +1,20 @@
-use std::net::SocketAddr;
+use std::{
+	fs,
+	io::{self, Write},
+	net::SocketAddr,
+	path::PathBuf,
+};
 
 use cumulus_primitives_core::ParaId;
 use frame_benchmarking_cli::{BenchmarkCmd, SUBSTRATE_REFERENCE_HARDWARE};
 use log::info;
 use kine_runtime::Block;
 use sc_cli::{
-	ChainSpec, CliConfiguration, DefaultConfigurationValues, ImportParams, KeystoreParams,
-	NetworkParams, Result, SharedParams, SubstrateCli,
+	ChainSpec, CliConfiguration, Database, DatabaseParams, DefaultConfigurationValues,
+	ImportParams, KeystoreParams, NetworkParams, Result, SharedParams, SubstrateCli,
 };
-use sc_service::config::{BasePath, PrometheusConfig};
+use sc_service::config::{BasePath, DatabaseSource, PrometheusConfig};
+use sp_core::crypto::{set_default_ss58_version, Ss58AddressFormat};
 use sp_runtime::traits::AccountIdConversion;
 
 use crate::{
@@ -22,7 +28,14 @@ fn load_spec(id: &str) -> std::result::Result<Box<dyn ChainSpec>, String> {
 		"dev" => Box::new(chain_spec::development_config()),
 		"template-rococo" => Box::new(chain_spec::local_testnet_config()),
 		"" | "local" => Box::new(chain_spec::local_testnet_config()),
-		path => Box::new(chain_spec::ChainSpec::from_json_file(std::path::PathBuf::from(path))?),
+		path => {
+			let path = std::path::PathBuf::from(path);
+			if chain_spec::GenesisFile::is_genesis_file(&path) {
+				Box::new(chain_spec::GenesisFile::from_file(&path)?.build()?)
+			} else {
+				Box::new(chain_spec::ChainSpec::from_json_file(path)?)
+			}
+		},
 	})
 }
 
@@ -98,6 +111,65 @@ impl SubstrateCli for RelayChainCli {
 	}
 }
 
+/// Detect the backend of the existing database rather than assume the compiled-in default.
+/// `Auto` opens an existing RocksDB database as such and creates a ParityDB one, which is what
+/// the runtime's `DbWeight` assumes, for new nodes.
+fn set_default_database(cli: &mut Cli) {
+	let params = match &mut cli.subcommand {
+		Some(Subcommand::CheckBlock(cmd)) => &mut cmd.import_params.database_params,
+		Some(Subcommand::ExportBlocks(cmd)) => &mut cmd.database_params,
+		Some(Subcommand::ExportState(cmd)) => &mut cmd.database_params,
+		Some(Subcommand::ImportBlocks(cmd)) => &mut cmd.import_params.database_params,
+		Some(Subcommand::Revert(cmd)) => &mut cmd.database_params,
+		Some(Subcommand::PurgeChain(cmd)) => &mut cmd.base.database_params,
+		Some(Subcommand::CheckUpgrade(cmd)) => &mut cmd.import_params.database_params,
+		None => &mut cli.run.base.import_params.database_params,
+		_ => return,
+	};
+	params.database.get_or_insert(Database::Auto);
+}
+
+/// The directories of a database. `DatabaseSource::path` resolves `Auto` to the ParityDB
+/// directory only, while the node may be running on an existing RocksDB database.
+fn database_paths(database: &DatabaseSource) -> Result<Vec<PathBuf>> {
+	match database {
+		DatabaseSource::Auto { paritydb_path, rocksdb_path, .. } =>
+			Ok(vec![paritydb_path.clone(), rocksdb_path.clone()]),
+		database => database
+			.path()
+			.map(|path| vec![path.to_path_buf()])
+			.ok_or_else(|| "Cannot purge a custom database implementation".into()),
+	}
+}
+
+/// Remove the database directories, after confirmation unless `yes`.
+fn purge_databases(paths: &[PathBuf], yes: bool) -> Result<()> {
+	if !yes {
+		for path in paths {
+			println!("{}", path.display());
+		}
+		print!("Are you sure to remove? [y/N]: ");
+		io::stdout().flush()?;
+
+		let mut input = String::new();
+		io::stdin().read_line(&mut input)?;
+		if !input.trim().eq_ignore_ascii_case("y") {
+			println!("Aborted");
+			return Ok(())
+		}
+	}
+
+	for path in paths {
+		match fs::remove_dir_all(path) {
+			Ok(()) => println!("{} removed.", path.display()),
+			Err(e) if e.kind() == io::ErrorKind::NotFound =>
+				eprintln!("{} did not exist.", path.display()),
+			Err(e) => return Err(e.into()),
+		}
+	}
+	Ok(())
+}
+
 macro_rules! construct_async_run {
 	(|$components:ident, $cli:ident, $cmd:ident, $config:ident| $( $code:tt )* ) => {{
 		let runner = $cli.create_runner($cmd)?;
@@ -111,7 +183,9 @@ macro_rules! construct_async_run {
 
 /// Parse command line arguments into service configuration.
 pub fn run() -> Result<()> {
-	let cli = Cli::from_args();
+	let mut cli = Cli::from_args();
+	set_default_database(&mut cli);
+	set_default_ss58_version(Ss58AddressFormat::custom(kine_runtime::SS58_PREFIX));
 
 	match &cli.subcommand {
 		Some(Subcommand::BuildSpec(cmd)) => {
@@ -159,7 +233,14 @@ pub fn run() -> Result<()> {
 				)
 				.map_err(|err| format!("Relay chain argument error: {}", err))?;
 
-				cmd.run(config, polkadot_config)
+				let mut paths = Vec::new();
+				if cmd.parachain || !cmd.relaychain {
+					paths.extend(database_paths(&config.database)?);
+				}
+				if cmd.relaychain || !cmd.parachain {
+					paths.extend(database_paths(&polkadot_config.database)?);
+				}
+				purge_databases(&paths, cmd.base.yes)
 			})
 		},
 		Some(Subcommand::ExportGenesisHead(cmd)) => {
@@ -177,6 +258,15 @@ pub fn run() -> Result<()> {
 				cmd.run(&*spec)
 			})
 		},
+		Some(Subcommand::CheckUpgrade(cmd)) => {
+			construct_async_run!(|components, cli, cmd, config| {
+				Ok(cmd.run(components.client, components.backend))
+			})
+		},
+		Some(Subcommand::ValidateSpec(cmd)) => cmd.run(&cli),
+		Some(Subcommand::ExportMetadata(cmd)) => cmd.run(),
+		Some(Subcommand::RuntimeInfo(cmd)) => cmd.run(),
+		Some(Subcommand::SessionKeys(cmd)) => cmd.run(&cli),
 		Some(Subcommand::Benchmark(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			// Switch on the concrete benchmark sub-command-
@@ -259,6 +349,9 @@ pub fn run() -> Result<()> {
 					collator_options,
 					id,
 					hwbench,
+					cli.eth,
+					cli.indexer,
+					cli.ipfs_gateway,
 				)
 				.await
 				.map(|r| r.0)
@@ -392,3 +485,38 @@ impl CliConfiguration<Self> for RelayChainCli {
 		self.base.base.node_name()
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn nodes_detect_the_backend_of_their_database() {
+		let mut cli = Cli::from_iter(["kine-node"]);
+		set_default_database(&mut cli);
+		assert!(matches!(cli.run.base.import_params.database_params.database, Some(Database::Auto)));
+
+		let mut cli = Cli::from_iter(["kine-node", "--database", "rocksdb"]);
+		set_default_database(&mut cli);
+		assert!(matches!(cli.run.base.import_params.database_params.database, Some(Database::RocksDb)));
+	}
+
+	#[test]
+	fn purging_an_auto_database_removes_an_existing_rocksdb_one() {
+		let base = tempfile::tempdir().unwrap();
+		let rocksdb_path = base.path().join("db/full");
+		fs::create_dir_all(&rocksdb_path).unwrap();
+		fs::write(rocksdb_path.join("CURRENT"), b"MANIFEST-000001").unwrap();
+
+		let database = DatabaseSource::Auto {
+			paritydb_path: base.path().join("paritydb/full"),
+			rocksdb_path: rocksdb_path.clone(),
+			cache_size: 1024,
+		};
+		let paths = database_paths(&database).unwrap();
+		assert!(paths.contains(&rocksdb_path));
+
+		purge_databases(&paths, true).unwrap();
+		assert!(!rocksdb_path.exists());
+	}
+}
@@ -0,0 +1,151 @@
+//! Frontier services: the Ethereum mapping database, the tasks that keep it in sync with the
+//! chain and the command line options of the Ethereum RPC.
+
+use std::{
+	collections::BTreeMap,
+	path::PathBuf,
+	sync::{Arc, Mutex},
+	time::Duration,
+};
+
+use fc_mapping_sync::{kv::MappingSyncWorker, EthereumBlockNotificationSinks, SyncStrategy};
+use fc_rpc::{EthTask, OverrideHandle};
+use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool};
+use futures::{future, prelude::*};
+use kine_runtime::opaque::Block;
+use sc_client_api::BlockchainEvents;
+use sc_network_sync::SyncingService;
+use sc_service::{Configuration, TaskManager};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+
+/// The Ethereum mapping database, from Ethereum block and transaction hashes to parachain blocks.
+pub type FrontierBackend = fc_db::kv::Backend<Block>;
+
+/// How many blocks the filters of `eth_newFilter` are kept for.
+const FILTER_RETAIN_THRESHOLD: u64 = 100;
+
+/// Options of the Ethereum RPC.
+#[derive(Debug, Clone, clap::Args)]
+pub struct EthConfiguration {
+	/// Maximum number of logs in a query.
+	#[arg(long, default_value = "10000")]
+	pub max_past_logs: u32,
+
+	/// Maximum fee history cache size.
+	#[arg(long, default_value = "2048")]
+	pub fee_history_limit: u64,
+
+	/// Enable the `eth_accounts` dev signer, with the well known development keys.
+	#[arg(long)]
+	pub enable_dev_signer: bool,
+
+	/// Maximum number of stored filters.
+	#[arg(long, default_value = "500")]
+	pub max_stored_filters: usize,
+
+	/// Size in bytes of the LRU cache for block data.
+	#[arg(long, default_value = "50")]
+	pub eth_log_block_cache: usize,
+
+	/// Size in bytes of the LRU cache for transaction statuses data.
+	#[arg(long, default_value = "50")]
+	pub eth_statuses_cache: usize,
+
+	/// Multiplier of the block gas limit allowed for `eth_call` and `eth_estimateGas`.
+	#[arg(long, default_value = "10")]
+	pub execute_gas_limit_multiplier: u64,
+}
+
+/// The handles shared between the Ethereum RPC and the tasks that feed it.
+pub struct FrontierPartialComponents {
+	/// The active filters of `eth_newFilter`.
+	pub filter_pool: Option<FilterPool>,
+	/// The base fees and rewards of recent blocks, for `eth_feeHistory`.
+	pub fee_history_cache: FeeHistoryCache,
+	/// How many blocks `fee_history_cache` holds.
+	pub fee_history_cache_limit: FeeHistoryCacheLimit,
+}
+
+impl FrontierPartialComponents {
+	/// Create the empty caches sized by `config`.
+	pub fn new(config: &EthConfiguration) -> Self {
+		Self {
+			filter_pool: Some(Arc::new(Mutex::new(BTreeMap::new()))),
+			fee_history_cache: Arc::new(Mutex::new(BTreeMap::new())),
+			fee_history_cache_limit: config.fee_history_limit,
+		}
+	}
+}
+
+/// The directory the Ethereum mapping database lives in, next to the chain database.
+pub fn db_config_dir(config: &Configuration) -> PathBuf {
+	config.base_path.config_dir(config.chain_spec.id())
+}
+
+/// Open the Ethereum mapping database of the node at `config`.
+pub fn open_frontier_backend<C>(client: Arc<C>, config: &Configuration) -> Result<FrontierBackend, String>
+where
+	C: HeaderBackend<Block>,
+{
+	FrontierBackend::open(client, &config.database, &db_config_dir(config))
+}
+
+/// Spawn the tasks that map new blocks into the Ethereum database, expire stale filters and
+/// record the fee history.
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_frontier_tasks<C, BE>(
+	task_manager: &TaskManager,
+	client: Arc<C>,
+	backend: Arc<BE>,
+	frontier_backend: Arc<FrontierBackend>,
+	overrides: Arc<OverrideHandle<Block>>,
+	components: &FrontierPartialComponents,
+	sync: Arc<SyncingService<Block>>,
+	pubsub_notification_sinks: Arc<
+		EthereumBlockNotificationSinks<fc_mapping_sync::EthereumBlockNotification<Block>>,
+	>,
+) where
+	C: ProvideRuntimeApi<Block> + BlockchainEvents<Block> + HeaderBackend<Block>,
+	C: sc_client_api::StorageProvider<Block, BE> + Send + Sync + 'static,
+	C::Api: sp_block_builder::BlockBuilder<Block> + fp_rpc::EthereumRuntimeRPCApi<Block>,
+	BE: sc_client_api::Backend<Block> + 'static,
+{
+	task_manager.spawn_essential_handle().spawn(
+		"frontier-mapping-sync-worker",
+		Some("frontier"),
+		MappingSyncWorker::new(
+			client.import_notification_stream(),
+			Duration::new(6, 0),
+			client.clone(),
+			backend,
+			overrides.clone(),
+			frontier_backend,
+			3,
+			0,
+			SyncStrategy::Parachain,
+			sync,
+			pubsub_notification_sinks,
+		)
+		.for_each(|()| future::ready(())),
+	);
+
+	if let Some(filter_pool) = components.filter_pool.clone() {
+		task_manager.spawn_essential_handle().spawn(
+			"frontier-filter-pool",
+			Some("frontier"),
+			fc_rpc::EthFilter::filter_pool_task(client.clone(), filter_pool, FILTER_RETAIN_THRESHOLD),
+		);
+	}
+
+	task_manager.spawn_essential_handle().spawn(
+		"frontier-fee-history",
+		Some("frontier"),
+		EthTask::fee_history_task(
+			client,
+			overrides,
+			components.fee_history_cache.clone(),
+			components.fee_history_cache_limit,
+		),
+	);
+}
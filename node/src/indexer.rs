@@ -0,0 +1,539 @@
+//! An index of the events and extrinsics of the chain in a local SQLite database, kept up to
+//! date by the node as it imports and finalizes blocks, for backends that would otherwise poll
+//! the RPC. Blocks of forks stay in the index, out of the canonical chain, until finality prunes
+//! them.
+
+use std::{
+	path::{Path, PathBuf},
+	sync::{Arc, Mutex, MutexGuard},
+};
+
+use codec::{Decode, Encode};
+use futures::StreamExt;
+use kine_runtime::{
+	opaque::Block, AccountId, BlockNumber, EventRecord, Hash, RuntimeCall, RuntimeEvent,
+	UncheckedExtrinsic,
+};
+use rusqlite::{params, Connection};
+use sc_client_api::{
+	Backend, BlockBackend, BlockImportNotification, BlockchainEvents, FinalityNotification,
+	StorageProvider,
+};
+use scale_info::{form::PortableForm, PortableRegistry, TypeDef, Variant};
+use serde::{Deserialize, Serialize};
+use sp_blockchain::HeaderBackend;
+use sp_core::{crypto::Ss58Codec, storage::StorageKey, twox_128, Bytes};
+use sp_runtime::{traits::Header as _, MultiAddress};
+
+const LOG_TARGET: &str = "indexer";
+
+/// The most rows a query returns.
+const MAX_LIMIT: u32 = 1_000;
+
+/// Options of the event indexer.
+#[derive(Debug, Clone, clap::Args)]
+pub struct IndexerConfiguration {
+	/// Index the events and extrinsics of the chain in a SQLite database at this path, served
+	/// by the `indexer_*` RPC methods. The events of blocks whose state was pruned cannot be
+	/// indexed, use `--state-pruning archive` to index the whole chain. The queries of the index
+	/// are unsafe RPC methods, served with `--rpc-methods unsafe` only.
+	#[arg(long, value_name = "PATH")]
+	pub indexer_db: Option<PathBuf>,
+}
+
+const SCHEMA: &str = "
+	CREATE TABLE IF NOT EXISTS blocks (
+		hash BLOB PRIMARY KEY,
+		number INTEGER NOT NULL,
+		parent_hash BLOB NOT NULL,
+		canonical INTEGER NOT NULL,
+		finalized INTEGER NOT NULL DEFAULT 0
+	);
+	CREATE INDEX IF NOT EXISTS blocks_by_number ON blocks (number, canonical);
+
+	CREATE TABLE IF NOT EXISTS extrinsics (
+		block_hash BLOB NOT NULL REFERENCES blocks (hash) ON DELETE CASCADE,
+		idx INTEGER NOT NULL,
+		hash BLOB NOT NULL,
+		signer TEXT,
+		pallet TEXT NOT NULL,
+		call TEXT NOT NULL,
+		success INTEGER NOT NULL,
+		data BLOB NOT NULL,
+		PRIMARY KEY (block_hash, idx)
+	);
+	CREATE INDEX IF NOT EXISTS extrinsics_by_signer ON extrinsics (signer);
+	CREATE INDEX IF NOT EXISTS extrinsics_by_call ON extrinsics (pallet, call);
+
+	CREATE TABLE IF NOT EXISTS events (
+		block_hash BLOB NOT NULL REFERENCES blocks (hash) ON DELETE CASCADE,
+		idx INTEGER NOT NULL,
+		extrinsic_idx INTEGER,
+		pallet TEXT NOT NULL,
+		variant TEXT NOT NULL,
+		data BLOB NOT NULL,
+		PRIMARY KEY (block_hash, idx)
+	);
+	CREATE INDEX IF NOT EXISTS events_by_variant ON events (pallet, variant);
+";
+
+/// An event of the canonical chain.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedEvent {
+	block_number: BlockNumber,
+	block_hash: Hash,
+	index: u32,
+	/// The extrinsic that emitted the event, none for the events of block initialization and
+	/// finalization.
+	extrinsic_index: Option<u32>,
+	pallet: String,
+	variant: String,
+	/// The SCALE encoded `RuntimeEvent`.
+	data: Bytes,
+}
+
+/// An extrinsic of the canonical chain.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedExtrinsic {
+	block_number: BlockNumber,
+	block_hash: Hash,
+	index: u32,
+	hash: Hash,
+	signer: Option<String>,
+	pallet: String,
+	call: String,
+	success: bool,
+	/// The SCALE encoded extrinsic.
+	data: Bytes,
+}
+
+/// Filters of the indexed events, all optional.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct EventQuery {
+	pallet: Option<String>,
+	variant: Option<String>,
+	from_block: Option<BlockNumber>,
+	to_block: Option<BlockNumber>,
+	finalized_only: bool,
+	newest_first: bool,
+	limit: Option<u32>,
+}
+
+/// Filters of the indexed extrinsics, all optional.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct ExtrinsicQuery {
+	signer: Option<AccountId>,
+	pallet: Option<String>,
+	call: Option<String>,
+	from_block: Option<BlockNumber>,
+	to_block: Option<BlockNumber>,
+	finalized_only: bool,
+	newest_first: bool,
+	limit: Option<u32>,
+}
+
+/// How far the index goes.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexerStatus {
+	best: Option<BlockNumber>,
+	finalized: Option<BlockNumber>,
+}
+
+/// The events and extrinsics of a block, ready to be written.
+struct BlockData {
+	hash: Hash,
+	number: BlockNumber,
+	parent_hash: Hash,
+	extrinsics: Vec<(Hash, Option<String>, (String, String), Vec<u8>)>,
+	events: Vec<(Option<u32>, (String, String), Vec<u8>)>,
+	failed: Vec<u32>,
+}
+
+/// The names of the pallets and variants of `RuntimeEvent` and `RuntimeCall`, from their type
+/// information.
+struct Names {
+	registry: PortableRegistry,
+	event: u32,
+	call: u32,
+}
+
+impl Names {
+	fn new() -> Self {
+		let mut registry = scale_info::Registry::new();
+		let event = registry.register_type(&scale_info::meta_type::<RuntimeEvent>()).id;
+		let call = registry.register_type(&scale_info::meta_type::<RuntimeCall>()).id;
+		Self { registry: registry.into(), event, call }
+	}
+
+	/// The pallet and variant of an encoded outer enum of type `ty`, whose first byte is the
+	/// index of the pallet and second byte the index of the variant of the pallet enum.
+	fn of(&self, ty: u32, encoded: &[u8]) -> (String, String) {
+		let variant = |ty: u32, index: u8| match &self.registry.resolve(ty)?.type_def {
+			TypeDef::Variant(def) => def.variants.iter().find(|variant| variant.index == index),
+			_ => None,
+		};
+		let pallet = encoded.first().and_then(|index| variant(ty, *index));
+		let inner =
+			pallet.and_then(|pallet| variant(pallet.fields.first()?.ty.id, *encoded.get(1)?));
+		let name = |variant: Option<&Variant<PortableForm>>| {
+			variant.map_or_else(|| "unknown".to_string(), |variant| variant.name.clone())
+		};
+		(name(pallet), name(inner))
+	}
+}
+
+/// The index, shared by the task writing it and the RPC reading it.
+#[derive(Clone)]
+pub struct Indexer {
+	db: Arc<Mutex<Connection>>,
+	names: Arc<Names>,
+}
+
+impl Indexer {
+	/// Open the index at `path`, creating it if needed.
+	pub fn open(path: &Path) -> rusqlite::Result<Self> {
+		let db = Connection::open(path)?;
+		db.execute_batch("PRAGMA journal_mode = WAL; PRAGMA foreign_keys = ON;")?;
+		db.execute_batch(SCHEMA)?;
+		Ok(Self { db: Arc::new(Mutex::new(db)), names: Arc::new(Names::new()) })
+	}
+
+	fn db(&self) -> MutexGuard<'_, Connection> {
+		self.db.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+	}
+
+	/// Index the blocks the node imports and finalizes, after the canonical blocks it imported
+	/// while the indexer was not running. The index is written synchronously, so the task runs
+	/// on a blocking thread.
+	pub async fn run<C, BE>(self, client: Arc<C>)
+	where
+		C: BlockchainEvents<Block>
+			+ BlockBackend<Block>
+			+ HeaderBackend<Block>
+			+ StorageProvider<Block, BE>
+			+ Send
+			+ Sync
+			+ 'static,
+		BE: Backend<Block> + 'static,
+	{
+		let mut imports = client.every_import_notification_stream().fuse();
+		let mut finality = client.finality_notification_stream().fuse();
+
+		if let Err(e) = self.catch_up(&*client) {
+			log::error!(target: LOG_TARGET, "Catching up with the chain failed: {e}");
+		}
+
+		loop {
+			futures::select! {
+				notification = imports.next() => match notification {
+					Some(notification) => if let Err(e) = self.on_import(&*client, notification) {
+						log::warn!(target: LOG_TARGET, "Indexing an imported block failed: {e}");
+					},
+					None => break,
+				},
+				notification = finality.next() => match notification {
+					Some(notification) => if let Err(e) = self.on_finality(notification) {
+						log::warn!(target: LOG_TARGET, "Indexing a finalized block failed: {e}");
+					},
+					None => break,
+				},
+			}
+		}
+	}
+
+	fn catch_up<C, BE>(&self, client: &C) -> Result<(), String>
+	where
+		C: BlockBackend<Block> + HeaderBackend<Block> + StorageProvider<Block, BE>,
+		BE: Backend<Block>,
+	{
+		let indexed = self.status().map_err(|e| e.to_string())?.best;
+		let best = client.info().best_number;
+		let from = indexed.map_or(0, |number| number + 1);
+		if from <= best {
+			log::info!(target: LOG_TARGET, "Indexing blocks {from} to {best}");
+		}
+		for number in from..=best {
+			let hash = client
+				.hash(number)
+				.map_err(|e| e.to_string())?
+				.ok_or_else(|| format!("Block {number} is missing"))?;
+			let block = self.read_block(client, hash)?;
+			self.insert_block(&block, true).map_err(|e| e.to_string())?;
+		}
+		Ok(())
+	}
+
+	fn on_import<C, BE>(
+		&self,
+		client: &C,
+		notification: BlockImportNotification<Block>,
+	) -> Result<(), String>
+	where
+		C: BlockBackend<Block> + HeaderBackend<Block> + StorageProvider<Block, BE>,
+		BE: Backend<Block>,
+	{
+		let block = self.read_block(client, notification.hash)?;
+		self.insert_block(&block, notification.is_new_best).map_err(|e| e.to_string())?;
+
+		if let Some(route) = notification.tree_route.filter(|_| notification.is_new_best) {
+			let retracted: Vec<Hash> = route.retracted().iter().map(|block| block.hash).collect();
+			let enacted: Vec<Hash> = route.enacted().iter().map(|block| block.hash).collect();
+			self.set_canonical(&retracted, false).map_err(|e| e.to_string())?;
+			self.set_canonical(&enacted, true).map_err(|e| e.to_string())?;
+		}
+		Ok(())
+	}
+
+	/// Mark the finalized blocks and drop the forks finality pruned.
+	fn on_finality(&self, notification: FinalityNotification<Block>) -> Result<(), String> {
+		let number = *notification.header.number();
+		let mut finalized: Vec<Hash> = notification.tree_route.to_vec();
+		finalized.push(notification.hash);
+		self.set_canonical(&finalized, true).map_err(|e| e.to_string())?;
+
+		let db = self.db();
+		db.execute(
+			"UPDATE blocks SET finalized = 1 WHERE canonical = 1 AND number <= ?1",
+			params![number],
+		)
+		.and_then(|_| {
+			db.execute("DELETE FROM blocks WHERE canonical = 0 AND number <= ?1", params![number])
+		})
+		.map(|_| ())
+		.map_err(|e| e.to_string())
+	}
+
+	fn read_block<C, BE>(&self, client: &C, hash: Hash) -> Result<BlockData, String>
+	where
+		C: BlockBackend<Block> + HeaderBackend<Block> + StorageProvider<Block, BE>,
+		BE: Backend<Block>,
+	{
+		let header = client
+			.header(hash)
+			.map_err(|e| e.to_string())?
+			.ok_or_else(|| format!("Header of {hash} is missing"))?;
+		let body = client
+			.block_body(hash)
+			.map_err(|e| e.to_string())?
+			.ok_or_else(|| format!("Body of {hash} is missing"))?;
+
+		let extrinsics = body
+			.into_iter()
+			.map(|opaque| {
+				let encoded = opaque.encode();
+				let hash = Hash::from(sp_core::blake2_256(&encoded));
+				match UncheckedExtrinsic::decode(&mut &encoded[..]) {
+					Ok(extrinsic) => {
+						let signer =
+							extrinsic.0.signature.as_ref().and_then(
+								|(address, _, _)| match address {
+									MultiAddress::Id(account) => Some(account.to_ss58check()),
+									_ => None,
+								},
+							);
+						let names = self.names.of(self.names.call, &extrinsic.0.function.encode());
+						(hash, signer, names, encoded)
+					},
+					Err(_) => (hash, None, ("unknown".into(), "unknown".into()), encoded),
+				}
+			})
+			.collect();
+
+		let events_key = StorageKey([twox_128(b"System"), twox_128(b"Events")].concat());
+		let records = match client.storage(hash, &events_key) {
+			Ok(data) => data
+				.map(|data| Vec::<EventRecord>::decode(&mut &data.0[..]))
+				.transpose()
+				.unwrap_or_else(|e| {
+					log::warn!(target: LOG_TARGET, "Events of {hash} do not decode: {e}");
+					None
+				})
+				.unwrap_or_default(),
+			Err(e) => {
+				log::warn!(target: LOG_TARGET, "Events of {hash} are not available: {e}");
+				Vec::new()
+			},
+		};
+
+		let mut failed = Vec::new();
+		let events = records
+			.into_iter()
+			.map(|record| {
+				let extrinsic = match record.phase {
+					frame_system::Phase::ApplyExtrinsic(index) => Some(index),
+					_ => None,
+				};
+				if let (
+					Some(index),
+					RuntimeEvent::System(frame_system::Event::ExtrinsicFailed { .. }),
+				) = (extrinsic, &record.event)
+				{
+					failed.push(index);
+				}
+				let encoded = record.event.encode();
+				(extrinsic, self.names.of(self.names.event, &encoded), encoded)
+			})
+			.collect();
+
+		Ok(BlockData {
+			hash,
+			number: *header.number(),
+			parent_hash: *header.parent_hash(),
+			extrinsics,
+			events,
+			failed,
+		})
+	}
+
+	fn insert_block(&self, block: &BlockData, canonical: bool) -> rusqlite::Result<()> {
+		let mut db = self.db();
+		let tx = db.transaction()?;
+		let hash = block.hash.as_bytes();
+
+		tx.execute("DELETE FROM blocks WHERE hash = ?1", params![hash])?;
+		if canonical {
+			// The block replaces the other blocks at its height in the canonical chain.
+			tx.execute("UPDATE blocks SET canonical = 0 WHERE number = ?1", params![block.number])?;
+		}
+		tx.execute(
+			"INSERT INTO blocks (hash, number, parent_hash, canonical) VALUES (?1, ?2, ?3, ?4)",
+			params![hash, block.number, block.parent_hash.as_bytes(), canonical],
+		)?;
+		for (index, (extrinsic_hash, signer, (pallet, call), data)) in
+			block.extrinsics.iter().enumerate()
+		{
+			let success = !block.failed.contains(&(index as u32));
+			tx.execute(
+				"INSERT INTO extrinsics (block_hash, idx, hash, signer, pallet, call, success, data)
+				VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+				params![hash, index, extrinsic_hash.as_bytes(), signer, pallet, call, success, data],
+			)?;
+		}
+		for (index, (extrinsic, (pallet, variant), data)) in block.events.iter().enumerate() {
+			tx.execute(
+				"INSERT INTO events (block_hash, idx, extrinsic_idx, pallet, variant, data)
+				VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+				params![hash, index, extrinsic, pallet, variant, data],
+			)?;
+		}
+		tx.commit()
+	}
+
+	fn set_canonical(&self, hashes: &[Hash], canonical: bool) -> rusqlite::Result<()> {
+		let mut db = self.db();
+		let tx = db.transaction()?;
+		for hash in hashes {
+			if canonical {
+				tx.execute(
+					"UPDATE blocks SET canonical = 0
+					WHERE number = (SELECT number FROM blocks WHERE hash = ?1) AND hash != ?1",
+					params![hash.as_bytes()],
+				)?;
+			}
+			tx.execute(
+				"UPDATE blocks SET canonical = ?2 WHERE hash = ?1",
+				params![hash.as_bytes(), canonical],
+			)?;
+		}
+		tx.commit()
+	}
+
+	/// The highest canonical and finalized indexed blocks.
+	pub fn status(&self) -> rusqlite::Result<IndexerStatus> {
+		self.db().query_row(
+			"SELECT MAX(CASE WHEN canonical = 1 THEN number END), MAX(CASE WHEN finalized = 1 THEN number END)
+			FROM blocks",
+			[],
+			|row| Ok(IndexerStatus { best: row.get(0)?, finalized: row.get(1)? }),
+		)
+	}
+
+	/// The events of the canonical chain matching `query`, in chain order.
+	pub fn events(&self, query: &EventQuery) -> rusqlite::Result<Vec<IndexedEvent>> {
+		let order = if query.newest_first { "DESC" } else { "ASC" };
+		let db = self.db();
+		let mut statement = db.prepare_cached(&format!(
+			"SELECT b.number, b.hash, e.idx, e.extrinsic_idx, e.pallet, e.variant, e.data
+			FROM events e JOIN blocks b ON b.hash = e.block_hash
+			WHERE b.canonical = 1
+				AND (?1 IS NULL OR e.pallet = ?1) AND (?2 IS NULL OR e.variant = ?2)
+				AND (?3 IS NULL OR b.number >= ?3) AND (?4 IS NULL OR b.number <= ?4)
+				AND (?5 = 0 OR b.finalized = 1)
+			ORDER BY b.number {order}, e.idx {order}
+			LIMIT ?6"
+		))?;
+		let rows = statement.query_map(
+			params![
+				query.pallet,
+				query.variant,
+				query.from_block,
+				query.to_block,
+				query.finalized_only,
+				limit(query.limit),
+			],
+			|row| {
+				Ok(IndexedEvent {
+					block_number: row.get(0)?,
+					block_hash: Hash::from_slice(&row.get::<_, Vec<u8>>(1)?),
+					index: row.get(2)?,
+					extrinsic_index: row.get(3)?,
+					pallet: row.get(4)?,
+					variant: row.get(5)?,
+					data: row.get::<_, Vec<u8>>(6)?.into(),
+				})
+			},
+		)?;
+		rows.collect()
+	}
+
+	/// The extrinsics of the canonical chain matching `query`, in chain order.
+	pub fn extrinsics(&self, query: &ExtrinsicQuery) -> rusqlite::Result<Vec<IndexedExtrinsic>> {
+		let order = if query.newest_first { "DESC" } else { "ASC" };
+		let db = self.db();
+		let mut statement = db.prepare_cached(&format!(
+			"SELECT b.number, b.hash, x.idx, x.hash, x.signer, x.pallet, x.call, x.success, x.data
+			FROM extrinsics x JOIN blocks b ON b.hash = x.block_hash
+			WHERE b.canonical = 1
+				AND (?1 IS NULL OR x.signer = ?1)
+				AND (?2 IS NULL OR x.pallet = ?2) AND (?3 IS NULL OR x.call = ?3)
+				AND (?4 IS NULL OR b.number >= ?4) AND (?5 IS NULL OR b.number <= ?5)
+				AND (?6 = 0 OR b.finalized = 1)
+			ORDER BY b.number {order}, x.idx {order}
+			LIMIT ?7"
+		))?;
+		let rows = statement.query_map(
+			params![
+				query.signer.as_ref().map(|signer| signer.to_ss58check()),
+				query.pallet,
+				query.call,
+				query.from_block,
+				query.to_block,
+				query.finalized_only,
+				limit(query.limit),
+			],
+			|row| {
+				Ok(IndexedExtrinsic {
+					block_number: row.get(0)?,
+					block_hash: Hash::from_slice(&row.get::<_, Vec<u8>>(1)?),
+					index: row.get(2)?,
+					hash: Hash::from_slice(&row.get::<_, Vec<u8>>(3)?),
+					signer: row.get(4)?,
+					pallet: row.get(5)?,
+					call: row.get(6)?,
+					success: row.get(7)?,
+					data: row.get::<_, Vec<u8>>(8)?.into(),
+				})
+			},
+		)?;
+		rows.collect()
+	}
+}
+
+fn limit(limit: Option<u32>) -> u32 {
+	limit.unwrap_or(100).min(MAX_LIMIT)
+}
@@ -0,0 +1,244 @@
+//! Session key management for collators, without a running node: generating or inserting the
+//! Aura key in the keystore, printing the encoded `SessionKeys` and signing the
+//! `session.set_keys` extrinsic that registers them, to be submitted later.
+
+use std::{path::PathBuf, sync::Arc};
+
+use codec::{Decode, Encode};
+use kine_runtime::{
+	AccountId, AuraId, Balance, Hash, Nonce, Runtime, RuntimeCall, SessionKeys, SignedExtra,
+	UncheckedExtrinsic,
+};
+use sc_cli::{utils, Error, KeystoreParams, Result, SharedParams, SubstrateCli};
+use sc_keystore::LocalKeystore;
+use sc_service::config::{BasePath, KeystoreConfig};
+use sp_core::{
+	bytes::to_hex,
+	crypto::{key_types::AURA, Ss58Codec},
+	sr25519, Pair,
+};
+use sp_keystore::{Keystore, KeystorePtr};
+use sp_runtime::generic::{Era, SignedPayload};
+
+/// Manage the session keys of a collator.
+#[derive(Debug, clap::Subcommand)]
+pub enum SessionKeysCmd {
+	/// Generate a new Aura key in the keystore and print the session keys.
+	Generate(GenerateCmd),
+
+	/// Insert an existing Aura key in the keystore and print the session keys.
+	Insert(InsertCmd),
+
+	/// Sign a `session.set_keys` extrinsic registering existing session keys.
+	SetKeys(SetKeysCmd),
+
+	/// Generate a new Aura key in the keystore and sign the `session.set_keys` extrinsic
+	/// registering it.
+	Rotate(RotateCmd),
+}
+
+/// The keystore of the node the keys are for.
+#[derive(Debug, Clone, clap::Args)]
+pub struct KeystoreArgs {
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub keystore_params: KeystoreParams,
+}
+
+/// The transaction the session keys are registered with.
+#[derive(Debug, Clone, clap::Args)]
+pub struct SignArgs {
+	/// The secret URI of the collator account, asked for when missing.
+	#[arg(long)]
+	pub signer: Option<String>,
+
+	/// The next nonce of the collator account.
+	#[arg(long)]
+	pub nonce: Nonce,
+
+	/// The hash of the genesis block of the chain the transaction is for.
+	#[arg(long)]
+	pub genesis_hash: Hash,
+
+	/// The spec version of the runtime, the one of this node by default.
+	#[arg(long, default_value_t = kine_runtime::VERSION.spec_version)]
+	pub spec_version: u32,
+
+	/// The transaction version of the runtime, the one of this node by default.
+	#[arg(long, default_value_t = kine_runtime::VERSION.transaction_version)]
+	pub transaction_version: u32,
+
+	/// A tip for the block author.
+	#[arg(long, default_value_t = 0)]
+	pub tip: Balance,
+
+	/// The file the hex encoded extrinsic is written to.
+	#[arg(long, short)]
+	pub output: PathBuf,
+}
+
+/// The `generate` command.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct GenerateCmd {
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub keystore: KeystoreArgs,
+}
+
+/// The `insert` command.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct InsertCmd {
+	/// The secret URI of the Aura key, asked for when missing.
+	#[arg(long)]
+	pub suri: Option<String>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub keystore: KeystoreArgs,
+}
+
+/// The `set-keys` command.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct SetKeysCmd {
+	/// The hex encoded session keys.
+	#[arg(long)]
+	pub keys: String,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub sign: SignArgs,
+}
+
+/// The `rotate` command.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct RotateCmd {
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub keystore: KeystoreArgs,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub sign: SignArgs,
+}
+
+impl SessionKeysCmd {
+	/// Run the command.
+	pub fn run<C: SubstrateCli>(&self, cli: &C) -> Result<()> {
+		match self {
+			Self::Generate(cmd) => {
+				let keys = generate(&cmd.keystore.open(cli)?)?;
+				print_keys(&keys);
+			},
+			Self::Insert(cmd) => {
+				let suri = utils::read_uri(cmd.suri.as_ref())?;
+				let pair = pair_from_suri(&suri)?;
+				cmd.keystore
+					.open(cli)?
+					.insert(AURA, &suri, pair.public().as_ref())
+					.map_err(|_| Error::KeystoreOperation)?;
+				let keys = SessionKeys { aura: AuraId::from(pair.public()) };
+				print_keys(&keys);
+			},
+			Self::SetKeys(cmd) => {
+				let encoded = decode_hex(&cmd.keys)?;
+				let keys = SessionKeys::decode(&mut &encoded[..])
+					.map_err(|e| Error::Input(format!("Invalid session keys: {e}")))?;
+				cmd.sign.write_set_keys(keys)?;
+			},
+			Self::Rotate(cmd) => {
+				let keys = generate(&cmd.keystore.open(cli)?)?;
+				print_keys(&keys);
+				cmd.sign.write_set_keys(keys)?;
+			},
+		}
+		Ok(())
+	}
+}
+
+impl KeystoreArgs {
+	/// Open the keystore of the chain, as the node would with the same options.
+	fn open<C: SubstrateCli>(&self, cli: &C) -> Result<KeystorePtr> {
+		let base_path = self
+			.shared_params
+			.base_path()?
+			.unwrap_or_else(|| BasePath::from_project("", "", &C::executable_name()));
+		let chain_id = self.shared_params.chain_id(self.shared_params.is_dev());
+		let chain_spec = cli.load_spec(&chain_id)?;
+		let config_dir = base_path.config_dir(chain_spec.id());
+
+		match self.keystore_params.keystore_config(&config_dir)? {
+			KeystoreConfig::Path { path, password } =>
+				Ok(Arc::new(LocalKeystore::open(path, password)?)),
+			KeystoreConfig::InMemory => Err(Error::Input("The keystore must be on disk.".into())),
+		}
+	}
+}
+
+impl SignArgs {
+	/// Sign `session.set_keys(keys)` and write it to the output file. The transaction is
+	/// immortal, it stays valid until the nonce is used or the runtime version changes.
+	fn write_set_keys(&self, keys: SessionKeys) -> Result<()> {
+		let suri = utils::read_uri(self.signer.as_ref())?;
+		let signer = pair_from_suri(&suri)?;
+
+		let call = RuntimeCall::Session(pallet_session::Call::set_keys { keys, proof: Vec::new() });
+		let extra: SignedExtra = (
+			frame_system::CheckNonZeroSender::<Runtime>::new(),
+			frame_system::CheckSpecVersion::<Runtime>::new(),
+			frame_system::CheckTxVersion::<Runtime>::new(),
+			frame_system::CheckGenesis::<Runtime>::new(),
+			frame_system::CheckEra::<Runtime>::from(Era::Immortal),
+			frame_system::CheckNonce::<Runtime>::from(self.nonce),
+			frame_system::CheckWeight::<Runtime>::new(),
+			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(self.tip),
+		);
+		let payload = SignedPayload::from_raw(
+			call.clone(),
+			extra.clone(),
+			(
+				(),
+				self.spec_version,
+				self.transaction_version,
+				self.genesis_hash,
+				self.genesis_hash,
+				(),
+				(),
+				(),
+			),
+		);
+		let signature = payload.using_encoded(|payload| signer.sign(payload));
+		let account = AccountId::from(signer.public());
+		let extrinsic =
+			UncheckedExtrinsic::new_signed(call, account.clone().into(), signature.into(), extra);
+
+		std::fs::write(&self.output, to_hex(&extrinsic.encode(), false))?;
+		println!("Signed by:     {}", account.to_ss58check());
+		println!("Extrinsic:     {}", self.output.display());
+		Ok(())
+	}
+}
+
+/// Generate a new Aura key in `keystore`.
+fn generate(keystore: &KeystorePtr) -> Result<SessionKeys> {
+	let public = keystore.sr25519_generate_new(AURA, None).map_err(|_| Error::KeystoreOperation)?;
+	Ok(SessionKeys { aura: AuraId::from(public) })
+}
+
+fn pair_from_suri(suri: &str) -> Result<sr25519::Pair> {
+	sr25519::Pair::from_string(suri, None)
+		.map_err(|e| Error::Input(format!("Invalid secret URI: {e:?}")))
+}
+
+fn decode_hex(hex: &str) -> Result<Vec<u8>> {
+	sp_core::bytes::from_hex(hex).map_err(|e| Error::Input(format!("Invalid hex: {e}")))
+}
+
+fn print_keys(keys: &SessionKeys) {
+	let aura: &sr25519::Public = keys.aura.as_ref();
+	println!("Aura key:      {}", aura.to_ss58check());
+	println!("Session keys:  {}", to_hex(&keys.encode(), false));
+}
@@ -5,8 +5,15 @@
 mod chain_spec;
 mod cli;
 mod command;
+mod eth;
+mod indexer;
+mod keys;
+mod metadata;
+mod metrics;
 mod rpc;
 mod service;
+mod upgrade;
+mod validate_spec;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
@@ -0,0 +1,207 @@
+//! Metadata export and runtime summaries, computed by executing a runtime wasm without starting
+//! a node.
+
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use codec::{Decode, Encode};
+use frame_metadata::{v15::RuntimeMetadataV15, RuntimeMetadata, RuntimeMetadataPrefixed};
+use sc_cli::{Error, Result};
+use sc_executor::{RuntimeBlob, WasmExecutor};
+use scale_info::TypeDef;
+use sp_core::{bytes::to_hex, twox_128, OpaqueMetadata};
+use sp_version::RuntimeVersion;
+
+/// The runtime executed.
+#[derive(Debug, Clone, clap::Args)]
+pub struct RuntimeArgs {
+	/// The runtime wasm, possibly compressed. The runtime bundled with the node by default.
+	#[arg(long)]
+	pub wasm: Option<PathBuf>,
+}
+
+/// The encoding of the exported metadata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum MetadataFormat {
+	/// The SCALE encoded metadata, as returned by `state_getMetadata`.
+	Scale,
+	/// The metadata as JSON.
+	Json,
+}
+
+/// The `export-metadata` command.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct ExportMetadataCmd {
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub runtime: RuntimeArgs,
+
+	/// The metadata version, which the runtime must support.
+	#[arg(long, default_value_t = 15)]
+	pub metadata_version: u32,
+
+	/// The encoding of the metadata.
+	#[arg(long, value_enum, default_value_t = MetadataFormat::Json)]
+	pub format: MetadataFormat,
+
+	/// The file the metadata is written to, the standard output by default. SCALE metadata is
+	/// written as bytes to files and hex encoded to the standard output.
+	#[arg(long, short)]
+	pub output: Option<PathBuf>,
+}
+
+/// The `runtime-info` command.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct RuntimeInfoCmd {
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub runtime: RuntimeArgs,
+}
+
+impl ExportMetadataCmd {
+	/// Run the command.
+	pub fn run(&self) -> Result<()> {
+		let runtime = Runtime::load(&self.runtime)?;
+		let metadata = runtime.metadata(self.metadata_version)?;
+
+		match (self.format, &self.output) {
+			(MetadataFormat::Scale, Some(path)) => fs::write(path, &metadata)?,
+			(MetadataFormat::Scale, None) => println!("{}", to_hex(&metadata, false)),
+			(MetadataFormat::Json, output) => {
+				let metadata = RuntimeMetadataPrefixed::decode(&mut &metadata[..])
+					.map_err(|e| Error::Application(format!("Invalid metadata: {e}").into()))?;
+				let json = serde_json::to_string_pretty(&metadata)
+					.map_err(|e| Error::Application(Box::new(e)))?;
+				match output {
+					Some(path) => fs::write(path, json)?,
+					None => println!("{json}"),
+				}
+			},
+		}
+		Ok(())
+	}
+}
+
+impl RuntimeInfoCmd {
+	/// Run the command.
+	pub fn run(&self) -> Result<()> {
+		let runtime = Runtime::load(&self.runtime)?;
+		let version = runtime.version()?;
+		let metadata = runtime.metadata_v15()?;
+
+		println!("Runtime:              {} {}", version.spec_name, version.impl_name);
+		println!("Spec version:         {}", version.spec_version);
+		println!("Impl version:         {}", version.impl_version);
+		println!("Transaction version:  {}", version.transaction_version);
+		println!("State version:        {}", version.state_version);
+
+		println!("\nAPIs:");
+		for api in &metadata.apis {
+			let id = sp_core::blake2_64(api.name.as_bytes());
+			let api_version = version.api_version(&id).map_or("-".into(), |v| v.to_string());
+			println!("  {:<40} v{:<4} {} methods", api.name, api_version, api.methods.len());
+		}
+
+		let variants = |ty: Option<u32>| {
+			ty.and_then(|id| metadata.types.resolve(id))
+				.map_or(0, |ty| match &ty.type_def {
+					TypeDef::Variant(variant) => variant.variants.len(),
+					_ => 0,
+				})
+		};
+
+		println!("\nPallets:");
+		println!("  {:>5}  {:<28} {:>6} {:>8} {:>7} {:>7}", "index", "name", "calls", "storage", "events", "errors");
+		for pallet in &metadata.pallets {
+			println!(
+				"  {:>5}  {:<28} {:>6} {:>8} {:>7} {:>7}",
+				pallet.index,
+				pallet.name,
+				variants(pallet.calls.as_ref().map(|calls| calls.ty.id)),
+				pallet.storage.as_ref().map_or(0, |storage| storage.entries.len()),
+				variants(pallet.event.as_ref().map(|event| event.ty.id)),
+				variants(pallet.error.as_ref().map(|error| error.ty.id)),
+			);
+		}
+		Ok(())
+	}
+}
+
+/// A runtime wasm and the executor running it.
+pub(crate) struct Runtime {
+	code: Vec<u8>,
+	executor: WasmExecutor<sp_io::SubstrateHostFunctions>,
+}
+
+impl Runtime {
+	fn load(args: &RuntimeArgs) -> Result<Self> {
+		let code = match &args.wasm {
+			Some(path) => fs::read(path)?,
+			None => kine_runtime::WASM_BINARY
+				.ok_or_else(|| Error::Input("The node was built without a runtime wasm.".into()))?
+				.to_vec(),
+		};
+		Ok(Self::from_code(code))
+	}
+
+	pub(crate) fn from_code(code: Vec<u8>) -> Self {
+		Self { code, executor: WasmExecutor::builder().build() }
+	}
+
+	/// Call a runtime API function on an empty state. Host functions of parachains that these
+	/// functions do not use are stubbed.
+	fn call<R: Decode>(&self, method: &str, data: &[u8]) -> Result<R> {
+		let blob = RuntimeBlob::uncompress_if_needed(&self.code)
+			.map_err(|e| Error::Application(Box::new(e)))?;
+		let mut ext = sp_io::TestExternalities::default();
+		let result = self
+			.executor
+			.uncached_call(blob, &mut ext.ext(), true, method, data)
+			.map_err(|e| Error::Application(Box::new(e)))?;
+		R::decode(&mut &result[..])
+			.map_err(|e| Error::Application(format!("Invalid result of {method}: {e}").into()))
+	}
+
+	pub(crate) fn version(&self) -> Result<RuntimeVersion> {
+		self.call("Core_version", &[])
+	}
+
+	/// The SCALE encoded metadata at `version`.
+	fn metadata(&self, version: u32) -> Result<Vec<u8>> {
+		let metadata: Option<OpaqueMetadata> =
+			self.call("Metadata_metadata_at_version", &version.encode())?;
+		metadata.map(|metadata| metadata.to_vec()).ok_or_else(|| {
+			let supported: Vec<u32> = self.call("Metadata_metadata_versions", &[]).unwrap_or_default();
+			Error::Input(format!(
+				"The runtime does not support metadata v{version}, only {supported:?}."
+			))
+		})
+	}
+
+	pub(crate) fn metadata_v15(&self) -> Result<RuntimeMetadataV15> {
+		let metadata = RuntimeMetadataPrefixed::decode(&mut &self.metadata(15)?[..])
+			.map_err(|e| Error::Application(format!("Invalid metadata: {e}").into()))?;
+		match metadata.1 {
+			RuntimeMetadata::V15(metadata) => Ok(metadata),
+			_ => Err(Error::Input("The runtime does not support metadata v15.".into())),
+		}
+	}
+
+	/// The pallets with storage, by the `twox_128` hash their storage keys start with.
+	pub(crate) fn storage_prefixes(&self) -> Result<BTreeMap<[u8; 16], String>> {
+		Ok(self
+			.metadata_v15()?
+			.pallets
+			.into_iter()
+			.filter_map(|pallet| pallet.storage)
+			.map(|storage| (twox_128(storage.prefix.as_bytes()), storage.prefix))
+			.collect())
+	}
+}
+
+/// The name of the pallet a storage key belongs to, given the `prefixes` of the runtime.
+pub(crate) fn pallet_of<'a>(prefixes: &'a BTreeMap<[u8; 16], String>, key: &[u8]) -> &'a str {
+	key.get(..16)
+		.and_then(|prefix| prefixes.get(prefix))
+		.map(String::as_str)
+		.unwrap_or(if key.starts_with(b":") { "well known keys" } else { "unknown" })
+}
@@ -0,0 +1,359 @@
+//! Prometheus metrics of the parachain side of the node: how collation goes against the budgets
+//! the runtime gives a block, and the health of the chain as the relay chain sees it. They are
+//! served next to the Substrate metrics, under the `kinera_` prefix.
+
+use std::{sync::Arc, time::Instant};
+
+use codec::{Compact, Decode, Encode};
+use cumulus_client_consensus_proposer::{Error as ProposerError, ProposerInterface};
+use cumulus_primitives_core::{AggregateMessageOrigin, ParaId};
+use cumulus_primitives_parachain_inherent::ParachainInherentData;
+use cumulus_relay_chain_interface::RelayChainInterface;
+use futures::StreamExt;
+use kine_runtime::{opaque::Block, Hash, MAXIMUM_BLOCK_WEIGHT};
+use sc_client_api::{Backend, BlockchainEvents, StorageProvider};
+use sp_consensus::Proposal;
+use sp_consensus_aura::{digests::CompatibleDigestItem, sr25519::AuthoritySignature};
+use sp_core::{storage::StorageKey, twox_128};
+use sp_inherents::InherentData;
+use sp_runtime::{traits::Block as BlockT, Digest, DigestItem};
+use sp_state_machine::StorageProof;
+use sp_weights::constants::WEIGHT_REF_TIME_PER_SECOND;
+use substrate_prometheus_endpoint::{
+	exponential_buckets, register, Counter, Gauge, GaugeVec, Histogram, HistogramOpts, Opts,
+	PrometheusError, Registry, F64, U64,
+};
+
+const LOG_TARGET: &str = "metrics";
+
+/// The Kinera metrics of the node.
+#[derive(Clone)]
+pub struct Metrics {
+	collation_attempts: Counter<U64>,
+	collation_successes: Counter<U64>,
+	collation_slot: Gauge<U64>,
+	block_build_time: Histogram,
+	block_build_budget: Gauge<F64>,
+	pov_size: Histogram,
+	pov_budget: Gauge<F64>,
+	unincluded_segment_depth: Gauge<U64>,
+	xcmp_inbound_messages: GaugeVec<U64>,
+	xcmp_outbound_pages: GaugeVec<U64>,
+	relay_parent_lag: Gauge<U64>,
+}
+
+impl Metrics {
+	/// Register the metrics on `registry`.
+	pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		let budget = build_budget();
+		Ok(Self {
+			collation_attempts: register(
+				Counter::new(
+					"kinera_collation_attempts_total",
+					"Number of blocks this collator started to build in its slots",
+				)?,
+				registry,
+			)?,
+			collation_successes: register(
+				Counter::new(
+					"kinera_collation_successes_total",
+					"Number of blocks this collator built in its slots",
+				)?,
+				registry,
+			)?,
+			collation_slot: register(
+				Gauge::new(
+					"kinera_collation_slot",
+					"The last slot in which this collator built a block",
+				)?,
+				registry,
+			)?,
+			block_build_time: register(
+				Histogram::with_opts(
+					HistogramOpts::new(
+						"kinera_block_build_seconds",
+						"Time this collator took to build a block",
+					)
+					.buckets(
+						[0.1, 0.25, 0.5, 0.75, 0.9, 1.0, 1.5, 2.0]
+							.iter()
+							.map(|fraction| fraction * budget)
+							.collect(),
+					),
+				)?,
+				registry,
+			)?,
+			block_build_budget: register(
+				Gauge::new(
+					"kinera_block_build_budget_ratio",
+					"Time taken to build the last block over the compute time of a block",
+				)?,
+				registry,
+			)?,
+			pov_size: register(
+				Histogram::with_opts(
+					HistogramOpts::new(
+						"kinera_pov_size_bytes",
+						"Uncompressed size of the proofs of validity of the blocks this collator \
+						 built",
+					)
+					.buckets(exponential_buckets(16.0 * 1024.0, 2.0, 10)?),
+				)?,
+				registry,
+			)?,
+			pov_budget: register(
+				Gauge::new(
+					"kinera_pov_budget_ratio",
+					"Uncompressed size of the last proof of validity over the maximum size",
+				)?,
+				registry,
+			)?,
+			unincluded_segment_depth: register(
+				Gauge::new(
+					"kinera_unincluded_segment_depth",
+					"Number of blocks of the best chain not yet included in the relay chain",
+				)?,
+				registry,
+			)?,
+			xcmp_inbound_messages: register(
+				GaugeVec::new(
+					Opts::new(
+						"kinera_xcmp_inbound_messages",
+						"Number of messages from a sibling waiting to be processed",
+					),
+					&["sibling"],
+				)?,
+				registry,
+			)?,
+			xcmp_outbound_pages: register(
+				GaugeVec::new(
+					Opts::new(
+						"kinera_xcmp_outbound_pages",
+						"Number of pages of messages to a sibling waiting to be sent",
+					),
+					&["recipient"],
+				)?,
+				registry,
+			)?,
+			relay_parent_lag: register(
+				Gauge::new(
+					"kinera_relay_parent_lag_blocks",
+					"Number of relay chain blocks between the relay parent of the best block and \
+					 the best relay chain block",
+				)?,
+				registry,
+			)?,
+		})
+	}
+
+	/// Follow the best blocks of the parachain and of the relay chain to keep the chain metrics
+	/// up to date.
+	pub async fn run<C, BE>(
+		self,
+		client: Arc<C>,
+		relay_chain_interface: Arc<dyn RelayChainInterface>,
+	) where
+		C: BlockchainEvents<Block> + StorageProvider<Block, BE> + Send + Sync + 'static,
+		BE: Backend<Block> + 'static,
+	{
+		let mut imports = client.import_notification_stream().fuse();
+		let mut relay_imports = match relay_chain_interface.import_notification_stream().await {
+			Ok(stream) => stream.fuse(),
+			Err(e) => {
+				log::error!(target: LOG_TARGET, "Following the relay chain failed: {e}");
+				return
+			},
+		};
+		let mut relay_best = None;
+		let mut relay_parent = None;
+
+		loop {
+			futures::select! {
+				notification = imports.next() => match notification {
+					Some(notification) if notification.is_new_best => {
+						relay_parent = self.on_best_block(&*client, notification.hash);
+					},
+					Some(_) => {},
+					None => break,
+				},
+				header = relay_imports.next() => match header {
+					Some(header) => relay_best = relay_best.max(Some(header.number)),
+					None => break,
+				},
+			}
+			if let (Some(best), Some(parent)) = (relay_best, relay_parent) {
+				self.relay_parent_lag.set(best.saturating_sub(parent).into());
+			}
+		}
+	}
+
+	/// Read the chain metrics from the state of the new best block, returning the number of its
+	/// relay parent.
+	fn on_best_block<C, BE>(&self, client: &C, hash: Hash) -> Option<u32>
+	where
+		C: StorageProvider<Block, BE>,
+		BE: Backend<Block>,
+	{
+		// Only the length of the segment is needed, not its blocks.
+		let segment = value_key(b"ParachainSystem", b"UnincludedSegment");
+		if let Some(Compact(depth)) = read::<_, _, Compact<u32>>(client, hash, segment) {
+			self.unincluded_segment_depth.set(depth.into());
+		}
+
+		self.xcmp_inbound_messages.reset();
+		let prefix = StorageKey(value_key(b"MessageQueue", b"BookStateFor"));
+		match client.storage_keys(hash, Some(&prefix), None) {
+			Ok(keys) => {
+				for key in keys {
+					// The origin follows the `Twox64Concat` hash of the key.
+					let origin = key.0.get(prefix.0.len() + 8..);
+					let Some(AggregateMessageOrigin::Sibling(sibling)) =
+						origin.and_then(|mut origin| Decode::decode(&mut origin).ok())
+					else {
+						continue
+					};
+					if let Some(book) = read::<_, _, BookState>(client, hash, key.0) {
+						self.xcmp_inbound_messages
+							.with_label_values(&[&u32::from(sibling).to_string()])
+							.set(book.message_count);
+					}
+				}
+			},
+			Err(e) => log::debug!(target: LOG_TARGET, "Reading the inbound queues failed: {e}"),
+		}
+
+		self.xcmp_outbound_pages.reset();
+		let channels = read::<_, _, Vec<OutboundChannel>>(
+			client,
+			hash,
+			value_key(b"XcmpQueue", b"OutboundXcmpStatus"),
+		);
+		for channel in channels.unwrap_or_default() {
+			self.xcmp_outbound_pages
+				.with_label_values(&[&u32::from(channel.recipient).to_string()])
+				.set(channel.last_index.saturating_sub(channel.first_index).into());
+		}
+
+		read(client, hash, value_key(b"ParachainSystem", b"LastRelayChainBlockNumber"))
+	}
+}
+
+/// The compute time of a block in seconds.
+fn build_budget() -> f64 {
+	MAXIMUM_BLOCK_WEIGHT.ref_time() as f64 / WEIGHT_REF_TIME_PER_SECOND as f64
+}
+
+fn value_key(pallet: &[u8], item: &[u8]) -> Vec<u8> {
+	[twox_128(pallet), twox_128(item)].concat()
+}
+
+fn read<C, BE, T: Decode>(client: &C, hash: Hash, key: Vec<u8>) -> Option<T>
+where
+	C: StorageProvider<Block, BE>,
+	BE: Backend<Block>,
+{
+	match client.storage(hash, &StorageKey(key)) {
+		Ok(data) => data.and_then(|data| T::decode(&mut &data.0[..]).ok()),
+		Err(e) => {
+			log::debug!(target: LOG_TARGET, "Reading the state of {hash} failed: {e}");
+			None
+		},
+	}
+}
+
+/// The encoding of the `BookState` of `pallet-message-queue`, which does not export its fields.
+#[derive(Decode)]
+#[allow(dead_code)]
+struct BookState {
+	begin: u32,
+	end: u32,
+	count: u32,
+	ready_neighbours: Option<(AggregateMessageOrigin, AggregateMessageOrigin)>,
+	message_count: u64,
+	size: u64,
+}
+
+/// The encoding of the `OutboundChannelDetails` of `cumulus-pallet-xcmp-queue`, which does not
+/// export its fields. Its `OutboundState` encodes like a `bool`, `Suspended` being `true`.
+#[derive(Decode)]
+#[allow(dead_code)]
+struct OutboundChannel {
+	recipient: ParaId,
+	suspended: bool,
+	signals_exist: bool,
+	first_index: u16,
+	last_index: u16,
+}
+
+/// A proposer recording the [`Metrics`] of the blocks it builds.
+pub struct MeteredProposer<P> {
+	inner: P,
+	metrics: Option<Metrics>,
+}
+
+impl<P> MeteredProposer<P> {
+	/// Wrap `inner`, which records nothing without `metrics`.
+	pub fn new(inner: P, metrics: Option<Metrics>) -> Self {
+		Self { inner, metrics }
+	}
+}
+
+#[async_trait::async_trait]
+impl<P> ProposerInterface<Block> for MeteredProposer<P>
+where
+	P: ProposerInterface<Block> + Send,
+{
+	async fn propose(
+		&mut self,
+		parent_header: &<Block as BlockT>::Header,
+		paras_inherent_data: &ParachainInherentData,
+		other_inherent_data: InherentData,
+		inherent_digests: Digest,
+		max_duration: std::time::Duration,
+		block_size_limit: Option<usize>,
+	) -> Result<Proposal<Block, StorageProof>, ProposerError> {
+		let Some(metrics) = self.metrics.clone() else {
+			return self
+				.inner
+				.propose(
+					parent_header,
+					paras_inherent_data,
+					other_inherent_data,
+					inherent_digests,
+					max_duration,
+					block_size_limit,
+				)
+				.await
+		};
+
+		metrics.collation_attempts.inc();
+		if let Some(slot) = inherent_digests.logs().iter().find_map(|item| {
+			<DigestItem as CompatibleDigestItem<AuthoritySignature>>::as_aura_pre_digest(item)
+		}) {
+			metrics.collation_slot.set(*slot);
+		}
+
+		let started = Instant::now();
+		let proposal = self
+			.inner
+			.propose(
+				parent_header,
+				paras_inherent_data,
+				other_inherent_data,
+				inherent_digests,
+				max_duration,
+				block_size_limit,
+			)
+			.await?;
+		let elapsed = started.elapsed().as_secs_f64();
+
+		metrics.collation_successes.inc();
+		metrics.block_build_time.observe(elapsed);
+		metrics.block_build_budget.set(elapsed / build_budget());
+		let pov_size = (proposal.block.encoded_size() + proposal.proof.encoded_size()) as f64;
+		metrics.pov_size.observe(pov_size);
+		metrics.pov_budget.set(pov_size / MAXIMUM_BLOCK_WEIGHT.proof_size() as f64);
+
+		Ok(proposal)
+	}
+}
@@ -5,52 +5,95 @@
 
 #![warn(missing_docs)]
 
+pub mod blobs;
+pub mod contracts;
+pub mod eth;
+pub mod indexer;
+
 use std::sync::Arc;
 
-use kine_runtime::{opaque::Block, AccountId, Balance, Nonce};
+use fc_mapping_sync::{EthereumBlockNotification, EthereumBlockNotificationSinks};
+use kine_runtime::{opaque::Block, AccountId, Balance, BlockNumber, EventRecord, Hash, Nonce};
 
+use sc_client_api::{backend::Backend, AuxStore, BlockchainEvents, StorageProvider, UsageProvider};
 pub use sc_rpc::DenyUnsafe;
+use sc_rpc::SubscriptionTaskExecutor;
+use sc_transaction_pool::ChainApi;
 use sc_transaction_pool_api::TransactionPool;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{CallApiAt, ProvideRuntimeApi};
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use sp_inherents::CreateInherentDataProviders;
+
+use eth::EthDeps;
 
 /// A type representing all RPC extensions.
 pub type RpcExtension = jsonrpsee::RpcModule<()>;
 
 /// Full client dependencies
-pub struct FullDeps<C, P> {
+pub struct FullDeps<C, P, BE, A: ChainApi, CIDP> {
 	/// The client instance to use.
 	pub client: Arc<C>,
+	/// The backend instance to use.
+	pub backend: Arc<BE>,
 	/// Transaction pool instance.
 	pub pool: Arc<P>,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
+	/// Ethereum RPC dependencies.
+	pub eth: EthDeps<C, P, A, CIDP>,
+	/// The index of events and extrinsics, when the node keeps one.
+	pub indexer: Option<crate::indexer::Indexer>,
 }
 
 /// Instantiate all RPC extensions.
-pub fn create_full<C, P>(
-	deps: FullDeps<C, P>,
+pub fn create_full<C, P, BE, A, CIDP>(
+	deps: FullDeps<C, P, BE, A, CIDP>,
+	subscription_task_executor: SubscriptionTaskExecutor,
+	pubsub_notification_sinks: Arc<EthereumBlockNotificationSinks<EthereumBlockNotification<Block>>>,
 ) -> Result<RpcExtension, Box<dyn std::error::Error + Send + Sync>>
 where
 	C: ProvideRuntimeApi<Block>
+		+ CallApiAt<Block>
 		+ HeaderBackend<Block>
 		+ HeaderMetadata<Block, Error = BlockChainError>
+		+ BlockchainEvents<Block>
+		+ AuxStore
+		+ UsageProvider<Block>
+		+ StorageProvider<Block, BE>
 		+ Send
 		+ Sync
 		+ 'static,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
+	C::Api: pallet_contracts::ContractsApi<Block, AccountId, Balance, BlockNumber, Hash, EventRecord>,
+	C::Api: fp_rpc::ConvertTransactionRuntimeApi<Block>,
+	C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
 	C::Api: BlockBuilder<Block>,
-	P: TransactionPool + Sync + Send + 'static,
+	BE: Backend<Block> + 'static,
+	BE::OffchainStorage: 'static,
+	P: TransactionPool<Block = Block> + Sync + Send + 'static,
+	A: ChainApi<Block = Block> + 'static,
+	CIDP: CreateInherentDataProviders<Block, ()> + Send + 'static,
 {
+	use blobs::{Blobs, BlobsApiServer};
+	use contracts::{Contracts, ContractsApiServer};
+	use indexer::{Indexer, IndexerApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 
 	let mut module = RpcExtension::new(());
-	let FullDeps { client, pool, deny_unsafe } = deps;
+	let FullDeps { client, backend, pool, deny_unsafe, eth, indexer } = deps;
 
 	module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
-	module.merge(TransactionPayment::new(client).into_rpc())?;
-	Ok(module)
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(Contracts::new(client).into_rpc())?;
+	if let Some(storage) = backend.offchain_storage() {
+		module.merge(Blobs::new(storage).into_rpc())?;
+	}
+	if let Some(index) = indexer {
+		module.merge(Indexer::new(index, deny_unsafe).into_rpc())?;
+	}
+
+	eth::create_eth(module, eth, subscription_task_executor, pubsub_notification_sinks)
 }
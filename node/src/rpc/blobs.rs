@@ -0,0 +1,39 @@
+//! An RPC method serving the blobs the runtime writes to the offchain index, such as the
+//! synopses and subtitles of films and the long texts of reviews and comments, whose hash only
+//! is kept on chain. The node only writes them when it runs with
+//! `--enable-offchain-indexing true`.
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use kine_runtime::Hash;
+use sp_core::{offchain::OffchainStorage, Bytes};
+
+/// Blobs RPC methods.
+#[rpc(client, server)]
+pub trait BlobsApi {
+	/// The blob with `hash`, if this node indexed it. Nodes only index the blobs of the blocks
+	/// they import with offchain indexing enabled.
+	#[method(name = "blobs_get")]
+	fn get(&self, hash: Hash) -> RpcResult<Option<Bytes>>;
+}
+
+/// Serves [`BlobsApiServer`] from the offchain database of the node.
+pub struct Blobs<S> {
+	storage: S,
+}
+
+impl<S> Blobs<S> {
+	/// Create the RPC handler for the offchain `storage`.
+	pub fn new(storage: S) -> Self {
+		Self { storage }
+	}
+}
+
+impl<S> BlobsApiServer for Blobs<S>
+where
+	S: OffchainStorage + 'static,
+{
+	fn get(&self, hash: Hash) -> RpcResult<Option<Bytes>> {
+		let key = kine_films::blob_key(hash.as_bytes());
+		Ok(self.storage.get(sp_offchain::STORAGE_PREFIX, &key).map(Into::into))
+	}
+}
@@ -0,0 +1,293 @@
+//! RPC methods to dry run contract calls, instantiations and uploads, and to read contract
+//! storage, on top of the `ContractsApi` runtime API.
+
+use std::sync::Arc;
+
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use kine_runtime::{opaque::Block, AccountId, Balance, BlockNumber, EventRecord, Hash};
+use pallet_contracts::{
+	Code, CodeUploadReturnValue, ContractExecResult, ContractsApi as ContractsRuntimeApi, Determinism,
+	StorageDeposit,
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_rpc::number::NumberOrHex;
+use sp_weights::Weight;
+
+/// The runtime API could not be called.
+const RUNTIME_ERROR: i32 = 1;
+/// A number did not fit in a balance.
+const INVALID_PARAMS: i32 = 2;
+
+/// A contract call to dry run.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CallRequest {
+	origin: AccountId,
+	dest: AccountId,
+	value: NumberOrHex,
+	gas_limit: Option<Weight>,
+	storage_deposit_limit: Option<NumberOrHex>,
+	input_data: Bytes,
+}
+
+/// The code of a contract to instantiate, new or already on chain.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub enum InstantiateCode {
+	/// Wasm code uploaded along with the instantiation.
+	Upload(Bytes),
+	/// The hash of code already stored on chain.
+	Existing(Hash),
+}
+
+/// A contract instantiation to dry run.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct InstantiateRequest {
+	origin: AccountId,
+	value: NumberOrHex,
+	gas_limit: Option<Weight>,
+	storage_deposit_limit: Option<NumberOrHex>,
+	code: InstantiateCode,
+	data: Bytes,
+	salt: Bytes,
+}
+
+/// A code upload to dry run.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CodeUploadRequest {
+	origin: AccountId,
+	code: Bytes,
+	storage_deposit_limit: Option<NumberOrHex>,
+	#[serde(default)]
+	relaxed_determinism: bool,
+}
+
+/// The storage deposit charged to, or refunded to, the origin.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Deposit {
+	/// The origin pays the deposit.
+	Charge(NumberOrHex),
+	/// The origin gets the deposit back.
+	Refund(NumberOrHex),
+}
+
+impl From<StorageDeposit<Balance>> for Deposit {
+	fn from(deposit: StorageDeposit<Balance>) -> Self {
+		match deposit {
+			StorageDeposit::Charge(amount) => Deposit::Charge(amount.into()),
+			StorageDeposit::Refund(amount) => Deposit::Refund(amount.into()),
+		}
+	}
+}
+
+/// What a contract returned.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnValue {
+	flags: u32,
+	data: Bytes,
+}
+
+/// The outcome of a dry run call.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallResponse {
+	gas_consumed: Weight,
+	gas_required: Weight,
+	storage_deposit: Deposit,
+	debug_message: String,
+	result: Result<ReturnValue, String>,
+}
+
+/// The outcome of a dry run instantiation.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstantiateResponse {
+	gas_consumed: Weight,
+	gas_required: Weight,
+	storage_deposit: Deposit,
+	debug_message: String,
+	result: Result<(AccountId, ReturnValue), String>,
+}
+
+/// The outcome of a dry run code upload.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeUploadResponse {
+	code_hash: Hash,
+	deposit: NumberOrHex,
+}
+
+/// Contracts RPC methods.
+#[rpc(client, server)]
+pub trait ContractsApi {
+	/// Dry run a call to a contract, at the best block or at `at`.
+	#[method(name = "contracts_call")]
+	fn call(&self, request: CallRequest, at: Option<Hash>) -> RpcResult<CallResponse>;
+
+	/// Dry run the instantiation of a contract.
+	#[method(name = "contracts_instantiate")]
+	fn instantiate(&self, request: InstantiateRequest, at: Option<Hash>) -> RpcResult<InstantiateResponse>;
+
+	/// Dry run the upload of contract code.
+	#[method(name = "contracts_uploadCode")]
+	fn upload_code(&self, request: CodeUploadRequest, at: Option<Hash>) -> RpcResult<Result<CodeUploadResponse, String>>;
+
+	/// Read a value from the storage of a contract.
+	#[method(name = "contracts_getStorage")]
+	fn get_storage(&self, address: AccountId, key: Bytes, at: Option<Hash>) -> RpcResult<Option<Bytes>>;
+}
+
+/// Serves [`ContractsApiServer`] from the runtime of a client.
+pub struct Contracts<C> {
+	client: Arc<C>,
+}
+
+impl<C> Contracts<C> {
+	/// Create the RPC handler for `client`.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+fn runtime_error(error: impl std::fmt::Debug) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(RUNTIME_ERROR, "Runtime error", Some(format!("{:?}", error)))).into()
+}
+
+fn balance(number: NumberOrHex) -> RpcResult<Balance> {
+	number.try_into().map_err(|_| {
+		CallError::Custom(ErrorObject::owned(INVALID_PARAMS, "Number does not fit in a balance", None::<()>))
+			.into()
+	})
+}
+
+fn optional_balance(number: Option<NumberOrHex>) -> RpcResult<Option<Balance>> {
+	number.map(balance).transpose()
+}
+
+fn debug_message(message: Vec<u8>) -> String {
+	String::from_utf8_lossy(&message).into_owned()
+}
+
+impl<C> Contracts<C>
+where
+	C: HeaderBackend<Block>,
+{
+	fn at(&self, at: Option<Hash>) -> Hash {
+		at.unwrap_or_else(|| self.client.info().best_hash)
+	}
+}
+
+impl<C> ContractsApiServer for Contracts<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash, EventRecord>,
+{
+	fn call(&self, request: CallRequest, at: Option<Hash>) -> RpcResult<CallResponse> {
+		let CallRequest { origin, dest, value, gas_limit, storage_deposit_limit, input_data } = request;
+
+		let ContractExecResult { gas_consumed, gas_required, storage_deposit, debug_message: message, result, .. } =
+			self.client
+				.runtime_api()
+				.call(
+					self.at(at),
+					origin,
+					dest,
+					balance(value)?,
+					gas_limit,
+					optional_balance(storage_deposit_limit)?,
+					input_data.to_vec(),
+				)
+				.map_err(runtime_error)?;
+
+		Ok(CallResponse {
+			gas_consumed,
+			gas_required,
+			storage_deposit: storage_deposit.into(),
+			debug_message: debug_message(message),
+			result: result
+				.map(|value| ReturnValue { flags: value.flags.bits(), data: value.data.into() })
+				.map_err(|error| format!("{:?}", error)),
+		})
+	}
+
+	fn instantiate(&self, request: InstantiateRequest, at: Option<Hash>) -> RpcResult<InstantiateResponse> {
+		let InstantiateRequest { origin, value, gas_limit, storage_deposit_limit, code, data, salt } = request;
+		let code = match code {
+			InstantiateCode::Upload(code) => Code::Upload(code.to_vec()),
+			InstantiateCode::Existing(hash) => Code::Existing(hash),
+		};
+
+		let outcome = self
+			.client
+			.runtime_api()
+			.instantiate(
+				self.at(at),
+				origin,
+				balance(value)?,
+				gas_limit,
+				optional_balance(storage_deposit_limit)?,
+				code,
+				data.to_vec(),
+				salt.to_vec(),
+			)
+			.map_err(runtime_error)?;
+
+		Ok(InstantiateResponse {
+			gas_consumed: outcome.gas_consumed,
+			gas_required: outcome.gas_required,
+			storage_deposit: outcome.storage_deposit.into(),
+			debug_message: debug_message(outcome.debug_message),
+			result: outcome
+				.result
+				.map(|value| {
+					let returned = ReturnValue { flags: value.result.flags.bits(), data: value.result.data.into() };
+					(value.account_id, returned)
+				})
+				.map_err(|error| format!("{:?}", error)),
+		})
+	}
+
+	fn upload_code(
+		&self,
+		request: CodeUploadRequest,
+		at: Option<Hash>,
+	) -> RpcResult<Result<CodeUploadResponse, String>> {
+		let CodeUploadRequest { origin, code, storage_deposit_limit, relaxed_determinism } = request;
+		let determinism = if relaxed_determinism { Determinism::Relaxed } else { Determinism::Enforced };
+
+		let outcome = self
+			.client
+			.runtime_api()
+			.upload_code(self.at(at), origin, code.to_vec(), optional_balance(storage_deposit_limit)?, determinism)
+			.map_err(runtime_error)?;
+
+		Ok(outcome
+			.map(|CodeUploadReturnValue { code_hash, deposit }| CodeUploadResponse {
+				code_hash,
+				deposit: deposit.into(),
+			})
+			.map_err(|error| format!("{:?}", error)))
+	}
+
+	fn get_storage(&self, address: AccountId, key: Bytes, at: Option<Hash>) -> RpcResult<Option<Bytes>> {
+		let value = self
+			.client
+			.runtime_api()
+			.get_storage(self.at(at), address, key.to_vec())
+			.map_err(runtime_error)?
+			.map_err(runtime_error)?;
+
+		Ok(value.map(Into::into))
+	}
+}
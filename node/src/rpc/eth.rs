@@ -0,0 +1,204 @@
+//! The Ethereum `eth_`, `net_` and `web3_` RPC methods, served by Frontier on top of the
+//! `EthereumRuntimeRPCApi` runtime API.
+
+use std::sync::Arc;
+
+use fc_mapping_sync::{EthereumBlockNotification, EthereumBlockNotificationSinks};
+use fc_rpc::{pending::ConsensusDataProvider, EthBlockDataCacheTask, EthConfig, OverrideHandle};
+use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool};
+use jsonrpsee::RpcModule;
+use kine_runtime::{opaque::Block, Hash, TransactionConverter};
+use sc_client_api::{backend::Backend, AuxStore, BlockchainEvents, StorageProvider, UsageProvider};
+use sc_network::NetworkService;
+use sc_network_sync::SyncingService;
+use sc_rpc::SubscriptionTaskExecutor;
+use sc_transaction_pool::{ChainApi, Pool};
+use sc_transaction_pool_api::TransactionPool;
+use sp_api::{CallApiAt, ProvideRuntimeApi};
+use sp_block_builder::BlockBuilder;
+use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use sp_inherents::CreateInherentDataProviders;
+
+/// Dependencies of the Ethereum RPC.
+pub struct EthDeps<C, P, A: ChainApi, CIDP> {
+	/// The client instance to use.
+	pub client: Arc<C>,
+	/// Transaction pool instance.
+	pub pool: Arc<P>,
+	/// Graph pool instance.
+	pub graph: Arc<Pool<A>>,
+	/// Whether `eth_accounts` signs with the development keys.
+	pub enable_dev_signer: bool,
+	/// Network service.
+	pub network: Arc<NetworkService<Block, Hash>>,
+	/// Chain syncing service.
+	pub sync: Arc<SyncingService<Block>>,
+	/// The Ethereum mapping database.
+	pub frontier_backend: Arc<dyn fc_api::Backend<Block>>,
+	/// Reads the Ethereum data of the runtime straight from storage.
+	pub overrides: Arc<OverrideHandle<Block>>,
+	/// Cache of Ethereum block data.
+	pub block_data_cache: Arc<EthBlockDataCacheTask<Block>>,
+	/// The active filters of `eth_newFilter`.
+	pub filter_pool: Option<FilterPool>,
+	/// Maximum number of logs in a query.
+	pub max_past_logs: u32,
+	/// The base fees and rewards of recent blocks.
+	pub fee_history_cache: FeeHistoryCache,
+	/// How many blocks `fee_history_cache` holds.
+	pub fee_history_cache_limit: FeeHistoryCacheLimit,
+	/// Multiplier of the block gas limit allowed for `eth_call` and `eth_estimateGas`.
+	pub execute_gas_limit_multiplier: u64,
+	/// Maximum number of stored filters.
+	pub max_stored_filters: usize,
+	/// Creates the inherents of the pending block.
+	pub pending_create_inherent_data_providers: CIDP,
+}
+
+/// Reads account data through the storage layout of `frame_system` with 32 byte accounts.
+pub struct DefaultEthConfig<C, BE>(std::marker::PhantomData<(C, BE)>);
+
+impl<C, BE> EthConfig<Block, C> for DefaultEthConfig<C, BE>
+where
+	C: StorageProvider<Block, BE> + Sync + Send + 'static,
+	BE: Backend<Block> + 'static,
+{
+	type EstimateGasAdapter = ();
+	type RuntimeStorageOverride =
+		fc_rpc::frontier_backend_client::SystemAccountId32StorageOverride<Block, C, BE>;
+}
+
+/// Merge the Ethereum RPC methods into `io`.
+pub fn create_eth<C, BE, P, A, CIDP>(
+	mut io: RpcModule<()>,
+	deps: EthDeps<C, P, A, CIDP>,
+	subscription_task_executor: SubscriptionTaskExecutor,
+	pubsub_notification_sinks: Arc<EthereumBlockNotificationSinks<EthereumBlockNotification<Block>>>,
+) -> Result<RpcModule<()>, Box<dyn std::error::Error + Send + Sync>>
+where
+	C: ProvideRuntimeApi<Block>
+		+ CallApiAt<Block>
+		+ HeaderBackend<Block>
+		+ HeaderMetadata<Block, Error = BlockChainError>
+		+ BlockchainEvents<Block>
+		+ AuxStore
+		+ UsageProvider<Block>
+		+ StorageProvider<Block, BE>
+		+ Send
+		+ Sync
+		+ 'static,
+	C::Api: BlockBuilder<Block>
+		+ fp_rpc::ConvertTransactionRuntimeApi<Block>
+		+ fp_rpc::EthereumRuntimeRPCApi<Block>,
+	BE: Backend<Block> + 'static,
+	P: TransactionPool<Block = Block> + 'static,
+	A: ChainApi<Block = Block> + 'static,
+	CIDP: CreateInherentDataProviders<Block, ()> + Send + 'static,
+{
+	use fc_rpc::{
+		Eth, EthApiServer, EthDevSigner, EthFilter, EthFilterApiServer, EthPubSub,
+		EthPubSubApiServer, EthSigner, Net, NetApiServer, Web3, Web3ApiServer,
+	};
+
+	let EthDeps {
+		client,
+		pool,
+		graph,
+		enable_dev_signer,
+		network,
+		sync,
+		frontier_backend,
+		overrides,
+		block_data_cache,
+		filter_pool,
+		max_past_logs,
+		fee_history_cache,
+		fee_history_cache_limit,
+		execute_gas_limit_multiplier,
+		max_stored_filters,
+		pending_create_inherent_data_providers,
+	} = deps;
+
+	let mut signers = Vec::new();
+	if enable_dev_signer {
+		signers.push(Box::new(EthDevSigner::new()) as Box<dyn EthSigner>);
+	}
+
+	io.merge(
+		Eth::<Block, C, P, _, BE, A, CIDP, ()>::new(
+			client.clone(),
+			pool.clone(),
+			graph.clone(),
+			Some(TransactionConverter::<Block>::default()),
+			sync.clone(),
+			signers,
+			overrides.clone(),
+			frontier_backend.clone(),
+			// Collators author through Aura, not through the Ethereum RPC.
+			false,
+			block_data_cache.clone(),
+			fee_history_cache,
+			fee_history_cache_limit,
+			execute_gas_limit_multiplier,
+			None::<Arc<dyn ConsensusDataProvider<Block>>>,
+			pending_create_inherent_data_providers,
+			None,
+		)
+		.replace_config::<DefaultEthConfig<C, BE>>()
+		.into_rpc(),
+	)?;
+
+	if let Some(filter_pool) = filter_pool {
+		io.merge(
+			EthFilter::new(
+				client.clone(),
+				frontier_backend,
+				graph.clone(),
+				filter_pool,
+				max_stored_filters,
+				max_past_logs,
+				block_data_cache,
+			)
+			.into_rpc(),
+		)?;
+	}
+
+	io.merge(
+		EthPubSub::new(
+			pool,
+			client.clone(),
+			sync,
+			subscription_task_executor,
+			overrides,
+			pubsub_notification_sinks,
+		)
+		.into_rpc(),
+	)?;
+
+	io.merge(
+		Net::new(
+			client.clone(),
+			network,
+			// Report the peer count as a number, like Ethereum clients do.
+			true,
+		)
+		.into_rpc(),
+	)?;
+
+	io.merge(Web3::new(client).into_rpc())?;
+
+	Ok(io)
+}
+
+/// The storage overrides of the runtime, keyed by the schema version they read.
+pub fn overrides_handle<C, BE>(client: Arc<C>) -> Arc<OverrideHandle<Block>>
+where
+	C: ProvideRuntimeApi<Block> + StorageProvider<Block, BE> + AuxStore,
+	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
+	C: Send + Sync + 'static,
+	C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
+	BE: Backend<Block> + 'static,
+{
+	fc_storage::overrides_handle(client)
+}
+
@@ -0,0 +1,69 @@
+//! RPC methods querying the events and extrinsics in the index of the node, when it runs with
+//! `--indexer-db`. Queries scan the database, so they are unsafe methods, and run on blocking
+//! threads like every read of the index.
+
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sc_rpc::DenyUnsafe;
+
+use crate::indexer::{
+	EventQuery, ExtrinsicQuery, IndexedEvent, IndexedExtrinsic, Indexer as Index, IndexerStatus,
+};
+
+/// The index could not be read.
+const INDEX_ERROR: i32 = 1;
+
+/// Indexer RPC methods.
+#[rpc(client, server)]
+pub trait IndexerApi {
+	/// The events of the canonical chain matching `query`, 100 by default and 1000 at most.
+	/// Unsafe.
+	#[method(name = "indexer_events", blocking)]
+	fn events(&self, query: EventQuery) -> RpcResult<Vec<IndexedEvent>>;
+
+	/// The extrinsics of the canonical chain matching `query`, 100 by default and 1000 at most.
+	/// Unsafe.
+	#[method(name = "indexer_extrinsics", blocking)]
+	fn extrinsics(&self, query: ExtrinsicQuery) -> RpcResult<Vec<IndexedExtrinsic>>;
+
+	/// The best and finalized blocks in the index.
+	#[method(name = "indexer_status", blocking)]
+	fn status(&self) -> RpcResult<IndexerStatus>;
+}
+
+/// Serves [`IndexerApiServer`] from the index of the node.
+pub struct Indexer {
+	index: Index,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl Indexer {
+	/// Create the RPC handler for `index`.
+	pub fn new(index: Index, deny_unsafe: DenyUnsafe) -> Self {
+		Self { index, deny_unsafe }
+	}
+}
+
+fn index_error(error: rusqlite::Error) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(INDEX_ERROR, "Index error", Some(error.to_string())))
+		.into()
+}
+
+impl IndexerApiServer for Indexer {
+	fn events(&self, query: EventQuery) -> RpcResult<Vec<IndexedEvent>> {
+		self.deny_unsafe.check_if_safe()?;
+		self.index.events(&query).map_err(index_error)
+	}
+
+	fn extrinsics(&self, query: ExtrinsicQuery) -> RpcResult<Vec<IndexedExtrinsic>> {
+		self.deny_unsafe.check_if_safe()?;
+		self.index.extrinsics(&query).map_err(index_error)
+	}
+
+	fn status(&self) -> RpcResult<IndexerStatus> {
+		self.index.status().map_err(index_error)
+	}
+}
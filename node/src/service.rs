@@ -12,6 +12,7 @@ use kine_runtime::{
 
 // Cumulus Imports
 use cumulus_client_collator::service::CollatorService;
+use cumulus_client_parachain_inherent::{MockValidationDataInherentDataProvider, MockXcmConfig};
 use cumulus_client_consensus_common::ParachainBlockImport as TParachainBlockImport;
 use cumulus_client_consensus_proposer::Proposer;
 use cumulus_client_service::{
@@ -23,7 +24,8 @@ use cumulus_relay_chain_interface::{OverseerHandle, RelayChainInterface};
 
 // Substrate Imports
 use frame_benchmarking_cli::SUBSTRATE_REFERENCE_HARDWARE;
-use sc_client_api::Backend;
+use codec::Decode;
+use sc_client_api::{Backend, StorageProvider};
 use sc_consensus::ImportQueue;
 use sc_executor::{
 	HeapAllocStrategy, NativeElseWasmExecutor, WasmExecutor, DEFAULT_HEAP_ALLOC_STRATEGY,
@@ -33,9 +35,16 @@ use sc_network_sync::SyncingService;
 use sc_service::{Configuration, PartialComponents, TFullBackend, TFullClient, TaskManager};
 use sc_telemetry::{Telemetry, TelemetryHandle, TelemetryWorker, TelemetryWorkerHandle};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
+use sp_core::{storage::StorageKey, twox_128};
 use sp_keystore::KeystorePtr;
 use substrate_prometheus_endpoint::Registry;
 
+use crate::{
+	eth::{open_frontier_backend, spawn_frontier_tasks, EthConfiguration, FrontierPartialComponents},
+	indexer::{Indexer, IndexerConfiguration},
+	metrics::{MeteredProposer, Metrics},
+};
+
 /// Native executor type.
 pub struct ParachainNativeExecutor;
 
@@ -154,6 +163,9 @@ async fn start_node_impl(
 	collator_options: CollatorOptions,
 	para_id: ParaId,
 	hwbench: Option<sc_sysinfo::HwBench>,
+	eth_config: EthConfiguration,
+	indexer_config: IndexerConfiguration,
+	ipfs_gateway: Option<String>,
 ) -> sc_service::error::Result<(TaskManager, Arc<ParachainClient>)> {
 	let parachain_config = prepare_node_config(parachain_config);
 
@@ -165,6 +177,29 @@ async fn start_node_impl(
 	let backend = params.backend.clone();
 	let mut task_manager = params.task_manager;
 
+	let frontier_backend = Arc::new(open_frontier_backend(client.clone(), &parachain_config)?);
+	let frontier_components = FrontierPartialComponents::new(&eth_config);
+	let overrides = crate::rpc::eth::overrides_handle(client.clone());
+	let block_data_cache = Arc::new(fc_rpc::EthBlockDataCacheTask::new(
+		task_manager.spawn_handle(),
+		overrides.clone(),
+		eth_config.eth_log_block_cache,
+		eth_config.eth_statuses_cache,
+		parachain_config.prometheus_registry().cloned(),
+	));
+	// Notified by the mapping sync worker, read by `eth_subscribe`.
+	let pubsub_notification_sinks: fc_mapping_sync::EthereumBlockNotificationSinks<
+		fc_mapping_sync::EthereumBlockNotification<Block>,
+	> = Default::default();
+	let pubsub_notification_sinks = Arc::new(pubsub_notification_sinks);
+
+	let indexer = indexer_config
+		.indexer_db
+		.as_deref()
+		.map(Indexer::open)
+		.transpose()
+		.map_err(|e| sc_service::Error::Application(Box::new(e)))?;
+
 	let (relay_chain_interface, collator_key) = build_relay_chain_interface(
 		polkadot_config,
 		&parachain_config,
@@ -178,6 +213,11 @@ async fn start_node_impl(
 
 	let validator = parachain_config.role.is_authority();
 	let prometheus_registry = parachain_config.prometheus_registry().cloned();
+	let metrics = prometheus_registry
+		.as_ref()
+		.map(Metrics::register)
+		.transpose()
+		.map_err(|e| sc_service::Error::Application(Box::new(e)))?;
 	let transaction_pool = params.transaction_pool.clone();
 	let import_queue_service = params.import_queue.service();
 
@@ -197,6 +237,21 @@ async fn start_node_impl(
 
 	if parachain_config.offchain_worker.enabled {
 		use futures::FutureExt;
+		use sp_core::offchain::OffchainStorage;
+
+		// Read by the offchain worker of `kine_availability`, which only probes content when
+		// the node sets a gateway. The storage is persistent, so an unset gateway is cleared.
+		let enable_http_requests = ipfs_gateway.is_some();
+		if let Some(mut storage) = backend.offchain_storage() {
+			match ipfs_gateway {
+				Some(gateway) => storage.set(
+					sp_offchain::STORAGE_PREFIX,
+					kine_availability::GATEWAY_KEY,
+					&codec::Encode::encode(gateway.as_bytes()),
+				),
+				None => storage.remove(sp_offchain::STORAGE_PREFIX, kine_availability::GATEWAY_KEY),
+			}
+		}
 
 		task_manager.spawn_handle().spawn(
 			"offchain-workers-runner",
@@ -210,7 +265,7 @@ async fn start_node_impl(
 				)),
 				network_provider: network.clone(),
 				is_validator: parachain_config.role.is_authority(),
-				enable_http_requests: false,
+				enable_http_requests,
 				custom_extensions: move |_| vec![],
 			})
 			.run(client.clone(), task_manager.spawn_handle())
@@ -220,16 +275,52 @@ async fn start_node_impl(
 
 	let rpc_builder = {
 		let client = client.clone();
+		let backend = backend.clone();
 		let transaction_pool = transaction_pool.clone();
-
-		Box::new(move |deny_unsafe, _| {
+		let network = network.clone();
+		let sync_service = sync_service.clone();
+		let frontier_backend = frontier_backend.clone();
+		let overrides = overrides.clone();
+		let filter_pool = frontier_components.filter_pool.clone();
+		let fee_history_cache = frontier_components.fee_history_cache.clone();
+		let fee_history_cache_limit = frontier_components.fee_history_cache_limit;
+		let pubsub_notification_sinks = pubsub_notification_sinks.clone();
+		let eth_config = eth_config.clone();
+		let indexer = indexer.clone();
+
+		Box::new(move |deny_unsafe, subscription_task_executor| {
+			let eth = crate::rpc::eth::EthDeps {
+				client: client.clone(),
+				pool: transaction_pool.clone(),
+				graph: transaction_pool.pool().clone(),
+				enable_dev_signer: eth_config.enable_dev_signer,
+				network: network.clone(),
+				sync: sync_service.clone(),
+				frontier_backend: frontier_backend.clone(),
+				overrides: overrides.clone(),
+				block_data_cache: block_data_cache.clone(),
+				filter_pool: filter_pool.clone(),
+				max_past_logs: eth_config.max_past_logs,
+				fee_history_cache: fee_history_cache.clone(),
+				fee_history_cache_limit,
+				execute_gas_limit_multiplier: eth_config.execute_gas_limit_multiplier,
+				max_stored_filters: eth_config.max_stored_filters,
+				pending_create_inherent_data_providers: pending_inherent_data_providers(
+					client.clone(),
+					para_id,
+				),
+			};
 			let deps = crate::rpc::FullDeps {
 				client: client.clone(),
+				backend: backend.clone(),
 				pool: transaction_pool.clone(),
 				deny_unsafe,
+				eth,
+				indexer: indexer.clone(),
 			};
 
-			crate::rpc::create_full(deps).map_err(Into::into)
+			crate::rpc::create_full(deps, subscription_task_executor, pubsub_notification_sinks.clone())
+				.map_err(Into::into)
 		})
 	};
 
@@ -240,7 +331,7 @@ async fn start_node_impl(
 		task_manager: &mut task_manager,
 		config: parachain_config,
 		keystore: params.keystore_container.keystore(),
-		backend,
+		backend: backend.clone(),
 		network: network.clone(),
 		sync_service: sync_service.clone(),
 		system_rpc_tx,
@@ -248,6 +339,30 @@ async fn start_node_impl(
 		telemetry: telemetry.as_mut(),
 	})?;
 
+	spawn_frontier_tasks(
+		&task_manager,
+		client.clone(),
+		backend.clone(),
+		frontier_backend,
+		overrides,
+		&frontier_components,
+		sync_service.clone(),
+		pubsub_notification_sinks,
+	);
+
+	if let Some(indexer) = indexer {
+		// SQLite blocks, keep it off the async executor.
+		task_manager.spawn_handle().spawn_blocking("indexer", None, indexer.run(client.clone()));
+	}
+
+	if let Some(metrics) = metrics.clone() {
+		task_manager.spawn_handle().spawn(
+			"kinera-metrics",
+			None,
+			metrics.run(client.clone(), relay_chain_interface.clone()),
+		);
+	}
+
 	if let Some(hwbench) = hwbench {
 		sc_sysinfo::print_hwbench(&hwbench);
 		// Here you can check whether the hardware meets your chains' requirements. Putting a link
@@ -306,6 +421,7 @@ async fn start_node_impl(
 			client.clone(),
 			block_import,
 			prometheus_registry.as_ref(),
+			metrics,
 			telemetry.as_ref().map(|t| t.handle()),
 			&task_manager,
 			relay_chain_interface.clone(),
@@ -325,6 +441,46 @@ async fn start_node_impl(
 	Ok((task_manager, client))
 }
 
+/// The inherents of the pending block built by `eth_call` and friends on top of `parent`. The
+/// pending block is never imported, so the relay chain data is mocked: the messaging state of
+/// `parent` is kept, with a relay parent right after its own so the block checks pass.
+fn pending_inherent_data_providers(
+	client: Arc<ParachainClient>,
+	para_id: ParaId,
+) -> impl Fn(
+	Hash,
+	(),
+) -> futures::future::Ready<
+	Result<
+		(sp_timestamp::InherentDataProvider, MockValidationDataInherentDataProvider),
+		Box<dyn std::error::Error + Send + Sync>,
+	>,
+> + Send + Sync + 'static {
+	move |parent, ()| {
+		let key = [twox_128(b"ParachainSystem"), twox_128(b"LastRelayChainBlockNumber")].concat();
+		let relay_parent = client
+			.storage(parent, &StorageKey(key))
+			.ok()
+			.flatten()
+			.and_then(|data| u32::decode(&mut &data.0[..]).ok())
+			.unwrap_or_default();
+
+		let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+		let parachain_inherent = MockValidationDataInherentDataProvider {
+			current_para_block: 0,
+			relay_offset: relay_parent.saturating_add(1),
+			relay_blocks_per_para_block: 1,
+			para_blocks_per_relay_epoch: 1,
+			relay_randomness_config: (),
+			xcm_config: MockXcmConfig::new(&*client, parent, para_id, Default::default()),
+			raw_downward_messages: Vec::new(),
+			raw_horizontal_messages: Vec::new(),
+			additional_key_values: None,
+		};
+		futures::future::ready(Ok((timestamp, parachain_inherent)))
+	}
+}
+
 /// Build the import queue for the parachain runtime.
 fn build_import_queue(
 	client: Arc<ParachainClient>,
@@ -359,6 +515,7 @@ fn start_consensus(
 	client: Arc<ParachainClient>,
 	block_import: ParachainBlockImport,
 	prometheus_registry: Option<&Registry>,
+	metrics: Option<Metrics>,
 	telemetry: Option<TelemetryHandle>,
 	task_manager: &TaskManager,
 	relay_chain_interface: Arc<dyn RelayChainInterface>,
@@ -388,7 +545,7 @@ fn start_consensus(
 		telemetry.clone(),
 	);
 
-	let proposer = Proposer::new(proposer_factory);
+	let proposer = MeteredProposer::new(Proposer::new(proposer_factory), metrics);
 
 	let collator_service = CollatorService::new(
 		client.clone(),
@@ -432,6 +589,19 @@ pub async fn start_parachain_node(
 	collator_options: CollatorOptions,
 	para_id: ParaId,
 	hwbench: Option<sc_sysinfo::HwBench>,
+	eth_config: EthConfiguration,
+	indexer_config: IndexerConfiguration,
+	ipfs_gateway: Option<String>,
 ) -> sc_service::error::Result<(TaskManager, Arc<ParachainClient>)> {
-	start_node_impl(parachain_config, polkadot_config, collator_options, para_id, hwbench).await
+	start_node_impl(
+		parachain_config,
+		polkadot_config,
+		collator_options,
+		para_id,
+		hwbench,
+		eth_config,
+		indexer_config,
+		ipfs_gateway,
+	)
+	.await
 }
@@ -0,0 +1,161 @@
+//! Runtime upgrade dry runs against the local database: the new runtime runs its migrations
+//! and state checks on top of the state of a block, and nothing is written back.
+
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
+
+use codec::Encode;
+use kine_runtime::Block;
+use sc_cli::{BlockNumberOrHash, CliConfiguration, Error, ImportParams, Result, SharedParams};
+use sc_client_api::{Backend, UsageProvider};
+use sc_executor::WasmExecutor;
+use sp_api::{Core, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	storage::well_known_keys,
+	traits::{CallContext, ReadRuntimeVersionExt, RuntimeCode, WrappedRuntimeCode},
+};
+use sp_runtime::traits::HashingFor;
+use sp_state_machine::{OverlayedChanges, StateMachine};
+use sp_weights::Weight;
+
+use crate::metadata::{pallet_of, Runtime};
+
+/// The checks run around the migrations, encoded as `frame_try_runtime::UpgradeCheckSelect`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, clap::ValueEnum)]
+pub enum UpgradeChecks {
+	/// Only run the migrations.
+	None,
+	/// Run the `pre_upgrade` and `post_upgrade` hooks and the `try_state` checks.
+	All,
+	/// Run the `pre_upgrade` and `post_upgrade` hooks.
+	PreAndPost,
+	/// Run the `try_state` checks.
+	TryState,
+}
+
+/// The `check-upgrade` command.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct CheckUpgradeCmd {
+	/// The new runtime wasm, built with the `try-runtime` feature.
+	#[arg(long)]
+	pub wasm: PathBuf,
+
+	/// The block whose state is upgraded, the best block by default.
+	#[arg(long, value_name = "HASH or NUMBER")]
+	pub at: Option<BlockNumberOrHash>,
+
+	/// The checks run around the migrations.
+	#[arg(long, value_enum, default_value_t = UpgradeChecks::All)]
+	pub checks: UpgradeChecks,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub import_params: ImportParams,
+}
+
+impl CheckUpgradeCmd {
+	/// Run the command.
+	pub async fn run<C, BA>(&self, client: Arc<C>, backend: Arc<BA>) -> Result<()>
+	where
+		C: HeaderBackend<Block> + UsageProvider<Block> + ProvideRuntimeApi<Block>,
+		C::Api: Core<Block>,
+		BA: Backend<Block>,
+	{
+		let hash = match self.at.as_ref().map(|at| at.parse::<Block>()).transpose()? {
+			Some(id) => client.expect_block_hash_from_id(&id)?,
+			None => client.usage_info().chain.best_hash,
+		};
+
+		let code = std::fs::read(&self.wasm)?;
+		let runtime = Runtime::from_code(code.clone());
+		let old_version = client.runtime_api().version(hash).map_err(|e| Error::Client(e.into()))?;
+		let new_version = runtime.version()?;
+		let prefixes = runtime.storage_prefixes()?;
+
+		let state = backend.state_at(hash)?;
+		let mut overlay = OverlayedChanges::<HashingFor<Block>>::default();
+		overlay.set_storage(well_known_keys::CODE.to_vec(), Some(code.clone()));
+
+		let executor = WasmExecutor::<sp_io::SubstrateHostFunctions>::builder()
+			.with_allow_missing_host_functions(true)
+			.build();
+		let mut extensions = sp_externalities::Extensions::default();
+		extensions.register(ReadRuntimeVersionExt::new(executor.clone()));
+		let code_fetcher = WrappedRuntimeCode(code.as_slice().into());
+		let runtime_code = RuntimeCode {
+			code_fetcher: &code_fetcher,
+			heap_pages: None,
+			hash: sp_core::blake2_256(&code).to_vec(),
+		};
+
+		let result = StateMachine::new(
+			&state,
+			&mut overlay,
+			&executor,
+			"TryRuntime_on_runtime_upgrade",
+			&self.checks.encode(),
+			&mut extensions,
+			&runtime_code,
+			CallContext::Offchain,
+		)
+		.execute()
+		.map_err(|e| Error::Application(format!("The upgrade failed: {e}").into()))?;
+		let (used, max): (Weight, Weight) = codec::Decode::decode(&mut &result[..])
+			.map_err(|e| Error::Application(format!("Invalid upgrade result: {e}").into()))?;
+
+		let changes = overlay
+			.drain_storage_changes(&state, new_version.state_version())
+			.map_err(|e| Error::Application(e.into()))?;
+		let mut per_pallet = BTreeMap::<&str, (usize, usize)>::new();
+		for (key, value) in &changes.main_storage_changes {
+			if key.as_slice() == well_known_keys::CODE {
+				continue
+			}
+			let (written, removed) = per_pallet.entry(pallet_of(&prefixes, key)).or_default();
+			match value {
+				Some(_) => *written += 1,
+				None => *removed += 1,
+			}
+		}
+
+		println!("Upgrade of block {hash:?}");
+		println!(
+			"Spec version:         {} -> {}{}",
+			old_version.spec_version,
+			new_version.spec_version,
+			if new_version.spec_version > old_version.spec_version { "" } else { "  (not bumped!)" },
+		);
+		println!(
+			"Transaction version:  {} -> {}",
+			old_version.transaction_version, new_version.transaction_version
+		);
+		println!(
+			"Weight used:          {} ref time, {} proof size ({:.1}% of a block)",
+			used.ref_time(),
+			used.proof_size(),
+			100.0 * used.ref_time() as f64 / max.ref_time().max(1) as f64,
+		);
+		println!("\nStorage changes:");
+		if per_pallet.is_empty() {
+			println!("  none");
+		}
+		for (pallet, (written, removed)) in per_pallet {
+			println!("  {pallet:<28} {written:>6} written {removed:>6} removed");
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for CheckUpgradeCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn import_params(&self) -> Option<&ImportParams> {
+		Some(&self.import_params)
+	}
+}
@@ -0,0 +1,206 @@
+//! Chain spec checks, for the mistakes that keep a parachain from starting or producing blocks,
+//! and pallet by pallet diffs of the genesis state of two specs.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use kine_runtime::{Balance, Runtime, EXISTENTIAL_DEPOSIT};
+use sc_cli::{Error, Result, SubstrateCli};
+use sc_service::ChainSpec;
+use sp_core::storage::{well_known_keys, Storage};
+
+use crate::{
+	chain_spec::Extensions,
+	metadata::{pallet_of, Runtime as Wasm},
+};
+
+/// The `validate-spec` command.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct ValidateSpecCmd {
+	/// The chain spec checked, a file or the id of a built-in spec.
+	pub chain: String,
+
+	/// Another chain spec the first one is compared with, pallet by pallet.
+	#[arg(long)]
+	pub diff: Option<String>,
+}
+
+impl ValidateSpecCmd {
+	/// Run the command.
+	pub fn run<C: SubstrateCli>(&self, cli: &C) -> Result<()> {
+		let spec = cli.load_spec(&self.chain)?;
+
+		let mut problems = check_endowments(&*spec)?;
+		let storage = match build_genesis(&*spec) {
+			Ok(storage) => {
+				problems.extend(check_genesis(&*spec, storage.clone()));
+				Some(storage)
+			},
+			Err(error) => {
+				problems.push(error);
+				None
+			},
+		};
+
+		if let Some(other) = &self.diff {
+			let storage = storage
+				.ok_or_else(|| Error::Input(format!("Cannot diff {}, it does not build.", self.chain)))?;
+			let other = cli.load_spec(other)?;
+			let other_storage = build_genesis(&*other).map_err(Error::Input)?;
+			diff(&*spec, &storage, &*other, &other_storage)?;
+		}
+
+		if problems.is_empty() {
+			println!("{}: no problems found.", spec.id());
+			return Ok(())
+		}
+		println!("{}:", spec.id());
+		for problem in &problems {
+			println!("  - {problem}");
+		}
+		Err(Error::Input(format!("{} problems found in {}.", problems.len(), spec.id())))
+	}
+}
+
+/// Build the genesis state with the genesis builder of the runtime of `spec`.
+fn build_genesis(spec: &dyn ChainSpec) -> std::result::Result<Storage, String> {
+	spec.as_storage_builder()
+		.build_storage()
+		.map_err(|e| format!("The runtime cannot build the genesis: {e}"))
+}
+
+/// Check the endowments of a JSON genesis, which the balances pallet refuses to build when they
+/// are below the existential deposit or repeated. Raw genesis states cannot have these.
+fn check_endowments(spec: &dyn ChainSpec) -> Result<Vec<String>> {
+	let json: serde_json::Value = serde_json::from_str(&spec.as_json(false)?)
+		.map_err(|e| Error::Application(Box::new(e)))?;
+	let genesis = &json["genesis"]["runtimeGenesis"];
+	let config = if genesis["patch"].is_object() { &genesis["patch"] } else { &genesis["config"] };
+	let Some(endowments) = config["balances"]["balances"].as_array() else { return Ok(Vec::new()) };
+
+	let mut problems = Vec::new();
+	let mut endowed = BTreeSet::new();
+	for endowment in endowments {
+		let account = endowment[0].as_str().unwrap_or_default();
+		let amount = &endowment[1];
+		let amount = amount
+			.as_u64()
+			.map(Balance::from)
+			.or_else(|| amount.as_f64().map(|amount| amount as Balance))
+			.or_else(|| amount.as_str().and_then(|amount| amount.parse().ok()));
+
+		match amount {
+			None => problems.push(format!("The endowment of {account} is not an amount.")),
+			Some(amount) if amount < EXISTENTIAL_DEPOSIT => problems.push(format!(
+				"The endowment of {account}, {amount}, is below the existential deposit of \
+				 {EXISTENTIAL_DEPOSIT}."
+			)),
+			Some(_) => {},
+		}
+		if !endowed.insert(account) {
+			problems.push(format!("{account} is endowed more than once."));
+		}
+	}
+	Ok(problems)
+}
+
+/// Check the genesis state against the extensions of `spec` and itself.
+fn check_genesis(spec: &dyn ChainSpec, storage: Storage) -> Vec<String> {
+	let mut problems = Vec::new();
+
+	sp_io::TestExternalities::new(storage).execute_with(|| {
+		let para_id = u32::from(parachain_info::Pallet::<Runtime>::parachain_id());
+		match Extensions::try_get(spec) {
+			None => problems.push("The spec has no `relay_chain` and `para_id` extensions.".into()),
+			Some(extensions) if extensions.para_id != para_id => problems.push(format!(
+				"The para id of the extensions, {}, is not the one of `parachainInfo`, {para_id}.",
+				extensions.para_id
+			)),
+			Some(_) => {},
+		}
+
+		for invulnerable in pallet_collator_selection::Invulnerables::<Runtime>::get() {
+			if pallet_session::NextKeys::<Runtime>::get(&invulnerable).is_none() {
+				problems.push(format!("The invulnerable {invulnerable} has no session keys."));
+			}
+		}
+
+		for (account, info) in frame_system::Account::<Runtime>::iter() {
+			let total = info.data.free.saturating_add(info.data.reserved);
+			if total < EXISTENTIAL_DEPOSIT {
+				problems.push(format!(
+					"The balance of {account}, {total}, is below the existential deposit of \
+					 {EXISTENTIAL_DEPOSIT}."
+				));
+			}
+		}
+	});
+	problems
+}
+
+/// Print the differences between two specs, with the changes to their genesis states grouped
+/// by pallet.
+fn diff(
+	left: &dyn ChainSpec,
+	left_storage: &Storage,
+	right: &dyn ChainSpec,
+	right_storage: &Storage,
+) -> Result<()> {
+	println!("{} -> {}", left.id(), right.id());
+
+	let fields = [
+		("name", left.name().to_string(), right.name().to_string()),
+		("chain type", format!("{:?}", left.chain_type()), format!("{:?}", right.chain_type())),
+		("protocol id", format!("{:?}", left.protocol_id()), format!("{:?}", right.protocol_id())),
+		("boot nodes", format!("{:?}", left.boot_nodes()), format!("{:?}", right.boot_nodes())),
+		("properties", format!("{:?}", left.properties()), format!("{:?}", right.properties())),
+		(
+			"extensions",
+			format!("{:?}", Extensions::try_get(left)),
+			format!("{:?}", Extensions::try_get(right)),
+		),
+	];
+	for (field, left, right) in fields {
+		if left != right {
+			println!("  {field}: {left} -> {right}");
+		}
+	}
+
+	let code = |storage: &Storage| storage.top.get(well_known_keys::CODE).cloned().unwrap_or_default();
+	let (left_code, right_code) = (code(left_storage), code(right_storage));
+	if left_code != right_code {
+		println!(
+			"  code: {:?} -> {:?}",
+			sp_core::H256(sp_core::blake2_256(&left_code)),
+			sp_core::H256(sp_core::blake2_256(&right_code)),
+		);
+	}
+
+	let prefixes = Wasm::from_code(right_code).storage_prefixes()?;
+	let mut per_pallet = BTreeMap::<&str, (usize, usize, usize)>::new();
+	for (key, value) in &right_storage.top {
+		if key.as_slice() == well_known_keys::CODE {
+			continue
+		}
+		let (added, _, changed) = per_pallet.entry(pallet_of(&prefixes, key)).or_default();
+		match left_storage.top.get(key) {
+			None => *added += 1,
+			Some(left_value) if left_value != value => *changed += 1,
+			Some(_) => {},
+		}
+	}
+	for key in left_storage.top.keys().filter(|key| !right_storage.top.contains_key(*key)) {
+		per_pallet.entry(pallet_of(&prefixes, key)).or_default().1 += 1;
+	}
+	per_pallet.retain(|_, changes| *changes != (0, 0, 0));
+
+	if left_storage.children_default != right_storage.children_default {
+		println!("  child tries differ");
+	}
+	if per_pallet.is_empty() {
+		println!("  same genesis storage");
+	}
+	for (pallet, (added, removed, changed)) in per_pallet {
+		println!("  {pallet:<28} {added:>6} added {removed:>6} removed {changed:>6} changed");
+	}
+	Ok(())
+}
@@ -0,0 +1,104 @@
+//! Benchmarking setup for kine-availability
+
+use super::*;
+
+use codec::Decode;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::{
+	traits::{
+		fungible::{Mutate, MutateHold},
+		EnsureOrigin, Get,
+	},
+	BoundedVec,
+};
+use frame_system::{offchain::SigningTypes, RawOrigin};
+use sp_runtime::traits::{Bounded, IdentifyAccount, TrailingZeroInput};
+use sp_std::{vec, vec::Vec};
+
+fn longest_cid<T: Config>() -> CidOf<T> {
+	BoundedVec::truncate_from(vec![b'a'; T::MaxCidLength::get() as usize])
+}
+
+fn fund<T: Config>(who: &T::AccountId) {
+	T::Currency::set_balance(who, BalanceOf::<T>::max_value() / 4u32.into());
+}
+
+fn register<T: Config>(owner: &T::AccountId) -> Result<ContentId, frame_benchmarking::BenchmarkError> {
+	fund::<T>(owner);
+	let deposit = T::ContentDeposit::get();
+	T::Currency::hold(&HoldReason::ContentDeposit.into(), owner, deposit)?;
+
+	let content = NextContentId::<T>::get();
+	NextContentId::<T>::put(content + 1);
+	Contents::<T>::insert(content, ContentInfo { owner: owner.clone(), cid: longest_cid::<T>(), deposit });
+	Ok(content)
+}
+
+fn fill_attesters<T: Config>(count: u32) {
+	let attesters = (0..count).map(|i| account::<T::AccountId>("attester", i, 0)).collect::<Vec<_>>();
+	Attesters::<T>::put(BoundedVec::truncate_from(attesters));
+}
+
+benchmarks! {
+	register_content {
+		let origin = T::RegisterOrigin::try_successful_origin()
+			.map_err(|_| frame_benchmarking::BenchmarkError::Weightless)?;
+		let owner = T::RegisterOrigin::ensure_origin(origin.clone()).map_err(|_| "Unsuccessful origin")?;
+		fund::<T>(&owner);
+	}: _<T::RuntimeOrigin>(origin, longest_cid::<T>())
+	verify {
+		assert!(Contents::<T>::contains_key(0));
+	}
+
+	unregister_content {
+		let owner: T::AccountId = whitelisted_caller();
+		let content = register::<T>(&owner)?;
+		let attestation = Attestation { available: true, at: frame_system::Pallet::<T>::block_number() };
+		for i in 0..T::MaxAttesters::get() {
+			Attestations::<T>::insert(content, account::<T::AccountId>("attester", i, 0), attestation);
+		}
+	}: _(RawOrigin::Signed(owner), content)
+	verify {
+		assert!(!Contents::<T>::contains_key(content));
+		assert_eq!(Attestations::<T>::iter_prefix(content).count(), 0);
+	}
+
+	add_attester {
+		fill_attesters::<T>(T::MaxAttesters::get() - 1);
+		let attester: T::AccountId = account("new attester", 0, 0);
+		let origin = T::AttesterOrigin::try_successful_origin()
+			.map_err(|_| frame_benchmarking::BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(origin, attester.clone())
+	verify {
+		assert!(Attesters::<T>::get().contains(&attester));
+	}
+
+	remove_attester {
+		fill_attesters::<T>(T::MaxAttesters::get());
+		let attester: T::AccountId = account("attester", T::MaxAttesters::get() - 1, 0);
+		let origin = T::AttesterOrigin::try_successful_origin()
+			.map_err(|_| frame_benchmarking::BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(origin, attester.clone())
+	verify {
+		assert!(!Attesters::<T>::get().contains(&attester));
+	}
+
+	attest {
+		// The signature is checked by `validate_unsigned`, not by the call.
+		let public = <T as SigningTypes>::Public::decode(&mut TrailingZeroInput::zeroes()).map_err(|_| "No public key")?;
+		let signature = <T as SigningTypes>::Signature::decode(&mut TrailingZeroInput::zeroes()).map_err(|_| "No signature")?;
+		let attester = public.clone().into_account();
+		let content = register::<T>(&whitelisted_caller())?;
+		let attestation = AttestationPayload {
+			public,
+			content,
+			available: true,
+			block_number: frame_system::Pallet::<T>::block_number(),
+		};
+	}: _(RawOrigin::None, attestation, signature)
+	verify {
+		assert!(Attestations::<T>::contains_key(content, attester));
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
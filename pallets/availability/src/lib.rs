@@ -0,0 +1,518 @@
+//** About **//
+	// Availability checks of the content behind the content hashes of films. Owners register
+	// the IPFS CIDs of their content, holding a deposit until they unregister it, and an
+	// offchain worker on the nodes of the attesters probes each CID on an IPFS gateway once
+	// every check period, submitting the result as an unsigned transaction signed with the
+	// attester's `avai` key, inserted in the keystore with `key insert --key-type avai`.
+	// Offchain workers run on collators by default, and only probe when the node runs with
+	// `--ipfs-gateway`, which sets the gateway, such as `http://127.0.0.1:8080`, and enables
+	// HTTP requests from offchain workers. Any HTTP server answering `HEAD /ipfs/<cid>` will do
+	// when testing. The last attestation of every attester is kept per content, so frontends
+	// and other pallets can tell whether content is still retrievable.
+
+
+
+	#![cfg_attr(not(feature = "std"), no_std)]
+
+	pub use pallet::*;
+
+	#[cfg(test)]
+	mod mock;
+
+	#[cfg(test)]
+	mod tests;
+
+	#[cfg(feature = "runtime-benchmarks")]
+	mod benchmarking;
+
+	use sp_core::crypto::KeyTypeId;
+
+	/// The key type of the keys attestations are signed with.
+	pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"avai");
+
+	/// The offchain persistent storage key of the URL of the IPFS gateway, a SCALE encoded
+	/// `Vec<u8>` set by the node. Content is not probed when it is not set.
+	pub const GATEWAY_KEY: &[u8] = b"kine-availability::gateway";
+
+	pub mod crypto {
+		use super::KEY_TYPE;
+		use sp_core::sr25519::{Public as Sr25519Public, Signature as Sr25519Signature};
+		use sp_runtime::{
+			app_crypto::{app_crypto, sr25519},
+			MultiSignature, MultiSigner,
+		};
+
+		app_crypto!(sr25519, KEY_TYPE);
+
+		/// The keys of attesters, as used by the runtime.
+		pub struct AttesterId;
+
+		impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for AttesterId {
+			type RuntimeAppPublic = Public;
+			type GenericSignature = Sr25519Signature;
+			type GenericPublic = Sr25519Public;
+		}
+
+		impl frame_system::offchain::AppCrypto<Sr25519Public, Sr25519Signature> for AttesterId {
+			type RuntimeAppPublic = Public;
+			type GenericSignature = Sr25519Signature;
+			type GenericPublic = Sr25519Public;
+		}
+	}
+
+
+
+	#[frame_support::pallet]
+	pub mod pallet {
+
+		//** Config **//
+
+			//* Imports *//
+
+				use super::GATEWAY_KEY;
+				use frame_support::{
+					dispatch::DispatchResultWithPostInfo,
+					pallet_prelude::*,
+					traits::{
+						fungible::{Inspect, InspectHold, Mutate, MutateHold},
+						tokens::Precision,
+					},
+				};
+				use frame_system::{
+					offchain::{AppCrypto, SendTransactionTypes, SendUnsignedTransaction, SignedPayload, Signer, SigningTypes},
+					pallet_prelude::*,
+				};
+				use sp_runtime::{
+					offchain::{http, storage::StorageValueRef, Duration},
+					traits::{IdentifyAccount, Saturating},
+					RuntimeAppPublic, SaturatedConversion,
+				};
+				use sp_std::vec::Vec;
+
+			//* Config *//
+
+				#[pallet::pallet]
+				pub struct Pallet<T>(_);
+
+				#[pallet::config]
+				pub trait Config: frame_system::Config + SigningTypes + SendTransactionTypes<Call<Self>> {
+					type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+					/// The currency content deposits are held in.
+					type Currency: Inspect<Self::AccountId>
+						+ Mutate<Self::AccountId>
+						+ InspectHold<Self::AccountId>
+						+ MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+					/// The overarching hold reason.
+					type RuntimeHoldReason: From<HoldReason>;
+
+					/// The keys attestations are signed with.
+					type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+					/// Registers content, as the owner of the content.
+					type RegisterOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+
+					/// The deposit held for every registered content, paying for its storage and
+					/// for the probes of the attesters.
+					#[pallet::constant]
+					type ContentDeposit: Get<BalanceOf<Self>>;
+
+					/// Adds and removes attesters.
+					type AttesterOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+					/// The number of blocks between two checks of the same content, during which
+					/// an attestation stays valid.
+					#[pallet::constant]
+					type CheckPeriod: Get<BlockNumberFor<Self>>;
+
+					/// The priority of attestations in the transaction pool.
+					#[pallet::constant]
+					type UnsignedPriority: Get<TransactionPriority>;
+
+					/// The maximum length of a CID.
+					#[pallet::constant]
+					type MaxCidLength: Get<u32>;
+
+					/// The maximum number of attesters.
+					#[pallet::constant]
+					type MaxAttesters: Get<u32>;
+				}
+
+
+
+		//** Types **//
+
+			//* Types *//
+
+				pub type ContentId = u32;
+				pub type BalanceOf<T> =
+					<<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+				pub type CidOf<T> = BoundedVec<u8, <T as Config>::MaxCidLength>;
+				pub type ContentInfoOf<T> =
+					ContentInfo<<T as frame_system::Config>::AccountId, CidOf<T>, BalanceOf<T>>;
+				pub type AttestationPayloadOf<T> =
+					AttestationPayload<<T as SigningTypes>::Public, BlockNumberFor<T>>;
+
+			//* Constants *//
+
+				const LOG_TARGET: &str = "runtime::availability";
+
+				/// How long a probe of the gateway may take, in milliseconds.
+				const PROBE_TIMEOUT: u64 = 5_000;
+
+			//* Enums *//
+
+				#[pallet::composite_enum]
+				pub enum HoldReason {
+					/// The deposit of registered content.
+					ContentDeposit,
+				}
+
+			//* Structs *//
+
+				#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+				pub struct ContentInfo<AccountId, Cid, Balance> {
+					pub owner: AccountId,
+					pub cid: Cid,
+					/// The deposit held from the owner.
+					pub deposit: Balance,
+				}
+
+				#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+				pub struct Attestation<BlockNumber> {
+					/// Whether the gateway served the content.
+					pub available: bool,
+					/// The block the content was probed at.
+					pub at: BlockNumber,
+				}
+
+				/// An attestation as signed by the attester.
+				#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+				pub struct AttestationPayload<Public, BlockNumber> {
+					pub public: Public,
+					pub content: ContentId,
+					pub available: bool,
+					pub block_number: BlockNumber,
+				}
+
+				impl<T: SigningTypes> SignedPayload<T> for AttestationPayload<T::Public, BlockNumberFor<T>> {
+					fn public(&self) -> T::Public {
+						self.public.clone()
+					}
+				}
+
+
+
+		//** Storage **//
+
+			#[pallet::storage]
+			#[pallet::getter(fn next_content_id)]
+			pub type NextContentId<T> = StorageValue<_, ContentId, ValueQuery>;
+
+			#[pallet::storage]
+			#[pallet::getter(fn content)]
+			pub type Contents<T: Config> = StorageMap<_, Twox64Concat, ContentId, ContentInfoOf<T>>;
+
+			#[pallet::storage]
+			#[pallet::getter(fn attesters)]
+			pub type Attesters<T: Config> = StorageValue<_, BoundedVec<T::AccountId, T::MaxAttesters>, ValueQuery>;
+
+			/// The last attestation of every attester of a content.
+			#[pallet::storage]
+			#[pallet::getter(fn attestation)]
+			pub type Attestations<T: Config> = StorageDoubleMap<
+				_,
+				Twox64Concat, ContentId,
+				Blake2_128Concat, T::AccountId,
+				Attestation<BlockNumberFor<T>>,
+			>;
+
+
+
+		//** Events **//
+
+			#[pallet::event]
+			#[pallet::generate_deposit(pub(super) fn deposit_event)]
+			pub enum Event<T: Config> {
+				ContentRegistered { content: ContentId, owner: T::AccountId, cid: CidOf<T> },
+				ContentUnregistered { content: ContentId },
+				AttesterAdded { attester: T::AccountId },
+				AttesterRemoved { attester: T::AccountId },
+				ContentAttested { content: ContentId, attester: T::AccountId, available: bool },
+			}
+
+
+
+		//** Errors **//
+
+			#[pallet::error]
+			pub enum Error<T> {
+				/// CIDs are not empty and only have ASCII letters and digits.
+				InvalidCid,
+				UnknownContent,
+				NotOwner,
+				AlreadyAttester,
+				NotAttester,
+				TooManyAttesters,
+			}
+
+
+		//** Hooks **//
+		#[pallet::hooks]
+		impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+
+			fn offchain_worker(now: BlockNumberFor<T>) {
+				Self::check_availability(now);
+			}
+
+		}
+
+
+
+		//** Validation **//
+
+			#[pallet::validate_unsigned]
+			impl<T: Config> ValidateUnsigned for Pallet<T> {
+				type Call = Call<T>;
+
+				fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+					let Call::attest { attestation, signature } = call else {
+						return InvalidTransaction::Call.into()
+					};
+					if !SignedPayload::<T>::verify::<T::AuthorityId>(attestation, signature.clone()) {
+						return InvalidTransaction::BadProof.into()
+					}
+
+					let attester = attestation.public.clone().into_account();
+					if !Attesters::<T>::get().contains(&attester) {
+						return InvalidTransaction::BadSigner.into()
+					}
+					if !Contents::<T>::contains_key(attestation.content) {
+						return InvalidTransaction::Call.into()
+					}
+
+					let now = frame_system::Pallet::<T>::block_number();
+					if attestation.block_number > now {
+						return InvalidTransaction::Future.into()
+					}
+					let newer = Attestations::<T>::get(attestation.content, &attester)
+						.map_or(true, |last| attestation.block_number > last.at);
+					if !newer || now.saturating_sub(attestation.block_number) >= T::CheckPeriod::get() {
+						return InvalidTransaction::Stale.into()
+					}
+
+					ValidTransaction::with_tag_prefix("KineAvailability")
+						.priority(T::UnsignedPriority::get())
+						.and_provides((attestation.content, attester, attestation.block_number))
+						.longevity(T::CheckPeriod::get().saturated_into())
+						.propagate(true)
+						.build()
+				}
+			}
+
+
+
+		//** Extrinsics **//
+
+			#[pallet::call]
+			impl<T: Config> Pallet<T> {
+
+				/// Register the CID of content to check the availability of, holding the content
+				/// deposit.
+				#[pallet::call_index(0)]
+				#[pallet::weight(Weight::from_parts(25_000_000, 0) + T::DbWeight::get().reads_writes(1, 2))]
+				pub fn register_content(origin: OriginFor<T>, cid: CidOf<T>) -> DispatchResultWithPostInfo {
+					let owner = T::RegisterOrigin::ensure_origin(origin)?;
+
+					// The CID ends up in the URL probed by the offchain workers.
+					ensure!(!cid.is_empty() && cid.iter().all(u8::is_ascii_alphanumeric), Error::<T>::InvalidCid);
+
+					let deposit = T::ContentDeposit::get();
+					T::Currency::hold(&HoldReason::ContentDeposit.into(), &owner, deposit)?;
+
+					let content = NextContentId::<T>::get();
+					NextContentId::<T>::put(content.saturating_add(1));
+					Contents::<T>::insert(content, ContentInfo { owner: owner.clone(), cid: cid.clone(), deposit });
+
+					Self::deposit_event(Event::ContentRegistered { content, owner, cid });
+					Ok(().into())
+				}
+
+
+				/// Stop checking content, dropping its attestations and releasing the deposit.
+				#[pallet::call_index(1)]
+				#[pallet::weight(
+					Weight::from_parts(25_000_000, 0)
+						+ T::DbWeight::get().reads_writes(1, 1 + T::MaxAttesters::get() as u64)
+				)]
+				pub fn unregister_content(origin: OriginFor<T>, content: ContentId) -> DispatchResultWithPostInfo {
+					let who = ensure_signed(origin)?;
+
+					let info = Contents::<T>::get(content).ok_or(Error::<T>::UnknownContent)?;
+					ensure!(info.owner == who, Error::<T>::NotOwner);
+
+					Contents::<T>::remove(content);
+					T::Currency::release(&HoldReason::ContentDeposit.into(), &who, info.deposit, Precision::BestEffort)?;
+					let _ = Attestations::<T>::clear_prefix(content, T::MaxAttesters::get(), None);
+
+					Self::deposit_event(Event::ContentUnregistered { content });
+					Ok(().into())
+				}
+
+
+				#[pallet::call_index(2)]
+				#[pallet::weight(Weight::from_parts(15_000_000, 0) + T::DbWeight::get().reads_writes(1, 1))]
+				pub fn add_attester(origin: OriginFor<T>, attester: T::AccountId) -> DispatchResultWithPostInfo {
+					T::AttesterOrigin::ensure_origin(origin)?;
+
+					Attesters::<T>::try_mutate(|attesters| {
+						ensure!(!attesters.contains(&attester), Error::<T>::AlreadyAttester);
+						attesters.try_push(attester.clone()).map_err(|_| Error::<T>::TooManyAttesters)
+					})?;
+
+					Self::deposit_event(Event::AttesterAdded { attester });
+					Ok(().into())
+				}
+
+
+				/// Remove an attester. Its past attestations stay until they are replaced or
+				/// the content is unregistered.
+				#[pallet::call_index(3)]
+				#[pallet::weight(Weight::from_parts(15_000_000, 0) + T::DbWeight::get().reads_writes(1, 1))]
+				pub fn remove_attester(origin: OriginFor<T>, attester: T::AccountId) -> DispatchResultWithPostInfo {
+					T::AttesterOrigin::ensure_origin(origin)?;
+
+					Attesters::<T>::try_mutate(|attesters| {
+						let index = attesters.iter().position(|a| a == &attester).ok_or(Error::<T>::NotAttester)?;
+						attesters.remove(index);
+						Ok::<_, Error<T>>(())
+					})?;
+
+					Self::deposit_event(Event::AttesterRemoved { attester });
+					Ok(().into())
+				}
+
+
+				/// Record the result of a probe, submitted by the offchain worker of an
+				/// attester. The signature and the attestation are checked by
+				/// `validate_unsigned`.
+				#[pallet::call_index(4)]
+				#[pallet::weight(Weight::from_parts(20_000_000, 0) + T::DbWeight::get().reads_writes(2, 1))]
+				pub fn attest(
+					origin: OriginFor<T>,
+					attestation: AttestationPayloadOf<T>,
+					_signature: T::Signature,
+				) -> DispatchResultWithPostInfo {
+					ensure_none(origin)?;
+
+					let AttestationPayload { public, content, available, block_number } = attestation;
+					let attester = public.into_account();
+					Attestations::<T>::insert(content, &attester, Attestation { available, at: block_number });
+
+					Self::deposit_event(Event::ContentAttested { content, attester, available });
+					Ok(().into())
+				}
+
+
+			}
+
+
+
+		//** Helpers **//
+
+			impl<T: Config> Pallet<T> {
+
+				/// Whether most attesters found `content` available during the last check
+				/// period, if any attested it.
+				pub fn is_available(content: ContentId) -> Option<bool> {
+					let now = frame_system::Pallet::<T>::block_number();
+					let (available, total) = Attestations::<T>::iter_prefix_values(content)
+						.filter(|attestation| now.saturating_sub(attestation.at) < T::CheckPeriod::get())
+						.fold((0u32, 0u32), |(available, total), attestation| {
+							(available + attestation.available as u32, total + 1)
+						});
+					(total > 0).then(|| available * 2 > total)
+				}
+
+
+				/// Probe the content due at `now` and submit the attestations, if this node
+				/// holds the key of an attester. Each content is due once per check period.
+				fn check_availability(now: BlockNumberFor<T>) {
+					let attesters = Attesters::<T>::get();
+					let keys: Vec<T::Public> = <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::all()
+						.into_iter()
+						.map(|key| {
+							let generic: <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic = key.into();
+							generic.into()
+						})
+						.filter(|public| attesters.contains(&public.clone().into_account()))
+						.collect();
+					if keys.is_empty() {
+						return
+					}
+					let Some(gateway) = Self::gateway() else {
+						log::debug!(target: LOG_TARGET, "No IPFS gateway set, skipping the availability checks");
+						return
+					};
+					let signer = Signer::<T, T::AuthorityId>::any_account().with_filter(keys);
+
+					let period = T::CheckPeriod::get().max(1u32.into());
+					let first: ContentId = (now % period).saturated_into();
+					for content in (first..NextContentId::<T>::get()).step_by(period.saturated_into::<u32>() as usize) {
+						let Some(info) = Contents::<T>::get(content) else { continue };
+						let available = match Self::probe(&gateway, &info.cid) {
+							Ok(available) => available,
+							Err(e) => {
+								log::warn!(target: LOG_TARGET, "Probing content {content} failed: {e:?}");
+								continue
+							},
+						};
+						let sent = signer.send_unsigned_transaction(
+							|account| AttestationPayload {
+								public: account.public.clone(),
+								content,
+								available,
+								block_number: now,
+							},
+							|attestation, signature| Call::attest { attestation, signature },
+						);
+						if let Some((_, Err(()))) = sent {
+							log::warn!(target: LOG_TARGET, "Submitting the attestation of content {content} failed");
+						}
+					}
+				}
+
+
+				/// The gateway URL set by the node, without trailing slash.
+				fn gateway() -> Option<Vec<u8>> {
+					let mut gateway = StorageValueRef::persistent(GATEWAY_KEY).get::<Vec<u8>>().ok().flatten()?;
+					while gateway.last() == Some(&b'/') {
+						gateway.pop();
+					}
+					Some(gateway)
+				}
+
+
+				/// Whether the gateway serves `cid`. Content the gateway does not find in time
+				/// is unavailable, an unreachable gateway is an error.
+				fn probe(gateway: &[u8], cid: &[u8]) -> Result<bool, http::Error> {
+					let url = [gateway, b"/ipfs/", cid].concat();
+					let url = sp_std::str::from_utf8(&url).map_err(|_| http::Error::Unknown)?;
+					let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(PROBE_TIMEOUT));
+
+					let pending = http::Request::get(url)
+						.method(http::Method::Other("HEAD"))
+						.deadline(deadline)
+						.send()
+						.map_err(|_| http::Error::IoError)?;
+					match pending.try_wait(deadline) {
+						Ok(Ok(response)) => Ok(response.code == 200),
+						Ok(Err(http::Error::DeadlineReached)) | Err(_) => Ok(false),
+						Ok(Err(e)) => Err(e),
+					}
+				}
+
+
+			}
+	}
@@ -0,0 +1,174 @@
+use crate::{mock::*, Attestation, AttestationPayload, Call, Error, Event, HoldReason, GATEWAY_KEY, KEY_TYPE};
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_noop, assert_ok,
+	pallet_prelude::{InvalidTransaction, TransactionSource, TransactionValidityError, ValidateUnsigned},
+	traits::{fungible::InspectHold, Hooks},
+};
+use frame_system::offchain::SignedPayload;
+use sp_core::{
+	offchain::{testing, OffchainDbExt, OffchainWorkerExt, TransactionPoolExt},
+	sr25519,
+};
+use sp_keystore::{testing::MemoryKeystore, Keystore, KeystoreExt};
+use sp_runtime::{offchain::storage::StorageValueRef, BoundedVec, DispatchError};
+use std::sync::Arc;
+
+fn cid(cid: &[u8]) -> crate::CidOf<Test> {
+	BoundedVec::truncate_from(cid.to_vec())
+}
+
+fn held(who: AccountId) -> u64 {
+	Balances::balance_on_hold(&HoldReason::ContentDeposit.into(), &who)
+}
+
+fn validate(call: &Call<Test>) -> Result<(), InvalidTransaction> {
+	Availability::validate_unsigned(TransactionSource::External, call)
+		.map(|_| ())
+		.map_err(|e| match e {
+			TransactionValidityError::Invalid(e) => e,
+			TransactionValidityError::Unknown(_) => InvalidTransaction::Custom(0),
+		})
+}
+
+#[test]
+fn owners_register_and_unregister_content() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Availability::register_content(RuntimeOrigin::signed(ALICE), cid(b"")), Error::<Test>::InvalidCid);
+		assert_noop!(
+			Availability::register_content(RuntimeOrigin::signed(ALICE), cid(b"../admin?x=1")),
+			Error::<Test>::InvalidCid
+		);
+
+		assert_noop!(
+			Availability::register_content(RuntimeOrigin::signed(POOR), cid(CID)),
+			sp_runtime::TokenError::FundsUnavailable
+		);
+
+		assert_ok!(Availability::register_content(RuntimeOrigin::signed(ALICE), cid(CID)));
+		System::assert_last_event(Event::ContentRegistered { content: 0, owner: ALICE, cid: cid(CID) }.into());
+		assert_eq!(Availability::next_content_id(), 1);
+		assert_eq!(held(ALICE), 10);
+
+		crate::Attestations::<Test>::insert(0, BOB, Attestation { available: true, at: 1 });
+		assert_eq!(Availability::is_available(0), Some(true));
+
+		assert_noop!(Availability::unregister_content(RuntimeOrigin::signed(BOB), 0), Error::<Test>::NotOwner);
+		assert_ok!(Availability::unregister_content(RuntimeOrigin::signed(ALICE), 0));
+		assert_eq!(held(ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), 100);
+		assert!(Availability::content(0).is_none());
+		assert!(Availability::attestation(0, BOB).is_none());
+		assert_noop!(Availability::unregister_content(RuntimeOrigin::signed(ALICE), 0), Error::<Test>::UnknownContent);
+	});
+}
+
+#[test]
+fn the_attester_origin_manages_attesters() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Availability::add_attester(RuntimeOrigin::signed(ALICE), ALICE), DispatchError::BadOrigin);
+
+		assert_ok!(Availability::add_attester(RuntimeOrigin::root(), ALICE));
+		assert_noop!(Availability::add_attester(RuntimeOrigin::root(), ALICE), Error::<Test>::AlreadyAttester);
+		assert_ok!(Availability::add_attester(RuntimeOrigin::root(), BOB));
+		assert_noop!(Availability::add_attester(RuntimeOrigin::root(), CAROL), Error::<Test>::TooManyAttesters);
+
+		assert_ok!(Availability::remove_attester(RuntimeOrigin::root(), ALICE));
+		System::assert_last_event(Event::AttesterRemoved { attester: ALICE }.into());
+		assert_noop!(Availability::remove_attester(RuntimeOrigin::root(), ALICE), Error::<Test>::NotAttester);
+		assert_eq!(Availability::attesters().into_inner(), vec![BOB]);
+	});
+}
+
+#[test]
+fn the_offchain_worker_attests_the_content_the_gateway_serves() {
+	let (offchain, offchain_state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let keystore = MemoryKeystore::new();
+	let attester = keystore.sr25519_generate_new(KEY_TYPE, None).unwrap();
+
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainWorkerExt::new(offchain.clone()));
+	ext.register_extension(OffchainDbExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(KeystoreExt(Arc::new(keystore)));
+
+	ext.execute_with(|| {
+		assert_ok!(Availability::register_content(RuntimeOrigin::signed(ALICE), cid(CID)));
+
+		// Keys of accounts which are not attesters are not used.
+		Availability::offchain_worker(10);
+		assert!(pool_state.read().transactions.is_empty());
+
+		assert_ok!(Availability::add_attester(RuntimeOrigin::root(), attester));
+
+		// Nothing is probed until the node sets a gateway.
+		Availability::offchain_worker(10);
+		assert!(pool_state.read().transactions.is_empty());
+
+		StorageValueRef::persistent(GATEWAY_KEY).set(&b"http://127.0.0.1:8080/".to_vec());
+		offchain_state.write().expect_request(testing::PendingRequest {
+			method: "HEAD".into(),
+			uri: format!("http://127.0.0.1:8080/ipfs/{}", std::str::from_utf8(CID).unwrap()),
+			response: Some(Vec::new()),
+			sent: true,
+			..Default::default()
+		});
+		System::set_block_number(10);
+		Availability::offchain_worker(10);
+
+		let transaction = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let extrinsic = Extrinsic::decode(&mut &transaction[..]).unwrap();
+		assert_eq!(extrinsic.signature, None);
+		let RuntimeCall::Availability(call) = extrinsic.call else { panic!("Unexpected call") };
+		let Call::attest { attestation, signature } = call.clone() else { panic!("Unexpected call") };
+		assert_eq!(attestation, AttestationPayload { public: attester, content: 0, available: true, block_number: 10 });
+		assert!(SignedPayload::<Test>::verify::<crate::crypto::AttesterId>(&attestation, signature.clone()));
+
+		assert_ok!(validate(&call));
+		assert_ok!(Availability::attest(RuntimeOrigin::none(), attestation, signature));
+		System::assert_last_event(Event::ContentAttested { content: 0, attester, available: true }.into());
+		assert_eq!(Availability::is_available(0), Some(true));
+
+		// Content is only due once per check period.
+		Availability::offchain_worker(11);
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
+
+#[test]
+fn attestations_must_be_signed_recent_and_new() {
+	let keystore = MemoryKeystore::new();
+	let attester = keystore.sr25519_generate_new(KEY_TYPE, None).unwrap();
+	let stranger = keystore.sr25519_generate_new(KEY_TYPE, None).unwrap();
+	let sign = |signer: &sr25519::Public, payload: &AttestationPayload<sr25519::Public, u64>| {
+		keystore.sr25519_sign(KEY_TYPE, signer, &payload.encode()).unwrap().unwrap()
+	};
+	let attest = |public: sr25519::Public, block_number: u64| {
+		let attestation = AttestationPayload { public, content: 0, available: false, block_number };
+		let signature = sign(&public, &attestation);
+		Call::<Test>::attest { attestation, signature }
+	};
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(Availability::register_content(RuntimeOrigin::signed(ALICE), cid(CID)));
+		assert_ok!(Availability::add_attester(RuntimeOrigin::root(), attester));
+		System::set_block_number(20);
+
+		assert_eq!(validate(&attest(stranger, 20)), Err(InvalidTransaction::BadSigner));
+		assert_eq!(validate(&attest(attester, 21)), Err(InvalidTransaction::Future));
+		assert_eq!(validate(&attest(attester, 10)), Err(InvalidTransaction::Stale));
+
+		let Call::attest { attestation, .. } = attest(attester, 20) else { unreachable!() };
+		let signature = sign(&stranger, &attestation);
+		assert_eq!(validate(&Call::attest { attestation, signature }), Err(InvalidTransaction::BadProof));
+
+		let call = attest(attester, 15);
+		assert_ok!(validate(&call));
+		let Call::attest { attestation, signature } = call.clone() else { unreachable!() };
+		assert_ok!(Availability::attest(RuntimeOrigin::none(), attestation, signature));
+		assert_eq!(validate(&call), Err(InvalidTransaction::Stale));
+		assert_eq!(Availability::is_available(0), Some(false));
+	});
+}
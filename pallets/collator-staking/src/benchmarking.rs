@@ -0,0 +1,69 @@
+//! Benchmarking setup for kine-collator-staking
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as CollatorStaking;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::fungible::{InspectHold, Mutate};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, Saturating, Zero};
+
+/// A candidate the benchmarks can delegate to.
+fn registered_candidate<T: Config>() -> Result<T::AccountId, frame_benchmarking::BenchmarkError> {
+	T::Candidates::candidates()
+		.into_iter()
+		.map(|(candidate, _)| candidate)
+		.next()
+		.ok_or(frame_benchmarking::BenchmarkError::Weightless)
+}
+
+fn funded_delegator<T: Config>() -> T::AccountId {
+	let delegator: T::AccountId = whitelisted_caller();
+	T::Currency::set_balance(&delegator, BalanceOf::<T>::max_value() / 4u32.into());
+	delegator
+}
+
+benchmarks! {
+	delegate {
+		let candidate = registered_candidate::<T>()?;
+		let delegator = funded_delegator::<T>();
+		let amount = T::MinDelegation::get();
+	}: _(RawOrigin::Signed(delegator.clone()), candidate.clone(), amount)
+	verify {
+		assert_eq!(Delegations::<T>::get(&candidate, &delegator), Some(amount));
+	}
+
+	undelegate {
+		let candidate = registered_candidate::<T>()?;
+		let delegator = funded_delegator::<T>();
+		let amount = T::MinDelegation::get();
+		CollatorStaking::<T>::delegate(RawOrigin::Signed(delegator.clone()).into(), candidate.clone(), amount)?;
+	}: _(RawOrigin::Signed(delegator.clone()), candidate.clone(), amount)
+	verify {
+		assert_eq!(Delegations::<T>::get(&candidate, &delegator), None);
+	}
+
+	withdraw_unbonded {
+		let candidate = registered_candidate::<T>()?;
+		let delegator = funded_delegator::<T>();
+		let amount = T::MinDelegation::get();
+		CollatorStaking::<T>::delegate(RawOrigin::Signed(delegator.clone()).into(), candidate.clone(), amount)?;
+		CollatorStaking::<T>::undelegate(RawOrigin::Signed(delegator.clone()).into(), candidate, amount)?;
+		CurrentSession::<T>::mutate(|session| *session = session.saturating_add(T::UnbondingPeriod::get()));
+	}: _(RawOrigin::Signed(delegator.clone()))
+	verify {
+		assert!(T::Currency::balance_on_hold(&HoldReason::Delegation.into(), &delegator).is_zero());
+	}
+
+	note_author {
+		let author: T::AccountId = account("author", 0, 0);
+	}: {
+		<CollatorStaking<T> as pallet_authorship::EventHandler<_, _>>::note_author(author.clone());
+	}
+	verify {
+		assert_eq!(AuthoredBlocks::<T>::get(&author), 1);
+	}
+}
+
+impl_benchmark_test_suite!(CollatorStaking, crate::mock::new_test_ext(), crate::mock::Test,);
@@ -0,0 +1,639 @@
+//** About **//
+	// Lets token holders delegate stake to collator candidates and earn a share of the
+	// rewards paid out every session. It wraps the session manager of the collator
+	// candidates (collator selection) and picks the collators of every session itself:
+	// the invulnerables, then the candidates with the most stake, their own bond plus the
+	// stake delegated to them. It counts the blocks each collator authors, pays the
+	// session reward pro rata to collators and their delegators, and slashes the
+	// delegations of collators that missed every block of a session. Undelegated stake,
+	// and delegations a slash left below the minimum, stay on hold for an unbonding period
+	// before they can be withdrawn.
+
+
+
+	#![cfg_attr(not(feature = "std"), no_std)]
+
+	pub use pallet::*;
+
+	#[cfg(test)]
+	mod mock;
+
+	#[cfg(test)]
+	mod tests;
+
+	#[cfg(feature = "runtime-benchmarks")]
+	mod benchmarking;
+
+
+
+	#[frame_support::pallet]
+	pub mod pallet {
+
+		//** Config **//
+
+			//* Imports *//
+
+				use frame_support::{
+					dispatch::DispatchResultWithPostInfo,
+					pallet_prelude::*,
+					traits::{
+						fungible::{BalancedHold, Inspect, InspectHold, Mutate, MutateHold},
+						tokens::{Precision, Preservation},
+					},
+				};
+				use frame_system::pallet_prelude::*;
+				use pallet_session::SessionManager;
+				use sp_runtime::{
+					traits::{Saturating, Zero},
+					Perbill,
+				};
+				use sp_std::vec::Vec;
+
+			//* Config *//
+
+				#[pallet::pallet]
+				pub struct Pallet<T>(_);
+
+				#[pallet::config]
+				pub trait Config: frame_system::Config {
+					type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+					/// The currency delegations are held in and rewards are paid with.
+					type Currency: Inspect<Self::AccountId>
+						+ Mutate<Self::AccountId>
+						+ InspectHold<Self::AccountId>
+						+ MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>
+						+ BalancedHold<Self::AccountId>;
+
+					/// The overarching hold reason.
+					type RuntimeHoldReason: From<HoldReason>;
+
+					/// The invulnerables and the candidates the collators are picked from.
+					type Candidates: CandidateSource<Self::AccountId, BalanceOf<Self>>;
+
+					/// The session manager of the candidates, usually collator selection. Its own
+					/// pick of collators is replaced by the one of this pallet.
+					type SessionManager: SessionManager<Self::AccountId>;
+
+					/// The account rewards are taken from, or `None` to mint them.
+					type RewardSource: Get<Option<Self::AccountId>>;
+
+					/// The reward shared by the collators of a session.
+					#[pallet::constant]
+					type RewardPerSession: Get<BalanceOf<Self>>;
+
+					/// The part of a collator's reward it keeps before paying its delegators.
+					#[pallet::constant]
+					type CollatorCommission: Get<Perbill>;
+
+					/// The part of every delegation slashed when its collator misses a whole session.
+					#[pallet::constant]
+					type MissedSessionSlash: Get<Perbill>;
+
+					/// The number of sessions undelegated stake stays on hold.
+					#[pallet::constant]
+					type UnbondingPeriod: Get<SessionIndex>;
+
+					/// The minimum stake of a delegation.
+					#[pallet::constant]
+					type MinDelegation: Get<BalanceOf<Self>>;
+
+					#[pallet::constant]
+					type MaxDelegatorsPerCandidate: Get<u32>;
+
+					#[pallet::constant]
+					type MaxDelegationsPerDelegator: Get<u32>;
+
+					#[pallet::constant]
+					type MaxUnbondingChunks: Get<u32>;
+
+					#[pallet::constant]
+					type MaxCollators: Get<u32>;
+				}
+
+
+
+		//** Types **//
+
+			//* Types *//
+
+				pub type SessionIndex = u32;
+				pub type BalanceOf<T> =
+					<<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+				pub type CollatorsOf<T> = BoundedVec<<T as frame_system::Config>::AccountId, <T as Config>::MaxCollators>;
+
+			//* Constants *//
+			//* Enums *//
+
+				#[pallet::composite_enum]
+				pub enum HoldReason {
+					/// Funds delegated to a collator candidate, including the unbonding ones.
+					Delegation,
+				}
+
+			//* Traits *//
+
+				/// Provides the collators the sessions are planned with.
+				pub trait CandidateSource<AccountId, Balance> {
+					/// The collators of every session, whatever their stake.
+					fn invulnerables() -> Vec<AccountId>;
+
+					/// The candidates for the remaining seats, with their own bond.
+					fn candidates() -> Vec<(AccountId, Balance)>;
+
+					/// The number of seats left to the candidates.
+					fn desired_candidates() -> u32;
+
+					fn is_candidate(who: &AccountId) -> bool
+					where
+						AccountId: PartialEq,
+					{
+						Self::candidates().iter().any(|(candidate, _)| candidate == who)
+					}
+				}
+
+			//* Structs *//
+
+				#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+				pub struct UnbondingChunk<Balance> {
+					pub amount: Balance,
+					/// The session from which the chunk can be withdrawn.
+					pub unlock_at: SessionIndex,
+				}
+
+
+
+		//** Storage **//
+
+			/// The stake each delegator put behind each candidate.
+			#[pallet::storage]
+			#[pallet::getter(fn delegation)]
+			pub type Delegations<T: Config> = StorageDoubleMap<
+				_,
+				Blake2_128Concat, T::AccountId,
+				Blake2_128Concat, T::AccountId,
+				BalanceOf<T>,
+			>;
+
+			/// The total stake delegated to each candidate.
+			#[pallet::storage]
+			#[pallet::getter(fn candidate_stake)]
+			pub type CandidateStake<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+			#[pallet::storage]
+			#[pallet::getter(fn delegator_count)]
+			pub type DelegatorCount<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+			#[pallet::storage]
+			#[pallet::getter(fn delegation_count)]
+			pub type DelegationCount<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+			#[pallet::storage]
+			#[pallet::getter(fn unbonding)]
+			pub type Unbonding<T: Config> = StorageMap<
+				_,
+				Blake2_128Concat, T::AccountId,
+				BoundedVec<UnbondingChunk<BalanceOf<T>>, T::MaxUnbondingChunks>,
+				ValueQuery,
+			>;
+
+			#[pallet::storage]
+			#[pallet::getter(fn current_session)]
+			pub type CurrentSession<T> = StorageValue<_, SessionIndex, ValueQuery>;
+
+			/// The collators picked for upcoming sessions, until the sessions start.
+			#[pallet::storage]
+			pub type PlannedCollators<T: Config> = StorageMap<_, Twox64Concat, SessionIndex, CollatorsOf<T>>;
+
+			/// The collators of the current session.
+			#[pallet::storage]
+			#[pallet::getter(fn active_collators)]
+			pub type ActiveCollators<T: Config> = StorageValue<_, CollatorsOf<T>, ValueQuery>;
+
+			/// The blocks each collator authored in the current session.
+			#[pallet::storage]
+			#[pallet::getter(fn authored_blocks)]
+			pub type AuthoredBlocks<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+
+
+		//** Events **//
+
+			#[pallet::event]
+			#[pallet::generate_deposit(pub(super) fn deposit_event)]
+			pub enum Event<T: Config> {
+				Delegated { delegator: T::AccountId, candidate: T::AccountId, amount: BalanceOf<T> },
+				Undelegated {
+					delegator: T::AccountId,
+					candidate: T::AccountId,
+					amount: BalanceOf<T>,
+					unlock_at: SessionIndex,
+				},
+				Withdrawn { delegator: T::AccountId, amount: BalanceOf<T> },
+				Rewarded { account: T::AccountId, amount: BalanceOf<T> },
+				CollatorSlashed { collator: T::AccountId, amount: BalanceOf<T> },
+			}
+
+
+
+		//** Errors **//
+
+			#[pallet::error]
+			pub enum Error<T> {
+				NotCandidate,
+				DelegationBelowMinimum,
+				TooManyDelegators,
+				TooManyDelegations,
+				TooManyUnbondingChunks,
+				NoDelegation,
+				InsufficientDelegation,
+				NothingToWithdraw,
+			}
+
+
+		//** Hooks **//
+		#[pallet::hooks]
+		impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+
+		}
+
+
+
+		//** Extrinsics **//
+
+			#[pallet::call]
+			impl<T: Config> Pallet<T> {
+
+				#[pallet::call_index(0)]
+				#[pallet::weight(Weight::from_parts(60_000_000, 0) + T::DbWeight::get().reads_writes(6, 5))]
+				pub fn delegate(
+					origin: OriginFor<T>,
+					candidate: T::AccountId,
+					amount: BalanceOf<T>,
+				) -> DispatchResultWithPostInfo {
+					let delegator = ensure_signed(origin)?;
+
+					ensure!(T::Candidates::is_candidate(&candidate), Error::<T>::NotCandidate);
+
+					let current = Delegations::<T>::get(&candidate, &delegator);
+					if current.is_none() {
+						ensure!(amount >= T::MinDelegation::get(), Error::<T>::DelegationBelowMinimum);
+						ensure!(
+							DelegatorCount::<T>::get(&candidate) < T::MaxDelegatorsPerCandidate::get(),
+							Error::<T>::TooManyDelegators
+						);
+						ensure!(
+							DelegationCount::<T>::get(&delegator) < T::MaxDelegationsPerDelegator::get(),
+							Error::<T>::TooManyDelegations
+						);
+
+						DelegatorCount::<T>::mutate(&candidate, |count| *count += 1);
+						DelegationCount::<T>::mutate(&delegator, |count| *count += 1);
+					}
+
+					T::Currency::hold(&HoldReason::Delegation.into(), &delegator, amount)?;
+
+					Delegations::<T>::insert(&candidate, &delegator, current.unwrap_or_default().saturating_add(amount));
+					CandidateStake::<T>::mutate(&candidate, |stake| *stake = stake.saturating_add(amount));
+
+					Self::deposit_event(Event::Delegated { delegator, candidate, amount });
+					Ok(().into())
+				}
+
+
+				#[pallet::call_index(1)]
+				#[pallet::weight(Weight::from_parts(60_000_000, 0) + T::DbWeight::get().reads_writes(5, 5))]
+				pub fn undelegate(
+					origin: OriginFor<T>,
+					candidate: T::AccountId,
+					amount: BalanceOf<T>,
+				) -> DispatchResultWithPostInfo {
+					let delegator = ensure_signed(origin)?;
+
+					let current = Delegations::<T>::get(&candidate, &delegator).ok_or(Error::<T>::NoDelegation)?;
+					ensure!(amount <= current, Error::<T>::InsufficientDelegation);
+
+					let remaining = current.saturating_sub(amount);
+					ensure!(
+						remaining.is_zero() || remaining >= T::MinDelegation::get(),
+						Error::<T>::DelegationBelowMinimum
+					);
+
+					let unlock_at = Self::unlock_at();
+					Unbonding::<T>::try_mutate(&delegator, |chunks| {
+						chunks.try_push(UnbondingChunk { amount, unlock_at })
+					})
+					.map_err(|_| Error::<T>::TooManyUnbondingChunks)?;
+
+					if remaining.is_zero() {
+						Self::remove_delegation(&candidate, &delegator);
+					} else {
+						Delegations::<T>::insert(&candidate, &delegator, remaining);
+					}
+					CandidateStake::<T>::mutate(&candidate, |stake| *stake = stake.saturating_sub(amount));
+
+					Self::deposit_event(Event::Undelegated { delegator, candidate, amount, unlock_at });
+					Ok(().into())
+				}
+
+
+				#[pallet::call_index(2)]
+				#[pallet::weight(Weight::from_parts(40_000_000, 0) + T::DbWeight::get().reads_writes(3, 2))]
+				pub fn withdraw_unbonded(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+					let delegator = ensure_signed(origin)?;
+
+					let current_session = CurrentSession::<T>::get();
+					let mut chunks = Unbonding::<T>::get(&delegator);
+
+					let mut amount = BalanceOf::<T>::zero();
+					chunks.retain(|chunk| {
+						let unlocked = chunk.unlock_at <= current_session;
+						if unlocked {
+							amount = amount.saturating_add(chunk.amount);
+						}
+						!unlocked
+					});
+					ensure!(!amount.is_zero(), Error::<T>::NothingToWithdraw);
+
+					// slashes may have taken part of the held funds
+					let held = T::Currency::balance_on_hold(&HoldReason::Delegation.into(), &delegator);
+					let amount = T::Currency::release(
+						&HoldReason::Delegation.into(),
+						&delegator,
+						amount.min(held),
+						Precision::BestEffort,
+					)?;
+
+					if chunks.is_empty() {
+						Unbonding::<T>::remove(&delegator);
+					} else {
+						Unbonding::<T>::insert(&delegator, chunks);
+					}
+
+					Self::deposit_event(Event::Withdrawn { delegator, amount });
+					Ok(().into())
+				}
+
+
+			}
+
+
+
+		//** Helpers **//
+
+			impl<T: Config> Pallet<T> {
+
+				fn unlock_at() -> SessionIndex {
+					CurrentSession::<T>::get().saturating_add(T::UnbondingPeriod::get())
+				}
+
+
+				/// Unbond `amount` of `delegator` without a call, merged into its latest chunk when
+				/// there is no room for a new one.
+				fn unbond(delegator: &T::AccountId, amount: BalanceOf<T>, unlock_at: SessionIndex) {
+					Unbonding::<T>::mutate(delegator, |chunks| {
+						if let Err(chunk) = chunks.try_push(UnbondingChunk { amount, unlock_at }) {
+							if let Some(last) = chunks.last_mut() {
+								last.amount = last.amount.saturating_add(chunk.amount);
+								last.unlock_at = last.unlock_at.max(chunk.unlock_at);
+							}
+						}
+					});
+				}
+
+
+				fn remove_delegation(candidate: &T::AccountId, delegator: &T::AccountId) {
+					Delegations::<T>::remove(candidate, delegator);
+					DelegatorCount::<T>::mutate(candidate, |count| *count = count.saturating_sub(1));
+					DelegationCount::<T>::mutate(delegator, |count| *count = count.saturating_sub(1));
+				}
+
+
+				/// Pay `amount` to `who`, from the reward source if there is one.
+				fn pay(who: &T::AccountId, amount: BalanceOf<T>) {
+					if amount.is_zero() {
+						return
+					}
+
+					let paid = match T::RewardSource::get() {
+						Some(source) => T::Currency::transfer(&source, who, amount, Preservation::Preserve),
+						None => T::Currency::mint_into(who, amount),
+					};
+
+					match paid {
+						Ok(_) => Self::deposit_event(Event::Rewarded { account: who.clone(), amount }),
+						Err(error) => log::warn!(
+							target: "runtime::collator-staking",
+							"failed to pay a reward of {:?}: {:?}",
+							amount,
+							error,
+						),
+					}
+				}
+
+
+				/// The weight of paying, slashing or unbonding one account in the session hooks.
+				fn account_weight() -> Weight {
+					Weight::from_parts(40_000_000, 0) + T::DbWeight::get().reads_writes(3, 3)
+				}
+
+
+				/// Charge the work of the session hooks to the current block.
+				fn register_weight(weight: Weight) {
+					frame_system::Pallet::<T>::register_extra_weight_unchecked(weight, DispatchClass::Mandatory);
+				}
+
+
+				/// The invulnerables, then the candidates with the most stake, their own bond plus
+				/// the stake delegated to them, up to the desired number of candidates.
+				pub(crate) fn select_collators() -> Vec<T::AccountId> {
+					let mut candidates: Vec<(T::AccountId, BalanceOf<T>)> = T::Candidates::candidates()
+						.into_iter()
+						.map(|(candidate, bond)| {
+							let stake = bond.saturating_add(CandidateStake::<T>::get(&candidate));
+							(candidate, stake)
+						})
+						.collect();
+					Self::register_weight(T::DbWeight::get().reads(3 + candidates.len() as u64));
+
+					// the sort is stable, ties keep the order of the candidates
+					candidates.sort_by(|a, b| b.1.cmp(&a.1));
+
+					let mut collators = T::Candidates::invulnerables();
+					collators.extend(
+						candidates
+							.into_iter()
+							.take(T::Candidates::desired_candidates() as usize)
+							.map(|(candidate, _)| candidate),
+					);
+					collators
+				}
+
+
+				/// Share the session reward between the collators that authored blocks, pro rata
+				/// to the blocks authored, and between each collator and its delegators. Returns
+				/// the number of accounts paid.
+				pub(crate) fn distribute_rewards(authored: &[(T::AccountId, u32)]) -> u32 {
+					let total_blocks: u32 = authored.iter().map(|(_, blocks)| *blocks).sum();
+					if total_blocks == 0 {
+						return 0
+					}
+
+					let mut payouts = 0;
+
+					let reward = T::RewardPerSession::get();
+					for (collator, blocks) in authored {
+						let collator_reward = Perbill::from_rational(*blocks, total_blocks) * reward;
+						let stake = CandidateStake::<T>::get(collator);
+
+						payouts += 1;
+						if stake.is_zero() {
+							Self::pay(collator, collator_reward);
+							continue
+						}
+
+						let commission = T::CollatorCommission::get() * collator_reward;
+						let delegators_reward = collator_reward.saturating_sub(commission);
+
+						let mut paid = BalanceOf::<T>::zero();
+						for (delegator, delegation) in Delegations::<T>::iter_prefix(collator) {
+							let share = Perbill::from_rational(delegation, stake) * delegators_reward;
+							paid = paid.saturating_add(share);
+							payouts += 1;
+							Self::pay(&delegator, share);
+						}
+
+						// the collator also keeps the rounding dust
+						Self::pay(collator, collator_reward.saturating_sub(paid));
+					}
+					payouts
+				}
+
+
+				/// Slash the delegations of a collator that did not author any block. Delegations
+				/// left below the minimum are unbonded. Returns the number of delegations slashed.
+				pub(crate) fn slash_collator(collator: &T::AccountId) -> u32 {
+					let fraction = T::MissedSessionSlash::get();
+					if fraction.is_zero() {
+						return 0
+					}
+
+					let mut slashed = BalanceOf::<T>::zero();
+					let mut unbonded = BalanceOf::<T>::zero();
+					let unlock_at = Self::unlock_at();
+					let delegations: Vec<_> = Delegations::<T>::iter_prefix(collator).collect();
+					for (delegator, delegation) in &delegations {
+						let amount = fraction * *delegation;
+						// the slashed credit is dropped, which burns it
+						let (_credit, missing) =
+							T::Currency::slash(&HoldReason::Delegation.into(), delegator, amount);
+						let taken = amount.saturating_sub(missing);
+						slashed = slashed.saturating_add(taken);
+
+						let remaining = delegation.saturating_sub(taken);
+						if remaining >= T::MinDelegation::get() {
+							Delegations::<T>::insert(collator, delegator, remaining);
+							continue
+						}
+
+						Self::remove_delegation(collator, delegator);
+						if !remaining.is_zero() {
+							unbonded = unbonded.saturating_add(remaining);
+							Self::unbond(delegator, remaining, unlock_at);
+							Self::deposit_event(Event::Undelegated {
+								delegator: delegator.clone(),
+								candidate: collator.clone(),
+								amount: remaining,
+								unlock_at,
+							});
+						}
+					}
+
+					let removed = slashed.saturating_add(unbonded);
+					if !removed.is_zero() {
+						CandidateStake::<T>::mutate(collator, |stake| *stake = stake.saturating_sub(removed));
+					}
+					if !slashed.is_zero() {
+						Self::deposit_event(Event::CollatorSlashed { collator: collator.clone(), amount: slashed });
+					}
+					delegations.len() as u32
+				}
+			}
+
+
+
+		//** Session **//
+
+			impl<T: Config> SessionManager<T::AccountId> for Pallet<T> {
+				fn new_session(new_index: SessionIndex) -> Option<Vec<T::AccountId>> {
+					// the inner session manager still kicks the stale candidates first
+					let collators = T::SessionManager::new_session(new_index).map(|_| Self::select_collators());
+
+					if let Some(ref collators) = collators {
+						PlannedCollators::<T>::insert(new_index, CollatorsOf::<T>::truncate_from(collators.clone()));
+					}
+
+					collators
+				}
+
+				fn new_session_genesis(new_index: SessionIndex) -> Option<Vec<T::AccountId>> {
+					let collators = T::SessionManager::new_session_genesis(new_index).map(|_| Self::select_collators());
+
+					if let Some(ref collators) = collators {
+						PlannedCollators::<T>::insert(new_index, CollatorsOf::<T>::truncate_from(collators.clone()));
+					}
+
+					collators
+				}
+
+				fn start_session(start_index: SessionIndex) {
+					T::SessionManager::start_session(start_index);
+
+					CurrentSession::<T>::put(start_index);
+					// without a new plan the collators of the previous session carry on
+					if let Some(collators) = PlannedCollators::<T>::take(start_index) {
+						ActiveCollators::<T>::put(collators);
+					}
+				}
+
+				fn end_session(end_index: SessionIndex) {
+					T::SessionManager::end_session(end_index);
+
+					let authored: Vec<(T::AccountId, u32)> = AuthoredBlocks::<T>::drain().collect();
+					let mut accounts = Self::distribute_rewards(&authored);
+
+					// only punish collators when the chain itself was producing blocks
+					let collators = ActiveCollators::<T>::get();
+					if !authored.is_empty() {
+						for collator in &collators {
+							if !authored.iter().any(|(author, _)| author == collator) {
+								accounts += Self::slash_collator(collator);
+							}
+						}
+					}
+
+					let entries = (authored.len() + collators.len()) as u64;
+					Self::register_weight(
+						T::DbWeight::get()
+							.reads_writes(entries.saturating_add(1), authored.len() as u64)
+							.saturating_add(Self::account_weight().saturating_mul(accounts.into())),
+					);
+				}
+			}
+
+
+
+		//** Authorship **//
+
+			impl<T: Config> pallet_authorship::EventHandler<T::AccountId, BlockNumberFor<T>> for Pallet<T> {
+				fn note_author(author: T::AccountId) {
+					AuthoredBlocks::<T>::mutate(author, |blocks| *blocks = blocks.saturating_add(1));
+
+					frame_system::Pallet::<T>::register_extra_weight_unchecked(
+						T::DbWeight::get().reads_writes(1, 1),
+						DispatchClass::Mandatory,
+					);
+				}
+			}
+	}
+
@@ -0,0 +1,166 @@
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU32, ConstU64, Everything},
+};
+use frame_system as system;
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage, Perbill,
+};
+use std::cell::RefCell;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>, HoldReason},
+		CollatorStaking: crate::{Pallet, Call, Storage, Event<T>, HoldReason},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type MaxHolds = ConstU32<1>;
+}
+
+pub const COLLATOR_A: u64 = 1;
+pub const COLLATOR_B: u64 = 2;
+pub const COLLATOR_C: u64 = 3;
+pub const NOT_CANDIDATE: u64 = 4;
+pub const ALICE: u64 = 10;
+pub const BOB: u64 = 11;
+pub const TREASURY: u64 = 99;
+
+thread_local! {
+	pub static CANDIDATES: RefCell<Vec<(u64, u64)>> = RefCell::new(vec![(COLLATOR_A, 50), (COLLATOR_B, 50)]);
+	pub static DESIRED_CANDIDATES: RefCell<u32> = RefCell::new(2);
+	pub static REWARD_SOURCE: RefCell<Option<u64>> = RefCell::new(None);
+}
+
+/// The candidates of a collator selection without invulnerables.
+pub struct TestCandidates;
+impl crate::CandidateSource<u64, u64> for TestCandidates {
+	fn invulnerables() -> Vec<u64> {
+		Vec::new()
+	}
+
+	fn candidates() -> Vec<(u64, u64)> {
+		CANDIDATES.with(|c| c.borrow().clone())
+	}
+
+	fn desired_candidates() -> u32 {
+		DESIRED_CANDIDATES.with(|d| *d.borrow())
+	}
+}
+
+pub fn set_candidates(candidates: Vec<(u64, u64)>, desired: u32) {
+	CANDIDATES.with(|c| *c.borrow_mut() = candidates);
+	DESIRED_CANDIDATES.with(|d| *d.borrow_mut() = desired);
+}
+
+/// Plans a new session every time, its own pick is replaced by the pallet's.
+pub struct TestSessionManager;
+impl pallet_session::SessionManager<u64> for TestSessionManager {
+	fn new_session(_new_index: u32) -> Option<Vec<u64>> {
+		Some(Vec::new())
+	}
+	fn end_session(_end_index: u32) {}
+	fn start_session(_start_index: u32) {}
+}
+
+pub struct TestRewardSource;
+impl frame_support::traits::Get<Option<u64>> for TestRewardSource {
+	fn get() -> Option<u64> {
+		REWARD_SOURCE.with(|s| *s.borrow())
+	}
+}
+
+pub fn set_reward_source(source: Option<u64>) {
+	REWARD_SOURCE.with(|s| *s.borrow_mut() = source);
+}
+
+parameter_types! {
+	pub const RewardPerSession: u64 = 1_000;
+	pub const CollatorCommission: Perbill = Perbill::from_percent(20);
+	pub const MissedSessionSlash: Perbill = Perbill::from_percent(10);
+}
+
+impl crate::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type Candidates = TestCandidates;
+	type SessionManager = TestSessionManager;
+	type RewardSource = TestRewardSource;
+	type RewardPerSession = RewardPerSession;
+	type CollatorCommission = CollatorCommission;
+	type MissedSessionSlash = MissedSessionSlash;
+	type UnbondingPeriod = ConstU32<2>;
+	type MinDelegation = ConstU64<10>;
+	type MaxDelegatorsPerCandidate = ConstU32<2>;
+	type MaxDelegationsPerDelegator = ConstU32<2>;
+	type MaxUnbondingChunks = ConstU32<2>;
+	type MaxCollators = ConstU32<10>;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(COLLATOR_A, 100), (COLLATOR_B, 100), (ALICE, 1_000), (BOB, 1_000), (TREASURY, 10_000)],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+
+	let mut ext: sp_io::TestExternalities = storage.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,219 @@
+use crate::{mock::*, CandidateStake, Delegations, Error, Event, HoldReason, UnbondingChunk};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::fungible::{Inspect, InspectHold},
+};
+use pallet_authorship::EventHandler;
+use pallet_session::SessionManager;
+
+fn held(who: u64) -> u64 {
+	Balances::balance_on_hold(&HoldReason::Delegation.into(), &who)
+}
+
+/// Plan and start `index`, with the mock collators.
+fn start_session(index: u32) {
+	CollatorStaking::new_session(index);
+	CollatorStaking::start_session(index);
+}
+
+fn author_blocks(author: u64, blocks: u32) {
+	for _ in 0..blocks {
+		CollatorStaking::note_author(author);
+	}
+}
+
+#[test]
+fn delegate_holds_funds() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollatorStaking::delegate(RuntimeOrigin::signed(ALICE), COLLATOR_A, 100));
+		assert_ok!(CollatorStaking::delegate(RuntimeOrigin::signed(ALICE), COLLATOR_A, 50));
+
+		assert_eq!(held(ALICE), 150);
+		assert_eq!(Delegations::<Test>::get(COLLATOR_A, ALICE), Some(150));
+		assert_eq!(CandidateStake::<Test>::get(COLLATOR_A), 150);
+		assert_eq!(CollatorStaking::delegator_count(COLLATOR_A), 1);
+		System::assert_last_event(Event::Delegated { delegator: ALICE, candidate: COLLATOR_A, amount: 50 }.into());
+	});
+}
+
+#[test]
+fn delegate_checks_candidate_and_limits() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CollatorStaking::delegate(RuntimeOrigin::signed(ALICE), NOT_CANDIDATE, 100),
+			Error::<Test>::NotCandidate
+		);
+		assert_noop!(
+			CollatorStaking::delegate(RuntimeOrigin::signed(ALICE), COLLATOR_A, 5),
+			Error::<Test>::DelegationBelowMinimum
+		);
+
+		assert_ok!(CollatorStaking::delegate(RuntimeOrigin::signed(ALICE), COLLATOR_A, 100));
+		assert_ok!(CollatorStaking::delegate(RuntimeOrigin::signed(BOB), COLLATOR_A, 100));
+		assert_noop!(
+			CollatorStaking::delegate(RuntimeOrigin::signed(TREASURY), COLLATOR_A, 100),
+			Error::<Test>::TooManyDelegators
+		);
+	});
+}
+
+#[test]
+fn undelegate_unbonds_until_period_passes() {
+	new_test_ext().execute_with(|| {
+		start_session(1);
+		assert_ok!(CollatorStaking::delegate(RuntimeOrigin::signed(ALICE), COLLATOR_A, 100));
+
+		assert_noop!(
+			CollatorStaking::undelegate(RuntimeOrigin::signed(ALICE), COLLATOR_A, 95),
+			Error::<Test>::DelegationBelowMinimum
+		);
+		assert_ok!(CollatorStaking::undelegate(RuntimeOrigin::signed(ALICE), COLLATOR_A, 100));
+
+		assert_eq!(Delegations::<Test>::get(COLLATOR_A, ALICE), None);
+		assert_eq!(CandidateStake::<Test>::get(COLLATOR_A), 0);
+		assert_eq!(CollatorStaking::unbonding(ALICE).into_inner(), vec![UnbondingChunk { amount: 100, unlock_at: 3 }]);
+
+		// still held until the unbonding period passes
+		assert_eq!(held(ALICE), 100);
+		assert_noop!(
+			CollatorStaking::withdraw_unbonded(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::NothingToWithdraw
+		);
+
+		start_session(3);
+		assert_ok!(CollatorStaking::withdraw_unbonded(RuntimeOrigin::signed(ALICE)));
+		assert_eq!(held(ALICE), 0);
+		assert_eq!(Balances::balance(&ALICE), 1_000);
+		System::assert_last_event(Event::Withdrawn { delegator: ALICE, amount: 100 }.into());
+	});
+}
+
+#[test]
+fn session_rewards_are_shared_with_delegators() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollatorStaking::delegate(RuntimeOrigin::signed(ALICE), COLLATOR_A, 100));
+		assert_ok!(CollatorStaking::delegate(RuntimeOrigin::signed(BOB), COLLATOR_A, 300));
+
+		start_session(1);
+		author_blocks(COLLATOR_A, 3);
+		author_blocks(COLLATOR_B, 1);
+		CollatorStaking::end_session(1);
+
+		// A earned 750, keeps 20% and shares the remaining 600 pro rata
+		assert_eq!(Balances::balance(&COLLATOR_A), 100 + 150);
+		assert_eq!(Balances::balance(&ALICE), 1_000 - 100 + 150);
+		assert_eq!(Balances::balance(&BOB), 1_000 - 300 + 450);
+		// B has no delegators and keeps all of its 250
+		assert_eq!(Balances::balance(&COLLATOR_B), 100 + 250);
+
+		assert_eq!(CollatorStaking::authored_blocks(COLLATOR_A), 0);
+	});
+}
+
+#[test]
+fn session_rewards_can_come_from_an_account() {
+	new_test_ext().execute_with(|| {
+		set_reward_source(Some(TREASURY));
+		let issuance = Balances::total_issuance();
+
+		start_session(1);
+		author_blocks(COLLATOR_A, 1);
+		CollatorStaking::end_session(1);
+
+		assert_eq!(Balances::balance(&COLLATOR_A), 100 + 1_000);
+		assert_eq!(Balances::balance(&TREASURY), 10_000 - 1_000);
+		assert_eq!(Balances::total_issuance(), issuance);
+	});
+}
+
+#[test]
+fn collators_missing_a_session_are_slashed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollatorStaking::delegate(RuntimeOrigin::signed(ALICE), COLLATOR_B, 100));
+		let issuance = Balances::total_issuance();
+
+		start_session(1);
+		author_blocks(COLLATOR_A, 2);
+		CollatorStaking::end_session(1);
+
+		assert_eq!(Delegations::<Test>::get(COLLATOR_B, ALICE), Some(90));
+		assert_eq!(CandidateStake::<Test>::get(COLLATOR_B), 90);
+		assert_eq!(held(ALICE), 90);
+		// the whole reward is minted to A, the slash is burned
+		assert_eq!(Balances::total_issuance(), issuance + 1_000 - 10);
+		assert!(System::events().iter().any(|record| record.event ==
+			Event::CollatorSlashed { collator: COLLATOR_B, amount: 10 }.into()));
+	});
+}
+
+#[test]
+fn no_slash_when_no_blocks_were_authored() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollatorStaking::delegate(RuntimeOrigin::signed(ALICE), COLLATOR_B, 100));
+
+		start_session(1);
+		CollatorStaking::end_session(1);
+
+		assert_eq!(Delegations::<Test>::get(COLLATOR_B, ALICE), Some(100));
+		assert_eq!(held(ALICE), 100);
+	});
+}
+
+#[test]
+fn collators_are_ranked_by_bond_and_delegations() {
+	new_test_ext().execute_with(|| {
+		set_candidates(vec![(COLLATOR_A, 50), (COLLATOR_B, 50), (COLLATOR_C, 60)], 2);
+		start_session(1);
+		assert_eq!(CollatorStaking::active_collators().into_inner(), vec![COLLATOR_C, COLLATOR_A]);
+
+		assert_ok!(CollatorStaking::delegate(RuntimeOrigin::signed(ALICE), COLLATOR_B, 100));
+		start_session(2);
+		assert_eq!(CollatorStaking::active_collators().into_inner(), vec![COLLATOR_B, COLLATOR_C]);
+	});
+}
+
+#[test]
+fn delegations_left_below_the_minimum_by_a_slash_are_unbonded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollatorStaking::delegate(RuntimeOrigin::signed(ALICE), COLLATOR_B, 10));
+		assert_ok!(CollatorStaking::delegate(RuntimeOrigin::signed(BOB), COLLATOR_B, 100));
+
+		start_session(1);
+		author_blocks(COLLATOR_A, 1);
+		CollatorStaking::end_session(1);
+
+		// ALICE's 10 became 9, below the minimum of 10
+		assert_eq!(Delegations::<Test>::get(COLLATOR_B, ALICE), None);
+		assert_eq!(CollatorStaking::delegator_count(COLLATOR_B), 1);
+		assert_eq!(CollatorStaking::delegation_count(ALICE), 0);
+		assert_eq!(CollatorStaking::unbonding(ALICE).into_inner(), vec![UnbondingChunk { amount: 9, unlock_at: 3 }]);
+		assert_eq!(Delegations::<Test>::get(COLLATOR_B, BOB), Some(90));
+		assert_eq!(CandidateStake::<Test>::get(COLLATOR_B), 90);
+
+		// the withdrawal reports what was released, not what was unbonded
+		start_session(3);
+		assert_ok!(CollatorStaking::withdraw_unbonded(RuntimeOrigin::signed(ALICE)));
+		assert_eq!(held(ALICE), 0);
+		System::assert_last_event(Event::Withdrawn { delegator: ALICE, amount: 9 }.into());
+	});
+}
+
+#[test]
+fn withdrawals_report_the_released_amount() {
+	new_test_ext().execute_with(|| {
+		start_session(1);
+		assert_ok!(CollatorStaking::delegate(RuntimeOrigin::signed(ALICE), COLLATOR_A, 100));
+		assert_ok!(CollatorStaking::undelegate(RuntimeOrigin::signed(ALICE), COLLATOR_A, 100));
+
+		// part of the unbonding funds were slashed away by other means
+		let (_credit, _) = <Balances as frame_support::traits::fungible::BalancedHold<u64>>::slash(
+			&HoldReason::Delegation.into(),
+			&ALICE,
+			30,
+		);
+
+		start_session(3);
+		assert_ok!(CollatorStaking::withdraw_unbonded(RuntimeOrigin::signed(ALICE)));
+		System::assert_last_event(Event::Withdrawn { delegator: ALICE, amount: 70 }.into());
+	});
+}
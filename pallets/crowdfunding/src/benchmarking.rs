@@ -0,0 +1,146 @@
+//! Benchmarking setup for kine-crowdfunding
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Crowdfunding;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::{
+	traits::{
+		fungible::{InspectHold, Mutate},
+		Get,
+	},
+	BoundedVec,
+};
+use frame_system::RawOrigin;
+use sp_runtime::{
+	traits::{Bounded, One, Zero},
+	Perbill,
+};
+use sp_std::{vec, vec::Vec};
+
+fn funded<T: Config>(who: &T::AccountId) {
+	T::Currency::set_balance(who, BalanceOf::<T>::max_value() / 4u32.into());
+}
+
+/// A campaign with the most tiers, released in two milestones.
+fn create<T: Config>(creator: &T::AccountId, goal: BalanceOf<T>) -> Result<CampaignId, frame_benchmarking::BenchmarkError> {
+	let description = BoundedVec::truncate_from(vec![0u8; T::MaxTierLength::get() as usize]);
+	let tiers = (0..T::MaxTiers::get())
+		.map(|index| RewardTier { threshold: T::MinContribution::get() * index.into(), description: description.clone() })
+		.collect::<Vec<_>>();
+	let milestones = vec![Perbill::from_percent(40), Perbill::from_percent(60)];
+
+	Crowdfunding::<T>::create_campaign(
+		RawOrigin::Signed(creator.clone()).into(),
+		goal,
+		One::one(),
+		BoundedVec::truncate_from(tiers),
+		BoundedVec::truncate_from(milestones),
+	)?;
+	Ok(NextCampaignId::<T>::get() - 1)
+}
+
+/// `count` backers contributing the minimum.
+fn back<T: Config>(campaign: CampaignId, count: u32) -> Vec<T::AccountId> {
+	let backers: Vec<T::AccountId> = (0..count).map(|index| account("backer", index, 0)).collect();
+	for backer in backers.iter() {
+		funded::<T>(backer);
+		let _ = Crowdfunding::<T>::contribute(RawOrigin::Signed(backer.clone()).into(), campaign, T::MinContribution::get());
+	}
+	backers
+}
+
+/// `count` backers contributing the minimum, then the deadline.
+fn back_and_settle<T: Config>(campaign: CampaignId, count: u32) -> Vec<T::AccountId> {
+	let backers = back::<T>(campaign, count);
+	let deadline = Campaigns::<T>::get(campaign).map(|details| details.deadline).unwrap_or_default();
+	frame_system::Pallet::<T>::set_block_number(deadline);
+	let _ = Crowdfunding::<T>::settle_campaign(RawOrigin::Root.into(), campaign);
+	backers
+}
+
+benchmarks! {
+	create_campaign {
+		let caller: T::AccountId = whitelisted_caller();
+		funded::<T>(&caller);
+		let description = BoundedVec::truncate_from(vec![0u8; T::MaxTierLength::get() as usize]);
+		let tiers = (0..T::MaxTiers::get())
+			.map(|index| RewardTier { threshold: T::MinContribution::get() * index.into(), description: description.clone() })
+			.collect::<Vec<_>>();
+		let milestones = vec![Perbill::from_percent(100)];
+	}: _(
+		RawOrigin::Signed(caller),
+		T::MinContribution::get(),
+		One::one(),
+		BoundedVec::truncate_from(tiers),
+		BoundedVec::truncate_from(milestones)
+	)
+	verify {
+		assert!(Campaigns::<T>::get(0).is_some());
+	}
+
+	contribute {
+		let creator: T::AccountId = account("creator", 0, 0);
+		let campaign = create::<T>(&creator, T::MinContribution::get())?;
+		let caller: T::AccountId = whitelisted_caller();
+		funded::<T>(&caller);
+		let amount = T::MinContribution::get();
+	}: _(RawOrigin::Signed(caller.clone()), campaign, amount)
+	verify {
+		assert_eq!(Contributions::<T>::get(campaign, &caller), Some(amount));
+	}
+
+	approve_milestone {
+		let creator: T::AccountId = account("creator", 0, 0);
+		funded::<T>(&creator);
+		let campaign = create::<T>(&creator, T::MinContribution::get())?;
+		let backers = back_and_settle::<T>(campaign, T::MaxBackers::get());
+		let caller = backers[0].clone();
+		// every other backer approves, so the caller's approval releases the milestone
+		for backer in backers.iter().skip(1) {
+			Approvals::<T>::insert(campaign, backer, ());
+		}
+		Campaigns::<T>::mutate(campaign, |details| {
+			if let Some(details) = details {
+				details.approvals = details.raised;
+			}
+		});
+	}: _(RawOrigin::Signed(caller), campaign)
+	verify {
+		assert_eq!(Campaigns::<T>::get(campaign).map(|details| details.released), Some(1));
+	}
+
+	claim_refund {
+		let creator: T::AccountId = account("creator", 0, 0);
+		let campaign = create::<T>(&creator, BalanceOf::<T>::max_value())?;
+		let backers = back_and_settle::<T>(campaign, 1);
+		let caller = backers[0].clone();
+	}: _(RawOrigin::Signed(caller.clone()), campaign)
+	verify {
+		assert!(T::Currency::balance_on_hold(&HoldReason::Contribution.into(), &caller).is_zero());
+	}
+
+	settle_campaign {
+		let creator: T::AccountId = account("creator", 0, 0);
+		let campaign = create::<T>(&creator, BalanceOf::<T>::max_value())?;
+		back::<T>(campaign, 1);
+		frame_system::Pallet::<T>::set_block_number(frame_system::Pallet::<T>::block_number() + One::one());
+	}: _(RawOrigin::Root, campaign)
+	verify {
+		assert_eq!(Campaigns::<T>::get(campaign).map(|details| details.state), Some(CampaignState::Failed));
+	}
+
+	expire_campaign {
+		let creator: T::AccountId = account("creator", 0, 0);
+		let campaign = create::<T>(&creator, T::MinContribution::get())?;
+		back_and_settle::<T>(campaign, T::MaxBackers::get());
+		let approval_deadline = Campaigns::<T>::get(campaign).map(|details| details.approval_deadline).unwrap_or_default();
+		frame_system::Pallet::<T>::set_block_number(approval_deadline);
+	}: _(RawOrigin::Root, campaign)
+	verify {
+		assert_eq!(Campaigns::<T>::get(campaign).map(|details| details.state), Some(CampaignState::Expired));
+	}
+}
+
+impl_benchmark_test_suite!(Crowdfunding, crate::mock::new_test_ext(), crate::mock::Test);
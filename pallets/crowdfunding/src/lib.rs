@@ -0,0 +1,668 @@
+//** About **//
+	// Crowdfunding campaigns for film productions. A creator opens a campaign with a goal, a
+	// deadline, reward tiers and the milestones the funds are released in. Backers contribute
+	// through balance holds, so the funds stay in their accounts until the campaign is
+	// settled. The scheduler settles each campaign at its deadline: a campaign that reached its
+	// goal succeeds and its funds are released to the creator one milestone tranche at a time,
+	// when backers holding enough of the raised funds approve the milestone. A campaign that
+	// missed its goal fails and its backers are refunded automatically, in batches, with the
+	// spare weight of the following blocks. Each milestone must be approved within
+	// `MilestonePeriod` of the success or of the previous release, otherwise the campaign
+	// expires and its backers claim back the tranches that weren't released. Creators can't
+	// back, and so approve the milestones of, their own campaigns.
+
+
+
+	#![cfg_attr(not(feature = "std"), no_std)]
+
+	pub use pallet::*;
+
+	#[cfg(test)]
+	mod mock;
+
+	#[cfg(test)]
+	mod tests;
+
+	#[cfg(feature = "runtime-benchmarks")]
+	mod benchmarking;
+
+
+
+	#[frame_support::pallet]
+	pub mod pallet {
+
+		//** Config **//
+
+			//* Imports *//
+
+				use frame_support::{
+					dispatch::DispatchResultWithPostInfo,
+					pallet_prelude::*,
+					traits::{
+						fungible::{Inspect, InspectHold, Mutate, MutateHold},
+						schedule::{
+							v3::{Named as ScheduleNamed, TaskName},
+							DispatchTime, LOWEST_PRIORITY,
+						},
+						tokens::{Fortitude, Precision, Restriction},
+						Bounded,
+					},
+					Hashable,
+				};
+				use frame_system::pallet_prelude::*;
+				use sp_runtime::{
+					traits::{Saturating, Zero},
+					Perbill,
+				};
+
+			//* Config *//
+
+				#[pallet::pallet]
+				pub struct Pallet<T>(_);
+
+				#[pallet::config]
+				pub trait Config: frame_system::Config {
+					type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+					/// The overarching call type, to schedule the settlements and expiries.
+					type RuntimeCall: From<Call<Self>> + IsType<<Self as frame_system::Config>::RuntimeCall>;
+
+					/// The caller origin, overarching type of all pallets origins.
+					type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>>;
+
+					/// Settles campaigns at their deadlines and expires the milestones that
+					/// weren't approved in time.
+					type Scheduler: ScheduleNamed<
+						BlockNumberFor<Self>,
+						<Self as frame_system::Config>::RuntimeCall,
+						Self::PalletsOrigin,
+					>;
+
+					/// The currency contributions are held in.
+					type Currency: Inspect<Self::AccountId>
+						+ Mutate<Self::AccountId>
+						+ InspectHold<Self::AccountId>
+						+ MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+					/// The overarching hold reason.
+					type RuntimeHoldReason: From<HoldReason>;
+
+					#[pallet::constant]
+					type MinContribution: Get<BalanceOf<Self>>;
+
+					/// The longest a campaign can raise funds for.
+					#[pallet::constant]
+					type MaxDuration: Get<BlockNumberFor<Self>>;
+
+					/// The part of the raised funds whose backers must approve a milestone
+					/// before its tranche is released.
+					#[pallet::constant]
+					type MilestoneApproval: Get<Perbill>;
+
+					/// How long backers have to approve a milestone, from the success of the
+					/// campaign or the release of the previous milestone.
+					#[pallet::constant]
+					type MilestonePeriod: Get<BlockNumberFor<Self>>;
+
+					#[pallet::constant]
+					type MaxBackers: Get<u32>;
+
+					#[pallet::constant]
+					type MaxTiers: Get<u32>;
+
+					#[pallet::constant]
+					type MaxMilestones: Get<u32>;
+
+					/// The maximum length of the description of a reward tier.
+					#[pallet::constant]
+					type MaxTierLength: Get<u32>;
+				}
+
+
+
+		//** Types **//
+
+			//* Types *//
+
+				pub type CampaignId = u32;
+				pub type BalanceOf<T> =
+					<<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+				pub type TierOf<T> = RewardTier<BalanceOf<T>, BoundedVec<u8, <T as Config>::MaxTierLength>>;
+				pub type TiersOf<T> = BoundedVec<TierOf<T>, <T as Config>::MaxTiers>;
+				pub type MilestonesOf<T> = BoundedVec<Perbill, <T as Config>::MaxMilestones>;
+				pub type CampaignOf<T> = Campaign<
+					<T as frame_system::Config>::AccountId,
+					BalanceOf<T>,
+					BlockNumberFor<T>,
+					TiersOf<T>,
+					MilestonesOf<T>,
+				>;
+
+			//* Constants *//
+			//* Enums *//
+
+				#[pallet::composite_enum]
+				pub enum HoldReason {
+					/// Funds contributed to a campaign that weren't released or refunded yet.
+					Contribution,
+				}
+
+				#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+				pub enum CampaignState {
+					/// Raising funds until the deadline.
+					Funding,
+					/// Reached its goal, releasing the funds milestone by milestone.
+					Succeeded,
+					/// Missed its goal, refunding its backers.
+					Failed,
+					/// Every milestone was released.
+					Completed,
+					/// Missed the approval deadline of a milestone, its backers claim back
+					/// what wasn't released.
+					Expired,
+				}
+
+			//* Structs *//
+
+				/// A reward for the backers that contributed at least `threshold`.
+				#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+				pub struct RewardTier<Balance, Description> {
+					pub threshold: Balance,
+					pub description: Description,
+				}
+
+				#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+				pub struct Campaign<AccountId, Balance, BlockNumber, Tiers, Milestones> {
+					pub creator: AccountId,
+					pub goal: Balance,
+					pub raised: Balance,
+					pub deadline: BlockNumber,
+					pub tiers: Tiers,
+					/// The part of the raised funds each milestone releases, adding up to 100%.
+					pub milestones: Milestones,
+					/// How many milestones were released.
+					pub released: u32,
+					/// When the approval of the next milestone closes.
+					pub approval_deadline: BlockNumber,
+					/// The contributions of the backers that approved the next milestone.
+					pub approvals: Balance,
+					pub backers: u32,
+					pub state: CampaignState,
+				}
+
+
+
+		//** Storage **//
+
+			#[pallet::storage]
+			#[pallet::getter(fn next_campaign_id)]
+			pub type NextCampaignId<T> = StorageValue<_, CampaignId, ValueQuery>;
+
+			#[pallet::storage]
+			#[pallet::getter(fn campaign)]
+			pub type Campaigns<T: Config> = StorageMap<_, Twox64Concat, CampaignId, CampaignOf<T>>;
+
+			/// What each backer contributed to each campaign.
+			#[pallet::storage]
+			#[pallet::getter(fn contribution)]
+			pub type Contributions<T: Config> = StorageDoubleMap<
+				_,
+				Twox64Concat, CampaignId,
+				Blake2_128Concat, T::AccountId,
+				BalanceOf<T>,
+			>;
+
+			/// The backers that approved the next milestone of each campaign.
+			#[pallet::storage]
+			pub type Approvals<T: Config> = StorageDoubleMap<
+				_,
+				Twox64Concat, CampaignId,
+				Blake2_128Concat, T::AccountId,
+				(),
+			>;
+
+			/// The failed campaigns whose backers are still being refunded.
+			#[pallet::storage]
+			#[pallet::getter(fn pending_refunds)]
+			pub type PendingRefunds<T> = StorageMap<_, Twox64Concat, CampaignId, ()>;
+
+
+
+		//** Events **//
+
+			#[pallet::event]
+			#[pallet::generate_deposit(pub(super) fn deposit_event)]
+			pub enum Event<T: Config> {
+				CampaignCreated {
+					campaign: CampaignId,
+					creator: T::AccountId,
+					goal: BalanceOf<T>,
+					deadline: BlockNumberFor<T>,
+				},
+				/// `tier` is the index of the best reward tier the backer's total contribution
+				/// reaches.
+				Contributed { campaign: CampaignId, backer: T::AccountId, amount: BalanceOf<T>, tier: Option<u32> },
+				CampaignSucceeded { campaign: CampaignId, raised: BalanceOf<T> },
+				CampaignFailed { campaign: CampaignId, raised: BalanceOf<T> },
+				Refunded { campaign: CampaignId, backer: T::AccountId, amount: BalanceOf<T> },
+				RefundsCompleted { campaign: CampaignId },
+				MilestoneApproved { campaign: CampaignId, backer: T::AccountId, approvals: BalanceOf<T> },
+				MilestoneReleased { campaign: CampaignId, milestone: u32, amount: BalanceOf<T> },
+				CampaignCompleted { campaign: CampaignId },
+				/// `milestone` wasn't approved in time, the rest of the contributions can be
+				/// claimed back.
+				CampaignExpired { campaign: CampaignId, milestone: u32 },
+			}
+
+
+
+		//** Errors **//
+
+			#[pallet::error]
+			pub enum Error<T> {
+				UnknownCampaign,
+				InvalidGoal,
+				InvalidDuration,
+				NoMilestones,
+				ZeroMilestone,
+				/// The milestones don't add up to 100%.
+				MilestonesNotWhole,
+				/// Creators can't back their own campaigns.
+				SelfBacking,
+				/// The deadline of the campaign, or of its milestone approval, didn't pass yet.
+				DeadlineNotReached,
+				CampaignNotFunding,
+				ContributionBelowMinimum,
+				TooManyBackers,
+				CampaignNotSucceeded,
+				/// The campaign neither failed nor expired.
+				CampaignNotRefunding,
+				NotBacker,
+				AlreadyApproved,
+			}
+
+
+		//** Hooks **//
+		#[pallet::hooks]
+		impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+
+			fn on_idle(_now: BlockNumberFor<T>, remaining: Weight) -> Weight {
+				Self::process_refunds(remaining)
+			}
+
+		}
+
+
+
+		//** Extrinsics **//
+
+			#[pallet::call]
+			impl<T: Config> Pallet<T> {
+
+				#[pallet::call_index(0)]
+				#[pallet::weight(Weight::from_parts(60_000_000, 0) + T::DbWeight::get().reads_writes(6, 7))]
+				pub fn create_campaign(
+					origin: OriginFor<T>,
+					goal: BalanceOf<T>,
+					duration: BlockNumberFor<T>,
+					tiers: TiersOf<T>,
+					milestones: MilestonesOf<T>,
+				) -> DispatchResultWithPostInfo {
+					let creator = ensure_signed(origin)?;
+
+					ensure!(!goal.is_zero(), Error::<T>::InvalidGoal);
+					ensure!(!duration.is_zero() && duration <= T::MaxDuration::get(), Error::<T>::InvalidDuration);
+					Self::ensure_valid_milestones(&milestones)?;
+
+					let campaign = NextCampaignId::<T>::get();
+					let deadline = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+					let approval_deadline = deadline.saturating_add(T::MilestonePeriod::get());
+
+					Self::schedule(Self::settle_task(campaign), deadline, Call::settle_campaign { campaign })?;
+					Self::schedule(Self::expiry_task(campaign), approval_deadline, Call::expire_campaign { campaign })?;
+					NextCampaignId::<T>::put(campaign.saturating_add(1));
+
+					Campaigns::<T>::insert(campaign, Campaign {
+						creator: creator.clone(),
+						goal,
+						raised: Zero::zero(),
+						deadline,
+						tiers,
+						milestones,
+						released: 0,
+						approval_deadline,
+						approvals: Zero::zero(),
+						backers: 0,
+						state: CampaignState::Funding,
+					});
+
+					Self::deposit_event(Event::CampaignCreated { campaign, creator, goal, deadline });
+					Ok(().into())
+				}
+
+
+				#[pallet::call_index(1)]
+				#[pallet::weight(Weight::from_parts(60_000_000, 0) + T::DbWeight::get().reads_writes(4, 4))]
+				pub fn contribute(
+					origin: OriginFor<T>,
+					campaign: CampaignId,
+					amount: BalanceOf<T>,
+				) -> DispatchResultWithPostInfo {
+					let backer = ensure_signed(origin)?;
+
+					let mut details = Campaigns::<T>::get(campaign).ok_or(Error::<T>::UnknownCampaign)?;
+					ensure!(backer != details.creator, Error::<T>::SelfBacking);
+					ensure!(
+						details.state == CampaignState::Funding
+							&& frame_system::Pallet::<T>::block_number() < details.deadline,
+						Error::<T>::CampaignNotFunding
+					);
+					ensure!(amount >= T::MinContribution::get(), Error::<T>::ContributionBelowMinimum);
+
+					let current = Contributions::<T>::get(campaign, &backer);
+					if current.is_none() {
+						ensure!(details.backers < T::MaxBackers::get(), Error::<T>::TooManyBackers);
+						details.backers += 1;
+					}
+
+					T::Currency::hold(&HoldReason::Contribution.into(), &backer, amount)?;
+
+					let total = current.unwrap_or_default().saturating_add(amount);
+					Contributions::<T>::insert(campaign, &backer, total);
+					details.raised = details.raised.saturating_add(amount);
+					let tier = Self::tier_of(&details.tiers, total);
+					Campaigns::<T>::insert(campaign, details);
+
+					Self::deposit_event(Event::Contributed { campaign, backer, amount, tier });
+					Ok(().into())
+				}
+
+
+				/// Approve the release of the next milestone of a successful campaign. The
+				/// approval that reaches `MilestoneApproval` releases the milestone tranche of
+				/// every backer to the creator, and opens the approval of the next milestone.
+				#[pallet::call_index(2)]
+				#[pallet::weight(
+					Weight::from_parts(40_000_000, 0)
+						+ T::DbWeight::get().reads_writes(5, 5)
+						+ Pallet::<T>::release_weight(T::MaxBackers::get())
+				)]
+				pub fn approve_milestone(origin: OriginFor<T>, campaign: CampaignId) -> DispatchResultWithPostInfo {
+					let backer = ensure_signed(origin)?;
+
+					let mut details = Campaigns::<T>::get(campaign).ok_or(Error::<T>::UnknownCampaign)?;
+					ensure!(backer != details.creator, Error::<T>::SelfBacking);
+					ensure!(
+						details.state == CampaignState::Succeeded
+							&& frame_system::Pallet::<T>::block_number() < details.approval_deadline,
+						Error::<T>::CampaignNotSucceeded
+					);
+					let contribution = Contributions::<T>::get(campaign, &backer).ok_or(Error::<T>::NotBacker)?;
+					ensure!(!Approvals::<T>::contains_key(campaign, &backer), Error::<T>::AlreadyApproved);
+
+					Approvals::<T>::insert(campaign, &backer, ());
+					details.approvals = details.approvals.saturating_add(contribution);
+					Self::deposit_event(Event::MilestoneApproved { campaign, backer, approvals: details.approvals });
+
+					if details.approvals < T::MilestoneApproval::get() * details.raised {
+						Campaigns::<T>::insert(campaign, details);
+						return Ok(Some(Weight::from_parts(40_000_000, 0) + T::DbWeight::get().reads_writes(3, 2)).into())
+					}
+
+					Self::release_milestone(campaign, &mut details)?;
+					Campaigns::<T>::insert(campaign, details);
+					Ok(().into())
+				}
+
+
+				/// Refund a backer of a failed campaign straight away, without waiting for the
+				/// automatic refunds, or refund the tranches an expired campaign didn't release.
+				#[pallet::call_index(3)]
+				#[pallet::weight(Weight::from_parts(40_000_000, 0) + T::DbWeight::get().reads_writes(2, 2))]
+				pub fn claim_refund(origin: OriginFor<T>, campaign: CampaignId) -> DispatchResultWithPostInfo {
+					let backer = ensure_signed(origin)?;
+
+					let details = Campaigns::<T>::get(campaign).ok_or(Error::<T>::UnknownCampaign)?;
+					let released = match details.state {
+						CampaignState::Failed => Perbill::zero(),
+						CampaignState::Expired => Self::released_by(&details.milestones, details.released),
+						_ => return Err(Error::<T>::CampaignNotRefunding.into()),
+					};
+					let contribution = Contributions::<T>::take(campaign, &backer).ok_or(Error::<T>::NotBacker)?;
+
+					Self::refund(campaign, &backer, contribution.saturating_sub(released * contribution));
+					Ok(().into())
+				}
+
+
+				/// Settle a campaign at its deadline, scheduled when the campaign is created.
+				#[pallet::call_index(4)]
+				#[pallet::weight(Weight::from_parts(30_000_000, 0) + T::DbWeight::get().reads_writes(3, 4))]
+				pub fn settle_campaign(origin: OriginFor<T>, campaign: CampaignId) -> DispatchResultWithPostInfo {
+					ensure_root(origin)?;
+
+					let mut details = Campaigns::<T>::get(campaign).ok_or(Error::<T>::UnknownCampaign)?;
+					ensure!(details.state == CampaignState::Funding, Error::<T>::CampaignNotFunding);
+					ensure!(
+						frame_system::Pallet::<T>::block_number() >= details.deadline,
+						Error::<T>::DeadlineNotReached
+					);
+					let raised = details.raised;
+
+					if raised >= details.goal {
+						details.state = CampaignState::Succeeded;
+						Self::deposit_event(Event::CampaignSucceeded { campaign, raised });
+					} else {
+						// there is no milestone left to expire
+						let _ = T::Scheduler::cancel_named(Self::expiry_task(campaign));
+						details.state = CampaignState::Failed;
+						PendingRefunds::<T>::insert(campaign, ());
+						Self::deposit_event(Event::CampaignFailed { campaign, raised });
+					}
+
+					Campaigns::<T>::insert(campaign, details);
+					Ok(().into())
+				}
+
+
+				/// Expire a successful campaign whose next milestone wasn't approved by its
+				/// approval deadline, scheduled when the campaign is created and moved on by each
+				/// release.
+				#[pallet::call_index(5)]
+				#[pallet::weight(Weight::from_parts(30_000_000, 0) + T::DbWeight::get().reads_writes(1, 2))]
+				pub fn expire_campaign(origin: OriginFor<T>, campaign: CampaignId) -> DispatchResultWithPostInfo {
+					ensure_root(origin)?;
+
+					let mut details = Campaigns::<T>::get(campaign).ok_or(Error::<T>::UnknownCampaign)?;
+					ensure!(details.state == CampaignState::Succeeded, Error::<T>::CampaignNotSucceeded);
+					ensure!(
+						frame_system::Pallet::<T>::block_number() >= details.approval_deadline,
+						Error::<T>::DeadlineNotReached
+					);
+
+					let _ = Approvals::<T>::clear_prefix(campaign, details.backers, None);
+					details.approvals = Zero::zero();
+					details.state = CampaignState::Expired;
+					Self::deposit_event(Event::CampaignExpired { campaign, milestone: details.released });
+
+					Campaigns::<T>::insert(campaign, details);
+					Ok(().into())
+				}
+
+
+			}
+
+
+
+		//** Helpers **//
+
+			impl<T: Config> Pallet<T> {
+
+				/// The index of the best reward tier `contribution` reaches.
+				pub fn tier_of(tiers: &TiersOf<T>, contribution: BalanceOf<T>) -> Option<u32> {
+					tiers
+						.iter()
+						.enumerate()
+						.filter(|(_, tier)| tier.threshold <= contribution)
+						.max_by_key(|(_, tier)| tier.threshold)
+						.map(|(index, _)| index as u32)
+				}
+
+
+				pub fn release_weight(backers: u32) -> Weight {
+					Weight::from_parts(60_000_000, 0)
+						.saturating_add(T::DbWeight::get().reads_writes(3, 4))
+						.saturating_mul(backers as u64)
+				}
+
+
+				fn refund_weight() -> Weight {
+					Weight::from_parts(40_000_000, 0).saturating_add(T::DbWeight::get().reads_writes(2, 2))
+				}
+
+
+				fn ensure_valid_milestones(milestones: &MilestonesOf<T>) -> DispatchResult {
+					ensure!(!milestones.is_empty(), Error::<T>::NoMilestones);
+					ensure!(milestones.iter().all(|share| !share.is_zero()), Error::<T>::ZeroMilestone);
+
+					let total: u64 = milestones.iter().map(|share| share.deconstruct() as u64).sum();
+					ensure!(total == Perbill::one().deconstruct() as u64, Error::<T>::MilestonesNotWhole);
+
+					Ok(())
+				}
+
+
+				fn settle_task(campaign: CampaignId) -> TaskName {
+					(b"kine/crowdfunding/settle", campaign).blake2_256()
+				}
+
+
+				fn expiry_task(campaign: CampaignId) -> TaskName {
+					(b"kine/crowdfunding/expire", campaign).blake2_256()
+				}
+
+
+				/// Schedule a call of this pallet, dispatched as root.
+				fn schedule(task: TaskName, when: BlockNumberFor<T>, call: Call<T>) -> DispatchResult {
+					let call: <T as frame_system::Config>::RuntimeCall = <T as Config>::RuntimeCall::from(call).into();
+					// the calls of this pallet are a few bytes long, well within the inline bound
+					let call = BoundedVec::try_from(call.encode()).map_err(|_| DispatchError::Exhausted)?;
+
+					T::Scheduler::schedule_named(
+						task,
+						DispatchTime::At(when),
+						None,
+						LOWEST_PRIORITY,
+						frame_system::RawOrigin::Root.into(),
+						Bounded::Inline(call),
+					)?;
+					Ok(())
+				}
+
+
+				/// The part of each contribution the first `count` milestones release.
+				fn released_by(milestones: &MilestonesOf<T>, count: u32) -> Perbill {
+					milestones.iter().take(count as usize).fold(Perbill::zero(), |total, share| total.saturating_add(*share))
+				}
+
+
+				/// Pay the next milestone tranche of every backer to the creator. A tranche is
+				/// the part of a contribution the milestones up to this one release, minus what
+				/// the earlier milestones released, so the last milestone releases the rest.
+				fn release_milestone(campaign: CampaignId, details: &mut CampaignOf<T>) -> DispatchResult {
+					let milestone = details.released;
+					let released_before = Self::released_by(&details.milestones, milestone);
+					let released_after = Self::released_by(&details.milestones, milestone + 1);
+					let last = milestone as usize + 1 == details.milestones.len();
+
+					let mut amount = BalanceOf::<T>::zero();
+					for (backer, contribution) in Contributions::<T>::iter_prefix(campaign) {
+						let tranche = if last {
+							contribution.saturating_sub(released_before * contribution)
+						} else {
+							(released_after * contribution).saturating_sub(released_before * contribution)
+						};
+						if tranche.is_zero() {
+							continue
+						}
+
+						T::Currency::transfer_on_hold(
+							&HoldReason::Contribution.into(),
+							&backer,
+							&details.creator,
+							tranche,
+							Precision::Exact,
+							Restriction::Free,
+							Fortitude::Polite,
+						)?;
+						amount = amount.saturating_add(tranche);
+					}
+
+					let _ = Approvals::<T>::clear_prefix(campaign, details.backers, None);
+					details.approvals = Zero::zero();
+					details.released = milestone + 1;
+					Self::deposit_event(Event::MilestoneReleased { campaign, milestone, amount });
+
+					if last {
+						T::Scheduler::cancel_named(Self::expiry_task(campaign))?;
+						let _ = Contributions::<T>::clear_prefix(campaign, details.backers, None);
+						details.state = CampaignState::Completed;
+						Self::deposit_event(Event::CampaignCompleted { campaign });
+					} else {
+						let deadline = frame_system::Pallet::<T>::block_number().saturating_add(T::MilestonePeriod::get());
+						// a release in the block the campaign succeeded keeps its deadline
+						if deadline != details.approval_deadline {
+							T::Scheduler::reschedule_named(Self::expiry_task(campaign), DispatchTime::At(deadline))?;
+							details.approval_deadline = deadline;
+						}
+					}
+
+					Ok(())
+				}
+
+
+				fn refund(campaign: CampaignId, backer: &T::AccountId, amount: BalanceOf<T>) {
+					match T::Currency::release(&HoldReason::Contribution.into(), backer, amount, Precision::BestEffort) {
+						Ok(amount) => Self::deposit_event(Event::Refunded { campaign, backer: backer.clone(), amount }),
+						Err(error) => log::warn!(
+							target: "runtime::crowdfunding",
+							"failed to refund {:?} of campaign {}: {:?}",
+							amount,
+							campaign,
+							error,
+						),
+					}
+				}
+
+
+				/// Refund the backers of failed campaigns with the weight left in the block.
+				pub(crate) fn process_refunds(remaining: Weight) -> Weight {
+					let mut used = T::DbWeight::get().reads(1);
+					if remaining.any_lt(used) {
+						return Weight::zero()
+					}
+
+					while let Some(campaign) = PendingRefunds::<T>::iter_keys().next() {
+						if remaining.any_lt(used.saturating_add(Self::refund_weight())) {
+							break
+						}
+						used.saturating_accrue(Self::refund_weight());
+
+						match Contributions::<T>::iter_prefix(campaign).next() {
+							Some((backer, amount)) => {
+								Contributions::<T>::remove(campaign, &backer);
+								Self::refund(campaign, &backer, amount);
+							},
+							None => {
+								PendingRefunds::<T>::remove(campaign);
+								Self::deposit_event(Event::RefundsCompleted { campaign });
+							},
+						}
+					}
+
+					used
+				}
+
+
+			}
+	}
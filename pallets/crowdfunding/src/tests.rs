@@ -0,0 +1,241 @@
+use crate::{
+	mock::*, CampaignState, Campaigns, Contributions, Error, Event, HoldReason, MilestonesOf, RewardTier, TiersOf,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{
+		fungible::{InspectHold, Mutate, MutateHold},
+		Hooks,
+	},
+};
+use sp_runtime::{DispatchError, Perbill};
+
+fn held(who: u64) -> u64 {
+	Balances::balance_on_hold(&HoldReason::Contribution.into(), &who)
+}
+
+fn milestones(percents: Vec<u32>) -> MilestonesOf<Test> {
+	percents.into_iter().map(Perbill::from_percent).collect::<Vec<_>>().try_into().unwrap()
+}
+
+fn tiers() -> TiersOf<Test> {
+	vec![
+		RewardTier { threshold: 10, description: b"credits".to_vec().try_into().unwrap() },
+		RewardTier { threshold: 200, description: b"premiere".to_vec().try_into().unwrap() },
+	]
+	.try_into()
+	.unwrap()
+}
+
+/// A campaign for 500 ending at block 11, released in a 40% and a 60% milestone.
+fn open_campaign() -> u32 {
+	assert_ok!(Crowdfunding::create_campaign(RuntimeOrigin::signed(CREATOR), 500, 10, tiers(), milestones(vec![40, 60])));
+	0
+}
+
+#[test]
+fn create_campaign_checks_parameters() {
+	new_test_ext().execute_with(|| {
+		let create = |goal, duration, shares| {
+			Crowdfunding::create_campaign(RuntimeOrigin::signed(CREATOR), goal, duration, tiers(), milestones(shares))
+		};
+
+		assert_noop!(create(0, 10, vec![100]), Error::<Test>::InvalidGoal);
+		assert_noop!(create(500, 0, vec![100]), Error::<Test>::InvalidDuration);
+		assert_noop!(create(500, 101, vec![100]), Error::<Test>::InvalidDuration);
+		assert_noop!(create(500, 10, vec![]), Error::<Test>::NoMilestones);
+		assert_noop!(create(500, 10, vec![100, 0]), Error::<Test>::ZeroMilestone);
+		assert_noop!(create(500, 10, vec![40, 40]), Error::<Test>::MilestonesNotWhole);
+
+		assert_ok!(create(500, 10, vec![100]));
+		assert_ok!(create(500, 10, vec![100]));
+		assert_noop!(create(500, 10, vec![100]), DispatchError::Exhausted);
+		System::assert_has_event(Event::CampaignCreated { campaign: 0, creator: CREATOR, goal: 500, deadline: 11 }.into());
+	});
+}
+
+#[test]
+fn contribute_holds_funds_and_reports_the_tier() {
+	new_test_ext().execute_with(|| {
+		let campaign = open_campaign();
+
+		assert_ok!(Crowdfunding::contribute(RuntimeOrigin::signed(ALICE), campaign, 150));
+		System::assert_last_event(Event::Contributed { campaign, backer: ALICE, amount: 150, tier: Some(0) }.into());
+		assert_ok!(Crowdfunding::contribute(RuntimeOrigin::signed(ALICE), campaign, 100));
+		System::assert_last_event(Event::Contributed { campaign, backer: ALICE, amount: 100, tier: Some(1) }.into());
+
+		assert_eq!(held(ALICE), 250);
+		assert_eq!(Crowdfunding::contribution(campaign, ALICE), Some(250));
+		assert_eq!(Crowdfunding::campaign(campaign).unwrap().raised, 250);
+
+		assert_noop!(
+			Crowdfunding::contribute(RuntimeOrigin::signed(BOB), campaign, 5),
+			Error::<Test>::ContributionBelowMinimum
+		);
+		assert_ok!(Crowdfunding::contribute(RuntimeOrigin::signed(BOB), campaign, 10));
+		assert_ok!(Crowdfunding::contribute(RuntimeOrigin::signed(CHARLIE), campaign, 10));
+		assert_noop!(
+			Crowdfunding::contribute(RuntimeOrigin::signed(DAVE), campaign, 10),
+			Error::<Test>::TooManyBackers
+		);
+
+		run_to_block(11);
+		assert_noop!(
+			Crowdfunding::contribute(RuntimeOrigin::signed(ALICE), campaign, 10),
+			Error::<Test>::CampaignNotFunding
+		);
+	});
+}
+
+#[test]
+fn failed_campaigns_refund_backers_automatically() {
+	new_test_ext().execute_with(|| {
+		let campaign = open_campaign();
+		assert_ok!(Crowdfunding::contribute(RuntimeOrigin::signed(ALICE), campaign, 100));
+		assert_ok!(Crowdfunding::contribute(RuntimeOrigin::signed(BOB), campaign, 50));
+
+		run_to_block(11);
+
+		assert_eq!(Crowdfunding::campaign(campaign).unwrap().state, CampaignState::Failed);
+		assert_eq!(held(ALICE), 0);
+		assert_eq!(held(BOB), 0);
+		assert_eq!(Balances::free_balance(ALICE), 1_000);
+		assert_eq!(Crowdfunding::contribution(campaign, ALICE), None);
+		assert_eq!(Crowdfunding::pending_refunds(campaign), None);
+		System::assert_has_event(Event::CampaignFailed { campaign, raised: 150 }.into());
+		System::assert_has_event(Event::Refunded { campaign, backer: BOB, amount: 50 }.into());
+		System::assert_last_event(Event::RefundsCompleted { campaign }.into());
+	});
+}
+
+#[test]
+fn backers_can_claim_refunds_before_the_automatic_ones() {
+	new_test_ext().execute_with(|| {
+		let campaign = open_campaign();
+		assert_ok!(Crowdfunding::contribute(RuntimeOrigin::signed(ALICE), campaign, 100));
+		assert_noop!(
+			Crowdfunding::claim_refund(RuntimeOrigin::signed(ALICE), campaign),
+			Error::<Test>::CampaignNotRefunding
+		);
+
+		System::set_block_number(11);
+		Scheduler::on_initialize(11);
+
+		assert_ok!(Crowdfunding::claim_refund(RuntimeOrigin::signed(ALICE), campaign));
+		assert_eq!(held(ALICE), 0);
+		assert_noop!(Crowdfunding::claim_refund(RuntimeOrigin::signed(ALICE), campaign), Error::<Test>::NotBacker);
+	});
+}
+
+#[test]
+fn successful_campaigns_release_funds_by_approved_milestones() {
+	new_test_ext().execute_with(|| {
+		let campaign = open_campaign();
+		assert_ok!(Crowdfunding::contribute(RuntimeOrigin::signed(ALICE), campaign, 300));
+		assert_ok!(Crowdfunding::contribute(RuntimeOrigin::signed(BOB), campaign, 200));
+		assert_noop!(
+			Crowdfunding::approve_milestone(RuntimeOrigin::signed(ALICE), campaign),
+			Error::<Test>::CampaignNotSucceeded
+		);
+
+		run_to_block(11);
+		System::assert_has_event(Event::CampaignSucceeded { campaign, raised: 500 }.into());
+		assert_noop!(
+			Crowdfunding::approve_milestone(RuntimeOrigin::signed(CHARLIE), campaign),
+			Error::<Test>::NotBacker
+		);
+
+		// Alice holds more than half of the raised funds
+		assert_ok!(Crowdfunding::approve_milestone(RuntimeOrigin::signed(ALICE), campaign));
+		System::assert_last_event(Event::MilestoneReleased { campaign, milestone: 0, amount: 200 }.into());
+		assert_eq!(Balances::free_balance(CREATOR), 10 + 200);
+		assert_eq!(held(ALICE), 180);
+		assert_eq!(held(BOB), 120);
+
+		assert_ok!(Crowdfunding::approve_milestone(RuntimeOrigin::signed(BOB), campaign));
+		assert_noop!(
+			Crowdfunding::approve_milestone(RuntimeOrigin::signed(BOB), campaign),
+			Error::<Test>::AlreadyApproved
+		);
+		assert_eq!(Balances::free_balance(CREATOR), 10 + 200);
+
+		assert_ok!(Crowdfunding::approve_milestone(RuntimeOrigin::signed(ALICE), campaign));
+		System::assert_last_event(Event::CampaignCompleted { campaign }.into());
+		assert_eq!(Balances::free_balance(CREATOR), 10 + 500);
+		assert_eq!(held(ALICE), 0);
+		assert_eq!(held(BOB), 0);
+		assert_eq!(Crowdfunding::campaign(campaign).unwrap().state, CampaignState::Completed);
+	});
+}
+
+#[test]
+fn creators_cannot_back_or_approve_their_campaigns() {
+	new_test_ext().execute_with(|| {
+		let campaign = open_campaign();
+		assert_noop!(
+			Crowdfunding::contribute(RuntimeOrigin::signed(CREATOR), campaign, 10),
+			Error::<Test>::SelfBacking
+		);
+
+		// a majority stake of the creator, planted in storage since `contribute` refuses it
+		assert_ok!(Balances::mint_into(&CREATOR, 400));
+		assert_ok!(Balances::hold(&HoldReason::Contribution.into(), &CREATOR, 400));
+		Contributions::<Test>::insert(campaign, CREATOR, 400);
+		Campaigns::<Test>::mutate(campaign, |details| {
+			let details = details.as_mut().unwrap();
+			details.raised += 400;
+			details.backers += 1;
+		});
+		assert_ok!(Crowdfunding::contribute(RuntimeOrigin::signed(ALICE), campaign, 100));
+
+		run_to_block(11);
+		assert_eq!(Crowdfunding::campaign(campaign).unwrap().state, CampaignState::Succeeded);
+		assert_noop!(
+			Crowdfunding::approve_milestone(RuntimeOrigin::signed(CREATOR), campaign),
+			Error::<Test>::SelfBacking
+		);
+		assert_eq!(Crowdfunding::campaign(campaign).unwrap().released, 0);
+		assert_eq!(held(CREATOR), 400);
+	});
+}
+
+#[test]
+fn backers_claim_back_the_tranches_of_expired_campaigns() {
+	new_test_ext().execute_with(|| {
+		let campaign = open_campaign();
+		assert_ok!(Crowdfunding::contribute(RuntimeOrigin::signed(ALICE), campaign, 300));
+		assert_ok!(Crowdfunding::contribute(RuntimeOrigin::signed(BOB), campaign, 200));
+		assert_noop!(Crowdfunding::settle_campaign(RuntimeOrigin::signed(ALICE), campaign), DispatchError::BadOrigin);
+		assert_noop!(Crowdfunding::settle_campaign(RuntimeOrigin::root(), campaign), Error::<Test>::DeadlineNotReached);
+
+		// the release moves the approval deadline of the next milestone to block 35
+		run_to_block(15);
+		assert_ok!(Crowdfunding::approve_milestone(RuntimeOrigin::signed(ALICE), campaign));
+		assert_eq!(Crowdfunding::campaign(campaign).unwrap().approval_deadline, 35);
+		assert_noop!(
+			Crowdfunding::claim_refund(RuntimeOrigin::signed(ALICE), campaign),
+			Error::<Test>::CampaignNotRefunding
+		);
+
+		run_to_block(34);
+		assert_ok!(Crowdfunding::approve_milestone(RuntimeOrigin::signed(BOB), campaign));
+		assert_eq!(Crowdfunding::campaign(campaign).unwrap().state, CampaignState::Succeeded);
+
+		run_to_block(35);
+		System::assert_has_event(Event::CampaignExpired { campaign, milestone: 1 }.into());
+		assert_eq!(Crowdfunding::campaign(campaign).unwrap().state, CampaignState::Expired);
+		assert_noop!(
+			Crowdfunding::approve_milestone(RuntimeOrigin::signed(ALICE), campaign),
+			Error::<Test>::CampaignNotSucceeded
+		);
+
+		// the 40% milestone stays with the creator, the rest goes back to the backers
+		assert_ok!(Crowdfunding::claim_refund(RuntimeOrigin::signed(ALICE), campaign));
+		System::assert_last_event(Event::Refunded { campaign, backer: ALICE, amount: 180 }.into());
+		assert_ok!(Crowdfunding::claim_refund(RuntimeOrigin::signed(BOB), campaign));
+		assert_eq!((held(ALICE), held(BOB)), (0, 0));
+		assert_eq!(Balances::free_balance(ALICE), 1_000 - 120);
+		assert_eq!(Balances::free_balance(BOB), 1_000 - 80);
+		assert_eq!(Balances::free_balance(CREATOR), 10 + 200);
+	});
+}
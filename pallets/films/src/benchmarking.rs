@@ -0,0 +1,107 @@
+//! Benchmarking setup for kine-films
+
+use super::*;
+
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller, BenchmarkError};
+use frame_support::{
+	traits::{fungible::Mutate, EnsureOrigin, Get},
+	BoundedVec,
+};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+use sp_std::{vec, vec::Vec};
+
+fn funded_owner<T: Config>() -> T::AccountId {
+	let owner: T::AccountId = whitelisted_caller();
+	T::Currency::set_balance(&owner, BalanceOf::<T>::max_value() / 4u32.into());
+	owner
+}
+
+fn longest_name<T: Config>() -> NameOf<T> {
+	BoundedVec::truncate_from(vec![b'a'; T::MaxNameLength::get() as usize])
+}
+
+/// Add a category and `count` tags, returning the tags.
+fn curate<T: Config>(count: u32) -> Result<TagsOf<T>, BenchmarkError> {
+	let origin = T::CuratorOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+	Pallet::<T>::add_category(origin.clone(), longest_name::<T>())?;
+	let tags = (0..count)
+		.map(|_| {
+			Pallet::<T>::add_tag(origin.clone(), longest_name::<T>())?;
+			Ok(NextTagId::<T>::get() - 1)
+		})
+		.collect::<Result<Vec<_>, BenchmarkError>>()?;
+	Ok(BoundedVec::truncate_from(tags))
+}
+
+fn register<T: Config>(owner: &T::AccountId) -> Result<(), BenchmarkError> {
+	let tags = curate::<T>(T::MaxTags::get())?;
+	Pallet::<T>::register_film(RawOrigin::Signed(owner.clone()).into(), Default::default(), 0, tags)?;
+	Ok(())
+}
+
+benchmarks! {
+	register_film {
+		let t in 0 .. T::MaxTags::get();
+		let owner = funded_owner::<T>();
+		let tags = curate::<T>(t)?;
+	}: _(RawOrigin::Signed(owner.clone()), Default::default(), 0, tags)
+	verify {
+		assert_eq!(Pallet::<T>::owner_of(0), Some(owner));
+	}
+
+	update_film {
+		let t in 0 .. T::MaxTags::get();
+		let owner = funded_owner::<T>();
+		register::<T>(&owner)?;
+		let tags = BoundedVec::truncate_from((0..t).collect::<Vec<_>>());
+	}: _(RawOrigin::Signed(owner), 0, Default::default(), 0, tags)
+
+	transfer_film {
+		let owner = funded_owner::<T>();
+		register::<T>(&owner)?;
+		let receiver: T::AccountId = frame_benchmarking::account("receiver", 0, 0);
+	}: _(RawOrigin::Signed(owner), 0, receiver.clone())
+	verify {
+		assert_eq!(Pallet::<T>::owner_of(0), Some(receiver));
+	}
+
+	remove_film {
+		let owner = funded_owner::<T>();
+		register::<T>(&owner)?;
+	}: _(RawOrigin::Signed(owner), 0)
+	verify {
+		assert!(!Films::<T>::contains_key(0));
+	}
+
+	add_category {
+		let origin = T::CuratorOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(origin, longest_name::<T>())
+	verify {
+		assert!(Categories::<T>::contains_key(0));
+	}
+
+	add_tag {
+		let origin = T::CuratorOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(origin, longest_name::<T>())
+	verify {
+		assert!(Tags::<T>::contains_key(0));
+	}
+
+	publish_text {
+		let b in 0 .. T::MaxBlobLength::get();
+		let owner = funded_owner::<T>();
+		register::<T>(&owner)?;
+		// Subtitles in as many languages as fit, the synopsis coming last.
+		let subtitles = (0..T::MaxTexts::get().saturating_sub(1))
+			.map(|index| (TextKind::Subtitles((index as u16).to_le_bytes()), T::Hash::default()))
+			.collect::<Vec<_>>();
+		Texts::<T>::insert(0, BoundedVec::truncate_from(subtitles));
+		let text = BoundedVec::truncate_from(vec![b'a'; b as usize]);
+	}: _(RawOrigin::Signed(owner), 0, TextKind::Synopsis, text)
+	verify {
+		assert!(Texts::<T>::get(0).iter().any(|(kind, _)| *kind == TextKind::Synopsis));
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,440 @@
+//** About **//
+	// The registry of films. A rights holder registers a film with the hash of its metadata,
+	// kept off chain, in one category and under a few tags, holding a deposit for as long as
+	// the film stays registered. Categories and tags are curated by the curator origin, so
+	// every film is filed under names frontends can rely on. The owner can refile the film,
+	// hand it over to another account, which takes over the deposit, or remove it to get the
+	// deposit back. Other pallets check film ids against the registry through `Contains`,
+	// and contracts reach it through the content chain extension of the runtime. The owner
+	// publishes the synopsis and subtitles of a film as blobs written to the offchain index of
+	// nodes running with `--enable-offchain-indexing true`, keeping only their hash on chain;
+	// nodes serve blobs by hash with the `blobs_get` RPC.
+
+
+
+	#![cfg_attr(not(feature = "std"), no_std)]
+
+	pub use pallet::*;
+
+	pub mod weights;
+	pub use weights::WeightInfo;
+
+	#[cfg(test)]
+	mod mock;
+
+	#[cfg(test)]
+	mod tests;
+
+	#[cfg(feature = "runtime-benchmarks")]
+	mod benchmarking;
+
+	use sp_std::vec::Vec;
+
+	/// The prefix of the offchain index keys of blobs, followed by their hash.
+	pub const BLOB_PREFIX: &[u8] = b"kine::blob::";
+
+	/// The offchain index key of the blob with `hash`, for every pallet writing blobs.
+	pub fn blob_key(hash: &[u8]) -> Vec<u8> {
+		[BLOB_PREFIX, hash].concat()
+	}
+
+
+
+	#[frame_support::pallet]
+	pub mod pallet {
+
+		//** Config **//
+
+			//* Imports *//
+
+				use crate::WeightInfo;
+				use frame_support::{
+					dispatch::DispatchResultWithPostInfo,
+					pallet_prelude::*,
+					traits::{
+						fungible::{Inspect, InspectHold, Mutate, MutateHold},
+						tokens::{Fortitude, Precision, Restriction},
+						Contains,
+					},
+				};
+				use frame_system::pallet_prelude::*;
+				use sp_runtime::traits::Hash;
+
+			//* Config *//
+
+				#[pallet::pallet]
+				pub struct Pallet<T>(_);
+
+				#[pallet::config]
+				pub trait Config: frame_system::Config {
+					type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+					/// The currency film deposits are held in.
+					type Currency: Inspect<Self::AccountId>
+						+ Mutate<Self::AccountId>
+						+ InspectHold<Self::AccountId>
+						+ MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+					/// The overarching hold reason.
+					type RuntimeHoldReason: From<HoldReason>;
+
+					/// Adds the categories and tags films are filed under.
+					type CuratorOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+					/// The deposit held for every registered film.
+					#[pallet::constant]
+					type FilmDeposit: Get<BalanceOf<Self>>;
+
+					/// The maximum number of tags of a film.
+					#[pallet::constant]
+					type MaxTags: Get<u32>;
+
+					/// The maximum length of the name of a category or a tag.
+					#[pallet::constant]
+					type MaxNameLength: Get<u32>;
+
+					/// The maximum length of a synopsis or of subtitles.
+					#[pallet::constant]
+					type MaxBlobLength: Get<u32>;
+
+					/// The maximum number of texts of a film, its synopsis and subtitles.
+					#[pallet::constant]
+					type MaxTexts: Get<u32>;
+
+					/// Weight information for the extrinsics of this pallet.
+					type WeightInfo: WeightInfo;
+				}
+
+
+
+		//** Types **//
+
+			//* Types *//
+
+				pub type FilmId = u32;
+				pub type CategoryId = u32;
+				pub type TagId = u32;
+				pub type BalanceOf<T> =
+					<<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+				pub type NameOf<T> = BoundedVec<u8, <T as Config>::MaxNameLength>;
+				pub type TagsOf<T> = BoundedVec<TagId, <T as Config>::MaxTags>;
+				pub type BlobOf<T> = BoundedVec<u8, <T as Config>::MaxBlobLength>;
+				pub type TextsOf<T> = BoundedVec<(TextKind, <T as frame_system::Config>::Hash), <T as Config>::MaxTexts>;
+				/// An ISO 639-1 language code, such as `*b"en"`.
+				pub type Language = [u8; 2];
+				pub type FilmOf<T> = Film<
+					<T as frame_system::Config>::AccountId,
+					<T as frame_system::Config>::Hash,
+					TagsOf<T>,
+					BalanceOf<T>,
+				>;
+
+			//* Constants *//
+			//* Enums *//
+
+				#[pallet::composite_enum]
+				pub enum HoldReason {
+					/// The deposit of a registered film.
+					FilmDeposit,
+				}
+
+				#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+				pub enum TextKind {
+					Synopsis,
+					Subtitles(Language),
+				}
+
+			//* Structs *//
+
+				#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+				pub struct Film<AccountId, Hash, Tags, Balance> {
+					/// The rights holder that manages the film and holds its deposit.
+					pub owner: AccountId,
+					/// The hash of the metadata of the film, stored off chain, such as on IPFS.
+					pub metadata: Hash,
+					pub category: CategoryId,
+					pub tags: Tags,
+					pub deposit: Balance,
+				}
+
+
+
+		//** Storage **//
+
+			#[pallet::storage]
+			#[pallet::getter(fn next_film_id)]
+			pub type NextFilmId<T> = StorageValue<_, FilmId, ValueQuery>;
+
+			#[pallet::storage]
+			#[pallet::getter(fn film)]
+			pub type Films<T: Config> = StorageMap<_, Twox64Concat, FilmId, FilmOf<T>>;
+
+			/// The hashes of the texts of a film written to the offchain index.
+			#[pallet::storage]
+			#[pallet::getter(fn texts)]
+			pub type Texts<T: Config> = StorageMap<_, Twox64Concat, FilmId, TextsOf<T>, ValueQuery>;
+
+			#[pallet::storage]
+			#[pallet::getter(fn next_category_id)]
+			pub type NextCategoryId<T> = StorageValue<_, CategoryId, ValueQuery>;
+
+			#[pallet::storage]
+			#[pallet::getter(fn category)]
+			pub type Categories<T: Config> = StorageMap<_, Twox64Concat, CategoryId, NameOf<T>>;
+
+			#[pallet::storage]
+			#[pallet::getter(fn next_tag_id)]
+			pub type NextTagId<T> = StorageValue<_, TagId, ValueQuery>;
+
+			#[pallet::storage]
+			#[pallet::getter(fn tag)]
+			pub type Tags<T: Config> = StorageMap<_, Twox64Concat, TagId, NameOf<T>>;
+
+
+
+		//** Events **//
+
+			#[pallet::event]
+			#[pallet::generate_deposit(pub(super) fn deposit_event)]
+			pub enum Event<T: Config> {
+				FilmRegistered { film: FilmId, owner: T::AccountId, category: CategoryId },
+				FilmUpdated { film: FilmId, category: CategoryId },
+				FilmTransferred { film: FilmId, owner: T::AccountId },
+				FilmRemoved { film: FilmId },
+				TextPublished { film: FilmId, kind: TextKind, hash: T::Hash },
+				CategoryAdded { category: CategoryId, name: NameOf<T> },
+				TagAdded { tag: TagId, name: NameOf<T> },
+			}
+
+
+
+		//** Errors **//
+
+			#[pallet::error]
+			pub enum Error<T> {
+				UnknownFilm,
+				NotOwner,
+				UnknownCategory,
+				UnknownTag,
+				DuplicateTag,
+				TooManyTexts,
+			}
+
+
+		//** Hooks **//
+		#[pallet::hooks]
+		impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+
+		}
+
+
+
+		//** Extrinsics **//
+
+			#[pallet::call]
+			impl<T: Config> Pallet<T> {
+
+				/// Register a film owned by the caller, holding the film deposit.
+				#[pallet::call_index(0)]
+				#[pallet::weight(T::WeightInfo::register_film(tags.len() as u32))]
+				pub fn register_film(
+					origin: OriginFor<T>,
+					metadata: T::Hash,
+					category: CategoryId,
+					tags: TagsOf<T>,
+				) -> DispatchResultWithPostInfo {
+					let owner = ensure_signed(origin)?;
+
+					Self::ensure_valid_filing(category, &tags)?;
+
+					let deposit = T::FilmDeposit::get();
+					T::Currency::hold(&HoldReason::FilmDeposit.into(), &owner, deposit)?;
+
+					let film = NextFilmId::<T>::get();
+					NextFilmId::<T>::put(film.saturating_add(1));
+					Films::<T>::insert(film, Film { owner: owner.clone(), metadata, category, tags, deposit });
+
+					Self::deposit_event(Event::FilmRegistered { film, owner, category });
+					Ok(().into())
+				}
+
+
+				/// Change the metadata, category and tags of a film.
+				#[pallet::call_index(1)]
+				#[pallet::weight(T::WeightInfo::update_film(tags.len() as u32))]
+				pub fn update_film(
+					origin: OriginFor<T>,
+					film: FilmId,
+					metadata: T::Hash,
+					category: CategoryId,
+					tags: TagsOf<T>,
+				) -> DispatchResultWithPostInfo {
+					let who = ensure_signed(origin)?;
+
+					Self::ensure_valid_filing(category, &tags)?;
+
+					Films::<T>::try_mutate(film, |maybe_film| -> DispatchResult {
+						let details = maybe_film.as_mut().ok_or(Error::<T>::UnknownFilm)?;
+						ensure!(details.owner == who, Error::<T>::NotOwner);
+						details.metadata = metadata;
+						details.category = category;
+						details.tags = tags;
+						Ok(())
+					})?;
+
+					Self::deposit_event(Event::FilmUpdated { film, category });
+					Ok(().into())
+				}
+
+
+				/// Hand a film over to `owner`. The deposit is transferred along with the film and
+				/// stays held on the new owner.
+				#[pallet::call_index(2)]
+				#[pallet::weight(T::WeightInfo::transfer_film())]
+				pub fn transfer_film(
+					origin: OriginFor<T>,
+					film: FilmId,
+					owner: T::AccountId,
+				) -> DispatchResultWithPostInfo {
+					let who = ensure_signed(origin)?;
+
+					Films::<T>::try_mutate(film, |maybe_film| -> DispatchResult {
+						let details = maybe_film.as_mut().ok_or(Error::<T>::UnknownFilm)?;
+						ensure!(details.owner == who, Error::<T>::NotOwner);
+						T::Currency::transfer_on_hold(
+							&HoldReason::FilmDeposit.into(),
+							&who,
+							&owner,
+							details.deposit,
+							Precision::BestEffort,
+							Restriction::OnHold,
+							Fortitude::Polite,
+						)?;
+						details.owner = owner.clone();
+						Ok(())
+					})?;
+
+					Self::deposit_event(Event::FilmTransferred { film, owner });
+					Ok(().into())
+				}
+
+
+				/// Remove a film from the registry, getting the deposit back. Its texts stay in the
+				/// offchain index, as other content may have the same hash.
+				#[pallet::call_index(3)]
+				#[pallet::weight(T::WeightInfo::remove_film())]
+				pub fn remove_film(origin: OriginFor<T>, film: FilmId) -> DispatchResultWithPostInfo {
+					let who = ensure_signed(origin)?;
+
+					let details = Films::<T>::get(film).ok_or(Error::<T>::UnknownFilm)?;
+					ensure!(details.owner == who, Error::<T>::NotOwner);
+
+					T::Currency::release(&HoldReason::FilmDeposit.into(), &who, details.deposit, Precision::BestEffort)?;
+					Films::<T>::remove(film);
+					Texts::<T>::remove(film);
+
+					Self::deposit_event(Event::FilmRemoved { film });
+					Ok(().into())
+				}
+
+
+				#[pallet::call_index(4)]
+				#[pallet::weight(T::WeightInfo::add_category())]
+				pub fn add_category(origin: OriginFor<T>, name: NameOf<T>) -> DispatchResultWithPostInfo {
+					T::CuratorOrigin::ensure_origin(origin)?;
+
+					let category = NextCategoryId::<T>::get();
+					NextCategoryId::<T>::put(category.saturating_add(1));
+					Categories::<T>::insert(category, &name);
+
+					Self::deposit_event(Event::CategoryAdded { category, name });
+					Ok(().into())
+				}
+
+
+				#[pallet::call_index(5)]
+				#[pallet::weight(T::WeightInfo::add_tag())]
+				pub fn add_tag(origin: OriginFor<T>, name: NameOf<T>) -> DispatchResultWithPostInfo {
+					T::CuratorOrigin::ensure_origin(origin)?;
+
+					let tag = NextTagId::<T>::get();
+					NextTagId::<T>::put(tag.saturating_add(1));
+					Tags::<T>::insert(tag, &name);
+
+					Self::deposit_event(Event::TagAdded { tag, name });
+					Ok(().into())
+				}
+
+
+				/// Publish the synopsis or subtitles of a film, replacing the previous text of
+				/// the same kind. The text is written to the offchain index and its hash kept on
+				/// chain.
+				#[pallet::call_index(6)]
+				#[pallet::weight(T::WeightInfo::publish_text(text.len() as u32))]
+				pub fn publish_text(
+					origin: OriginFor<T>,
+					film: FilmId,
+					kind: TextKind,
+					text: BlobOf<T>,
+				) -> DispatchResultWithPostInfo {
+					let who = ensure_signed(origin)?;
+
+					let details = Films::<T>::get(film).ok_or(Error::<T>::UnknownFilm)?;
+					ensure!(details.owner == who, Error::<T>::NotOwner);
+
+					let hash = T::Hashing::hash(&text);
+					Texts::<T>::try_mutate(film, |texts| -> DispatchResult {
+						match texts.iter_mut().find(|(published, _)| *published == kind) {
+							Some((_, previous)) => *previous = hash,
+							None => texts.try_push((kind, hash)).map_err(|_| Error::<T>::TooManyTexts)?,
+						}
+						Ok(())
+					})?;
+					sp_io::offchain_index::set(&crate::blob_key(hash.as_ref()), &text);
+
+					Self::deposit_event(Event::TextPublished { film, kind, hash });
+					Ok(().into())
+				}
+
+
+			}
+
+
+
+		//** Helpers **//
+
+			impl<T: Config> Pallet<T> {
+
+				/// The owner of `film`, if it is registered.
+				pub fn owner_of(film: FilmId) -> Option<T::AccountId> {
+					Films::<T>::get(film).map(|details| details.owner)
+				}
+
+
+				/// Whether `film` is filed under `tag`.
+				pub fn has_tag(film: FilmId, tag: TagId) -> bool {
+					Films::<T>::get(film).map_or(false, |details| details.tags.contains(&tag))
+				}
+
+
+				fn ensure_valid_filing(category: CategoryId, tags: &TagsOf<T>) -> DispatchResult {
+					ensure!(Categories::<T>::contains_key(category), Error::<T>::UnknownCategory);
+
+					for (index, tag) in tags.iter().enumerate() {
+						ensure!(Tags::<T>::contains_key(tag), Error::<T>::UnknownTag);
+						ensure!(!tags[..index].contains(tag), Error::<T>::DuplicateTag);
+					}
+
+					Ok(())
+				}
+
+
+			}
+
+
+			impl<T: Config> Contains<FilmId> for Pallet<T> {
+				fn contains(film: &FilmId) -> bool {
+					Films::<T>::contains_key(film)
+				}
+			}
+	}
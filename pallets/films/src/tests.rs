@@ -0,0 +1,140 @@
+use crate::{mock::*, Error, Event, HoldReason, TextKind};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{fungible::InspectHold, Contains},
+};
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	DispatchError,
+};
+
+fn name(name: &[u8]) -> crate::NameOf<Test> {
+	name.to_vec().try_into().unwrap()
+}
+
+fn tags(tags: &[u32]) -> crate::TagsOf<Test> {
+	tags.to_vec().try_into().unwrap()
+}
+
+fn held(who: u64) -> u64 {
+	Balances::balance_on_hold(&HoldReason::FilmDeposit.into(), &who)
+}
+
+/// Add the categories `0` and `1` and the tags `0` and `1`.
+fn curate() {
+	assert_ok!(Films::add_category(RuntimeOrigin::root(), name(b"Documentary")));
+	assert_ok!(Films::add_category(RuntimeOrigin::root(), name(b"Animation")));
+	assert_ok!(Films::add_tag(RuntimeOrigin::root(), name(b"Nature")));
+	assert_ok!(Films::add_tag(RuntimeOrigin::root(), name(b"Ocean")));
+}
+
+#[test]
+fn only_the_curator_adds_categories_and_tags() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Films::add_category(RuntimeOrigin::signed(ALICE), name(b"Drama")), DispatchError::BadOrigin);
+		assert_noop!(Films::add_tag(RuntimeOrigin::signed(ALICE), name(b"Noir")), DispatchError::BadOrigin);
+
+		curate();
+		assert_eq!(Films::category(1), Some(name(b"Animation")));
+		assert_eq!(Films::tag(0), Some(name(b"Nature")));
+		System::assert_last_event(Event::TagAdded { tag: 1, name: name(b"Ocean") }.into());
+	});
+}
+
+#[test]
+fn films_are_filed_under_curated_categories_and_tags() {
+	new_test_ext().execute_with(|| {
+		curate();
+		let metadata = H256::repeat_byte(1);
+
+		assert_noop!(
+			Films::register_film(RuntimeOrigin::signed(ALICE), metadata, 2, tags(&[])),
+			Error::<Test>::UnknownCategory
+		);
+		assert_noop!(
+			Films::register_film(RuntimeOrigin::signed(ALICE), metadata, 0, tags(&[0, 2])),
+			Error::<Test>::UnknownTag
+		);
+		assert_noop!(
+			Films::register_film(RuntimeOrigin::signed(ALICE), metadata, 0, tags(&[1, 1])),
+			Error::<Test>::DuplicateTag
+		);
+		assert_noop!(
+			Films::register_film(RuntimeOrigin::signed(POOR), metadata, 0, tags(&[])),
+			sp_runtime::TokenError::FundsUnavailable
+		);
+
+		assert_ok!(Films::register_film(RuntimeOrigin::signed(ALICE), metadata, 0, tags(&[0, 1])));
+		assert_eq!(held(ALICE), 10);
+		assert!(Films::contains(&0));
+		assert!(!Films::contains(&1));
+		assert_eq!(Films::owner_of(0), Some(ALICE));
+		assert!(Films::has_tag(0, 1));
+		System::assert_last_event(Event::FilmRegistered { film: 0, owner: ALICE, category: 0 }.into());
+
+		assert_noop!(
+			Films::update_film(RuntimeOrigin::signed(BOB), 0, metadata, 1, tags(&[])),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(Films::update_film(RuntimeOrigin::signed(ALICE), 0, H256::repeat_byte(2), 1, tags(&[0])));
+		let film = Films::film(0).unwrap();
+		assert_eq!((film.metadata, film.category), (H256::repeat_byte(2), 1));
+		assert!(!Films::has_tag(0, 1));
+	});
+}
+
+#[test]
+fn the_deposit_follows_the_film() {
+	new_test_ext().execute_with(|| {
+		curate();
+		assert_ok!(Films::register_film(RuntimeOrigin::signed(ALICE), H256::zero(), 0, tags(&[])));
+
+		assert_noop!(Films::transfer_film(RuntimeOrigin::signed(BOB), 0, BOB), Error::<Test>::NotOwner);
+		assert_ok!(Films::transfer_film(RuntimeOrigin::signed(ALICE), 0, BOB));
+		assert_eq!((held(ALICE), held(BOB)), (0, 10));
+		assert_eq!(Balances::free_balance(ALICE), 90);
+		assert_eq!(Films::owner_of(0), Some(BOB));
+
+		assert_noop!(Films::remove_film(RuntimeOrigin::signed(ALICE), 0), Error::<Test>::NotOwner);
+		assert_ok!(Films::remove_film(RuntimeOrigin::signed(BOB), 0));
+		assert_eq!(held(BOB), 0);
+		assert_eq!(Balances::free_balance(BOB), 110);
+		assert!(!Films::contains(&0));
+		assert_noop!(Films::remove_film(RuntimeOrigin::signed(BOB), 0), Error::<Test>::UnknownFilm);
+	});
+}
+
+#[test]
+fn owners_publish_texts_to_the_offchain_index() {
+	let synopsis = vec![b'a'; 500];
+	let hash = BlakeTwo256::hash(&synopsis);
+	let text = |text: &[u8]| crate::BlobOf::<Test>::truncate_from(text.to_vec());
+
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		curate();
+		assert_ok!(Films::register_film(RuntimeOrigin::signed(ALICE), H256::zero(), 0, tags(&[])));
+
+		assert_noop!(
+			Films::publish_text(RuntimeOrigin::signed(BOB), 0, TextKind::Synopsis, text(&synopsis)),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(Films::publish_text(RuntimeOrigin::signed(ALICE), 0, TextKind::Synopsis, text(b"Draft")));
+		assert_ok!(Films::publish_text(RuntimeOrigin::signed(ALICE), 0, TextKind::Synopsis, text(&synopsis)));
+		System::assert_last_event(Event::TextPublished { film: 0, kind: TextKind::Synopsis, hash }.into());
+		assert_ok!(Films::publish_text(RuntimeOrigin::signed(ALICE), 0, TextKind::Subtitles(*b"en"), text(b"1")));
+		assert_noop!(
+			Films::publish_text(RuntimeOrigin::signed(ALICE), 0, TextKind::Subtitles(*b"pt"), text(b"1")),
+			Error::<Test>::TooManyTexts
+		);
+		assert_eq!(Films::texts(0).len(), 2);
+		assert_eq!(Films::texts(0)[0], (TextKind::Synopsis, hash));
+
+		assert_ok!(Films::remove_film(RuntimeOrigin::signed(ALICE), 0));
+		assert!(Films::texts(0).is_empty());
+	});
+
+	ext.persist_offchain_overlay();
+	assert_eq!(ext.offchain_db().get(&crate::blob_key(hash.as_bytes())), Some(synopsis));
+}
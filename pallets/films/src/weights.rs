@@ -0,0 +1,135 @@
+//! Weights for `kine_films`
+//!
+//! Conservative estimates until the pallet is first benchmarked on the reference hardware.
+//! Regenerate with `./scripts/benchmark.sh kine_films`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `kine_films`.
+pub trait WeightInfo {
+	fn register_film(t: u32, ) -> Weight;
+	fn update_film(t: u32, ) -> Weight;
+	fn transfer_film() -> Weight;
+	fn remove_film() -> Weight;
+	fn add_category() -> Weight;
+	fn add_tag() -> Weight;
+	fn publish_text(b: u32, ) -> Weight;
+}
+
+/// Weights for `kine_films` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `Films::Categories` (r:1 w:0)
+	/// Storage: `Films::Tags` (r:8 w:0)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `Films::NextFilmId` (r:1 w:1)
+	/// Storage: `Films::Films` (r:0 w:1)
+	/// The range of component `t` is `[0, 8]`.
+	fn register_film(t: u32, ) -> Weight {
+		Weight::from_parts(45_000_000, 4_000)
+			.saturating_add(Weight::from_parts(3_000_000, 2_500).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(t.into())))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `Films::Categories` (r:1 w:0)
+	/// Storage: `Films::Tags` (r:8 w:0)
+	/// Storage: `Films::Films` (r:1 w:1)
+	/// The range of component `t` is `[0, 8]`.
+	fn update_film(t: u32, ) -> Weight {
+		Weight::from_parts(25_000_000, 3_500)
+			.saturating_add(Weight::from_parts(3_000_000, 2_500).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(t.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Films::Films` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:2 w:2)
+	/// Storage: `System::Account` (r:2 w:2)
+	fn transfer_film() -> Weight {
+		Weight::from_parts(60_000_000, 6_000)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	/// Storage: `Films::Films` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `Films::Texts` (r:0 w:1)
+	fn remove_film() -> Weight {
+		Weight::from_parts(40_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `Films::NextCategoryId` (r:1 w:1)
+	/// Storage: `Films::Categories` (r:0 w:1)
+	fn add_category() -> Weight {
+		Weight::from_parts(15_000_000, 1_500)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Films::NextTagId` (r:1 w:1)
+	/// Storage: `Films::Tags` (r:0 w:1)
+	fn add_tag() -> Weight {
+		Weight::from_parts(15_000_000, 1_500)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Films::Films` (r:1 w:0)
+	/// Storage: `Films::Texts` (r:1 w:1)
+	/// The range of component `b` is `[0, 65536]`.
+	fn publish_text(b: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 5_000)
+			.saturating_add(Weight::from_parts(1_500, 1).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	fn register_film(t: u32, ) -> Weight {
+		Weight::from_parts(45_000_000, 4_000)
+			.saturating_add(Weight::from_parts(3_000_000, 2_500).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(t.into())))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	fn update_film(t: u32, ) -> Weight {
+		Weight::from_parts(25_000_000, 3_500)
+			.saturating_add(Weight::from_parts(3_000_000, 2_500).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(t.into())))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn transfer_film() -> Weight {
+		Weight::from_parts(60_000_000, 6_000)
+			.saturating_add(RocksDbWeight::get().reads(5))
+			.saturating_add(RocksDbWeight::get().writes(5))
+	}
+	fn remove_film() -> Weight {
+		Weight::from_parts(40_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	fn add_category() -> Weight {
+		Weight::from_parts(15_000_000, 1_500)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	fn add_tag() -> Weight {
+		Weight::from_parts(15_000_000, 1_500)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	fn publish_text(b: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 5_000)
+			.saturating_add(Weight::from_parts(1_500, 1).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+}
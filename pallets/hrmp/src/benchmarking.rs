@@ -0,0 +1,51 @@
+//! Benchmarking setup for kine-hrmp
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as HrmpManager;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+
+benchmarks! {
+	request_open_channel {
+		let origin = T::ChannelManagerOrigin::try_successful_origin()
+			.map_err(|_| frame_benchmarking::BenchmarkError::Weightless)?;
+		let recipient: cumulus_primitives_core::ParaId = (u32::from(T::SelfParaId::get()) + 1).into();
+	}: _<T::RuntimeOrigin>(origin, recipient, 8, 1024)
+	verify {
+		assert_eq!(Channels::<T>::get((T::SelfParaId::get(), recipient)), Some(ChannelStatus::Requested));
+	}
+
+	accept_open_channel {
+		let origin = T::ChannelManagerOrigin::try_successful_origin()
+			.map_err(|_| frame_benchmarking::BenchmarkError::Weightless)?;
+		let sender: cumulus_primitives_core::ParaId = (u32::from(T::SelfParaId::get()) + 1).into();
+	}: _<T::RuntimeOrigin>(origin, sender)
+	verify {
+		assert_eq!(Channels::<T>::get((sender, T::SelfParaId::get())), Some(ChannelStatus::Accepted));
+	}
+
+	close_channel {
+		let origin = T::ChannelManagerOrigin::try_successful_origin()
+			.map_err(|_| frame_benchmarking::BenchmarkError::Weightless)?;
+		let sender = T::SelfParaId::get();
+		let recipient: cumulus_primitives_core::ParaId = (u32::from(sender) + 1).into();
+		Channels::<T>::insert((sender, recipient), ChannelStatus::Open);
+	}: _<T::RuntimeOrigin>(origin, sender, recipient)
+	verify {
+		assert_eq!(Channels::<T>::get((sender, recipient)), Some(ChannelStatus::Closing));
+	}
+
+	force_clear {
+		let origin = T::ChannelManagerOrigin::try_successful_origin()
+			.map_err(|_| frame_benchmarking::BenchmarkError::Weightless)?;
+		let sender = T::SelfParaId::get();
+		let recipient: cumulus_primitives_core::ParaId = (u32::from(sender) + 1).into();
+		Channels::<T>::insert((sender, recipient), ChannelStatus::Requested);
+	}: _<T::RuntimeOrigin>(origin, sender, recipient)
+	verify {
+		assert!(!Channels::<T>::contains_key((sender, recipient)));
+	}
+}
+
+impl_benchmark_test_suite!(HrmpManager, crate::mock::new_test_ext(), crate::mock::Test,);
@@ -0,0 +1,353 @@
+//** About **//
+	// Governance driven management of the HRMP channels between this parachain and its
+	// siblings. Channel requests, acceptances and closures are encoded as relay chain
+	// calls, wrapped in a `Transact` and sent upwards. The status of every channel is
+	// kept in sync with the messaging state the relay chain reports each block. A channel
+	// whose `Transact` failed on the relay chain stays `Requested` or `Accepted`, the
+	// manager origin clears it with `force_clear` before trying again.
+
+
+
+	#![cfg_attr(not(feature = "std"), no_std)]
+
+	pub use pallet::*;
+
+	pub mod weights;
+	pub use weights::WeightInfo;
+
+	#[cfg(test)]
+	mod mock;
+
+	#[cfg(test)]
+	mod tests;
+
+	#[cfg(feature = "runtime-benchmarks")]
+	mod benchmarking;
+
+
+
+	#[frame_support::pallet]
+	pub mod pallet {
+
+		//** Config **//
+
+			//* Imports *//
+
+				use crate::WeightInfo;
+				use codec::Encode;
+				use cumulus_primitives_core::ParaId;
+				use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
+				use frame_system::pallet_prelude::*;
+				use sp_std::{vec, vec::Vec};
+				use xcm::latest::prelude::*;
+
+			//* Config *//
+
+				#[pallet::pallet]
+				pub struct Pallet<T>(_);
+
+				#[pallet::config]
+				pub trait Config: frame_system::Config {
+					type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+					/// The origin allowed to request, accept, close and clear channels.
+					type ChannelManagerOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+					/// The id of this parachain.
+					type SelfParaId: Get<ParaId>;
+
+					/// The router used to deliver the `Transact` messages to the relay chain.
+					type XcmSender: SendXcm;
+
+					/// The source of the channels the relay chain currently considers open.
+					type ChannelSource: HrmpChannelSource;
+
+					/// The index of the `Hrmp` pallet in the relay chain runtime.
+					#[pallet::constant]
+					type RelayHrmpPalletIndex: Get<u8>;
+
+					/// The amount of the relay chain token withdrawn from the sovereign account
+					/// to pay for each `Transact`.
+					#[pallet::constant]
+					type RelayFee: Get<u128>;
+
+					/// The weight bought on the relay chain for each `Transact`.
+					#[pallet::constant]
+					type RelayTransactWeight: Get<Weight>;
+
+					/// Weight information for the extrinsics of this pallet.
+					type WeightInfo: WeightInfo;
+				}
+
+
+
+		//** Types **//
+
+			//* Types *//
+
+				/// A channel is identified by its `(sender, recipient)` pair.
+				pub type ChannelId = (ParaId, ParaId);
+
+			//* Constants *//
+
+				/// Call indices of the relay chain `Hrmp` pallet.
+				const HRMP_INIT_OPEN_CHANNEL: u8 = 0;
+				const HRMP_ACCEPT_OPEN_CHANNEL: u8 = 1;
+				const HRMP_CLOSE_CHANNEL: u8 = 2;
+
+			//* Enums *//
+
+				#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+				pub enum ChannelStatus {
+					/// This chain asked the relay chain to open the channel.
+					Requested,
+					/// This chain accepted a channel requested by a sibling.
+					Accepted,
+					/// The relay chain reports the channel as open.
+					Open,
+					/// This chain asked the relay chain to close the channel.
+					Closing,
+				}
+
+			//* Traits *//
+
+				/// Provides the channels the relay chain currently considers open for this chain.
+				pub trait HrmpChannelSource {
+					/// The senders of the channels through which this chain receives messages.
+					fn ingress() -> Vec<ParaId>;
+
+					/// The recipients of the channels through which this chain sends messages.
+					fn egress() -> Vec<ParaId>;
+				}
+
+				impl HrmpChannelSource for () {
+					fn ingress() -> Vec<ParaId> {
+						Vec::new()
+					}
+
+					fn egress() -> Vec<ParaId> {
+						Vec::new()
+					}
+				}
+
+
+
+		//** Storage **//
+
+			#[pallet::storage]
+			#[pallet::getter(fn channels)]
+			pub type Channels<T> = CountedStorageMap<_, Twox64Concat, ChannelId, ChannelStatus>;
+
+
+
+		//** Events **//
+
+			#[pallet::event]
+			#[pallet::generate_deposit(pub(super) fn deposit_event)]
+			pub enum Event<T: Config> {
+				OpenChannelRequested { recipient: ParaId, max_capacity: u32, max_message_size: u32 },
+				OpenChannelAccepted { sender: ParaId },
+				CloseChannelRequested { sender: ParaId, recipient: ParaId },
+				ChannelOpened { sender: ParaId, recipient: ParaId },
+				ChannelClosed { sender: ParaId, recipient: ParaId },
+				ChannelCleared { sender: ParaId, recipient: ParaId, status: ChannelStatus },
+			}
+
+
+
+		//** Errors **//
+
+			#[pallet::error]
+			pub enum Error<T> {
+				ChannelToSelf,
+				ChannelAlreadyTracked,
+				ChannelNotInvolvingSelf,
+				ChannelNotTracked,
+				ChannelNotOpen,
+				XcmSendFailed,
+			}
+
+
+		//** Hooks **//
+		#[pallet::hooks]
+		impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+
+			fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+				// reserve the weight used by `on_finalize` to sync the channels
+				let tracked = Channels::<T>::count() as u64;
+				T::DbWeight::get().reads_writes(tracked.saturating_add(1), tracked.saturating_add(1))
+			}
+
+			fn on_finalize(_n: BlockNumberFor<T>) {
+				Self::sync_channels();
+			}
+		}
+
+
+
+		//** Extrinsics **//
+
+			#[pallet::call]
+			impl<T: Config> Pallet<T> {
+
+				#[pallet::call_index(0)]
+				#[pallet::weight(T::WeightInfo::request_open_channel())]
+				pub fn request_open_channel(
+					origin: OriginFor<T>,
+					recipient: ParaId,
+					max_capacity: u32,
+					max_message_size: u32,
+				) -> DispatchResultWithPostInfo {
+					T::ChannelManagerOrigin::ensure_origin(origin)?;
+
+					let sender = T::SelfParaId::get();
+					ensure!(recipient != sender, Error::<T>::ChannelToSelf);
+					ensure!(!Channels::<T>::contains_key((sender, recipient)), Error::<T>::ChannelAlreadyTracked);
+
+					Self::send_relay_call(
+						HRMP_INIT_OPEN_CHANNEL,
+						(recipient, max_capacity, max_message_size).encode(),
+					)?;
+					Channels::<T>::insert((sender, recipient), ChannelStatus::Requested);
+
+					Self::deposit_event(Event::OpenChannelRequested { recipient, max_capacity, max_message_size });
+					Ok(().into())
+				}
+
+
+				#[pallet::call_index(1)]
+				#[pallet::weight(T::WeightInfo::accept_open_channel())]
+				pub fn accept_open_channel(origin: OriginFor<T>, sender: ParaId) -> DispatchResultWithPostInfo {
+					T::ChannelManagerOrigin::ensure_origin(origin)?;
+
+					let recipient = T::SelfParaId::get();
+					ensure!(sender != recipient, Error::<T>::ChannelToSelf);
+					ensure!(!Channels::<T>::contains_key((sender, recipient)), Error::<T>::ChannelAlreadyTracked);
+
+					Self::send_relay_call(HRMP_ACCEPT_OPEN_CHANNEL, sender.encode())?;
+					Channels::<T>::insert((sender, recipient), ChannelStatus::Accepted);
+
+					Self::deposit_event(Event::OpenChannelAccepted { sender });
+					Ok(().into())
+				}
+
+
+				#[pallet::call_index(2)]
+				#[pallet::weight(T::WeightInfo::close_channel())]
+				pub fn close_channel(
+					origin: OriginFor<T>,
+					sender: ParaId,
+					recipient: ParaId,
+				) -> DispatchResultWithPostInfo {
+					T::ChannelManagerOrigin::ensure_origin(origin)?;
+
+					let self_id = T::SelfParaId::get();
+					ensure!(sender != recipient, Error::<T>::ChannelToSelf);
+					ensure!(sender == self_id || recipient == self_id, Error::<T>::ChannelNotInvolvingSelf);
+					let status = Channels::<T>::get((sender, recipient)).ok_or(Error::<T>::ChannelNotTracked)?;
+					ensure!(status == ChannelStatus::Open, Error::<T>::ChannelNotOpen);
+
+					// the relay chain expects a `HrmpChannelId`, which encodes as the pair
+					Self::send_relay_call(HRMP_CLOSE_CHANNEL, (sender, recipient).encode())?;
+					Channels::<T>::insert((sender, recipient), ChannelStatus::Closing);
+
+					Self::deposit_event(Event::CloseChannelRequested { sender, recipient });
+					Ok(().into())
+				}
+
+
+				/// Stop tracking a channel, typically one whose `Transact` failed on the relay chain
+				/// so that it can be requested again. A channel the relay chain still reports as
+				/// open is tracked as `Open` again at the end of the block.
+				#[pallet::call_index(3)]
+				#[pallet::weight(T::WeightInfo::force_clear())]
+				pub fn force_clear(
+					origin: OriginFor<T>,
+					sender: ParaId,
+					recipient: ParaId,
+				) -> DispatchResultWithPostInfo {
+					T::ChannelManagerOrigin::ensure_origin(origin)?;
+
+					let status = Channels::<T>::take((sender, recipient)).ok_or(Error::<T>::ChannelNotTracked)?;
+
+					Self::deposit_event(Event::ChannelCleared { sender, recipient, status });
+					Ok(().into())
+				}
+
+
+			}
+
+
+
+		//** Helpers **//
+
+			impl<T: Config> Pallet<T> {
+
+				/// Wrap an encoded call to the relay chain `Hrmp` pallet in a `Transact`, paid
+				/// for by this chain's sovereign account, and send it upwards.
+				fn send_relay_call(call_index: u8, encoded_args: Vec<u8>) -> DispatchResult {
+					let mut call = vec![T::RelayHrmpPalletIndex::get(), call_index];
+					call.extend(encoded_args);
+
+					let fee: MultiAsset = (MultiLocation::here(), T::RelayFee::get()).into();
+					let sovereign = MultiLocation {
+						parents: 0,
+						interior: X1(Parachain(T::SelfParaId::get().into())),
+					};
+
+					let message = Xcm(vec![
+						WithdrawAsset(fee.clone().into()),
+						BuyExecution { fees: fee, weight_limit: Unlimited },
+						Transact {
+							origin_kind: OriginKind::Native,
+							require_weight_at_most: T::RelayTransactWeight::get(),
+							call: call.into(),
+						},
+						RefundSurplus,
+						DepositAsset { assets: Wild(AllCounted(1)), beneficiary: sovereign },
+					]);
+
+					send_xcm::<T::XcmSender>(MultiLocation::parent(), message)
+						.map_err(|_| Error::<T>::XcmSendFailed)?;
+
+					Ok(())
+				}
+
+
+				/// Reconcile the tracked channels with the ones the relay chain reports as open.
+				pub(crate) fn sync_channels() {
+					let self_id = T::SelfParaId::get();
+					let open: Vec<ChannelId> = T::ChannelSource::ingress()
+						.into_iter()
+						.map(|sender| (sender, self_id))
+						.chain(T::ChannelSource::egress().into_iter().map(|recipient| (self_id, recipient)))
+						.collect();
+
+					let tracked: Vec<(ChannelId, ChannelStatus)> = Channels::<T>::iter().collect();
+					for ((sender, recipient), status) in tracked {
+						let is_open = open.contains(&(sender, recipient));
+
+						match (status, is_open) {
+							(ChannelStatus::Requested | ChannelStatus::Accepted, true) => {
+								Channels::<T>::insert((sender, recipient), ChannelStatus::Open);
+								Self::deposit_event(Event::ChannelOpened { sender, recipient });
+							},
+							(ChannelStatus::Open | ChannelStatus::Closing, false) => {
+								Channels::<T>::remove((sender, recipient));
+								Self::deposit_event(Event::ChannelClosed { sender, recipient });
+							},
+							_ => {},
+						}
+					}
+
+					// channels opened before this pallet was deployed, or by the relay chain itself
+					for (sender, recipient) in open {
+						if !Channels::<T>::contains_key((sender, recipient)) {
+							Channels::<T>::insert((sender, recipient), ChannelStatus::Open);
+							Self::deposit_event(Event::ChannelOpened { sender, recipient });
+						}
+					}
+				}
+			}
+	}
+
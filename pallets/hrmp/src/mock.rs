@@ -0,0 +1,128 @@
+use crate::HrmpChannelSource;
+use cumulus_primitives_core::ParaId;
+use frame_support::{derive_impl, parameter_types, traits::Everything, weights::Weight};
+use frame_system as system;
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage,
+};
+use std::cell::RefCell;
+use xcm::latest::prelude::*;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		HrmpManager: crate::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+thread_local! {
+	pub static SENT_XCM: RefCell<Vec<(MultiLocation, Xcm<()>)>> = RefCell::new(Vec::new());
+	pub static INGRESS: RefCell<Vec<ParaId>> = RefCell::new(Vec::new());
+	pub static EGRESS: RefCell<Vec<ParaId>> = RefCell::new(Vec::new());
+}
+
+/// Records every message instead of delivering it.
+pub struct TestSendXcm;
+impl SendXcm for TestSendXcm {
+	type Ticket = (MultiLocation, Xcm<()>);
+
+	fn validate(
+		dest: &mut Option<MultiLocation>,
+		msg: &mut Option<Xcm<()>>,
+	) -> SendResult<Self::Ticket> {
+		let pair = (dest.take().unwrap(), msg.take().unwrap());
+		Ok((pair, MultiAssets::new()))
+	}
+
+	fn deliver(pair: Self::Ticket) -> Result<XcmHash, SendError> {
+		SENT_XCM.with(|q| q.borrow_mut().push(pair));
+		Ok([0u8; 32])
+	}
+}
+
+pub fn sent_xcm() -> Vec<(MultiLocation, Xcm<()>)> {
+	SENT_XCM.with(|q| q.borrow().clone())
+}
+
+/// Channels reported as open by the (mocked) relay chain.
+pub struct TestChannelSource;
+impl HrmpChannelSource for TestChannelSource {
+	fn ingress() -> Vec<ParaId> {
+		INGRESS.with(|c| c.borrow().clone())
+	}
+
+	fn egress() -> Vec<ParaId> {
+		EGRESS.with(|c| c.borrow().clone())
+	}
+}
+
+pub fn set_open_channels(ingress: Vec<u32>, egress: Vec<u32>) {
+	INGRESS.with(|c| *c.borrow_mut() = ingress.into_iter().map(Into::into).collect());
+	EGRESS.with(|c| *c.borrow_mut() = egress.into_iter().map(Into::into).collect());
+}
+
+parameter_types! {
+	pub SelfParaId: ParaId = ParaId::from(2000);
+	pub const RelayHrmpPalletIndex: u8 = 60;
+	pub const RelayFee: u128 = 1_000_000_000_000;
+	pub RelayTransactWeight: Weight = Weight::from_parts(1_000_000_000, 65_536);
+}
+
+impl crate::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ChannelManagerOrigin = EnsureRoot<u64>;
+	type SelfParaId = SelfParaId;
+	type XcmSender = TestSendXcm;
+	type ChannelSource = TestChannelSource;
+	type RelayHrmpPalletIndex = RelayHrmpPalletIndex;
+	type RelayFee = RelayFee;
+	type RelayTransactWeight = RelayTransactWeight;
+	type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities =
+		system::GenesisConfig::<Test>::default().build_storage().unwrap().into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,142 @@
+use crate::{mock::*, ChannelStatus, Channels, Error, Event};
+use cumulus_primitives_core::ParaId;
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use sp_runtime::DispatchError;
+use xcm::latest::prelude::*;
+
+fn para(id: u32) -> ParaId {
+	id.into()
+}
+
+fn transact_call(message: &Xcm<()>) -> Vec<u8> {
+	message
+		.0
+		.iter()
+		.find_map(|instruction| match instruction {
+			Transact { call, .. } => Some(call.clone().into_encoded()),
+			_ => None,
+		})
+		.expect("every relay message carries a transact; qed")
+}
+
+#[test]
+fn request_open_channel_sends_transact_to_relay() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(HrmpManager::request_open_channel(RuntimeOrigin::root(), 2001.into(), 8, 1024));
+
+		let sent = sent_xcm();
+		assert_eq!(sent.len(), 1);
+		assert_eq!(sent[0].0, MultiLocation::parent());
+
+		let mut expected = vec![60u8, 0];
+		expected.extend(codec::Encode::encode(&(2001u32, 8u32, 1024u32)));
+		assert_eq!(transact_call(&sent[0].1), expected);
+
+		assert_eq!(HrmpManager::channels((para(2000), para(2001))), Some(ChannelStatus::Requested));
+		System::assert_last_event(
+			Event::OpenChannelRequested { recipient: 2001.into(), max_capacity: 8, max_message_size: 1024 }
+				.into(),
+		);
+	});
+}
+
+#[test]
+fn only_governance_manages_channels() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			HrmpManager::request_open_channel(RuntimeOrigin::signed(1), 2001.into(), 8, 1024),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			HrmpManager::accept_open_channel(RuntimeOrigin::signed(1), 2001.into()),
+			DispatchError::BadOrigin
+		);
+		assert!(sent_xcm().is_empty());
+	});
+}
+
+#[test]
+fn correct_error_for_invalid_channels() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			HrmpManager::request_open_channel(RuntimeOrigin::root(), 2000.into(), 8, 1024),
+			Error::<Test>::ChannelToSelf
+		);
+		assert_noop!(
+			HrmpManager::close_channel(RuntimeOrigin::root(), 2001.into(), 2002.into()),
+			Error::<Test>::ChannelNotInvolvingSelf
+		);
+		assert_noop!(
+			HrmpManager::close_channel(RuntimeOrigin::root(), 2000.into(), 2001.into()),
+			Error::<Test>::ChannelNotTracked
+		);
+		assert_noop!(
+			HrmpManager::force_clear(RuntimeOrigin::root(), 2000.into(), 2001.into()),
+			Error::<Test>::ChannelNotTracked
+		);
+
+		assert_ok!(HrmpManager::accept_open_channel(RuntimeOrigin::root(), 2001.into()));
+		assert_noop!(
+			HrmpManager::accept_open_channel(RuntimeOrigin::root(), 2001.into()),
+			Error::<Test>::ChannelAlreadyTracked
+		);
+	});
+}
+
+#[test]
+fn channel_status_follows_relay_messaging_state() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(HrmpManager::request_open_channel(RuntimeOrigin::root(), 2001.into(), 8, 1024));
+		assert_ok!(HrmpManager::accept_open_channel(RuntimeOrigin::root(), 2002.into()));
+
+		// the relay chain has not opened anything yet
+		HrmpManager::on_finalize(1);
+		assert_eq!(HrmpManager::channels((para(2000), para(2001))), Some(ChannelStatus::Requested));
+
+		set_open_channels(vec![2002, 2003], vec![2001]);
+		HrmpManager::on_finalize(2);
+		assert_eq!(HrmpManager::channels((para(2000), para(2001))), Some(ChannelStatus::Open));
+		assert_eq!(HrmpManager::channels((para(2002), para(2000))), Some(ChannelStatus::Open));
+		// untracked channels are picked up as well
+		assert_eq!(HrmpManager::channels((para(2003), para(2000))), Some(ChannelStatus::Open));
+
+		assert_ok!(HrmpManager::close_channel(RuntimeOrigin::root(), 2000.into(), 2001.into()));
+		assert_eq!(HrmpManager::channels((para(2000), para(2001))), Some(ChannelStatus::Closing));
+
+		set_open_channels(vec![2002, 2003], vec![]);
+		HrmpManager::on_finalize(3);
+		assert!(!Channels::<Test>::contains_key((para(2000), para(2001))));
+		System::assert_has_event(Event::ChannelClosed { sender: 2000.into(), recipient: 2001.into() }.into());
+	});
+}
+
+#[test]
+fn failed_requests_are_cleared_and_retried() {
+	new_test_ext().execute_with(|| {
+		// the relay chain rejected the `Transact`, the channel never opens
+		assert_ok!(HrmpManager::request_open_channel(RuntimeOrigin::root(), 2001.into(), 8, 1024));
+		HrmpManager::on_finalize(1);
+		assert_noop!(
+			HrmpManager::close_channel(RuntimeOrigin::root(), 2000.into(), 2001.into()),
+			Error::<Test>::ChannelNotOpen
+		);
+		assert_noop!(
+			HrmpManager::request_open_channel(RuntimeOrigin::root(), 2001.into(), 8, 1024),
+			Error::<Test>::ChannelAlreadyTracked
+		);
+
+		assert_noop!(
+			HrmpManager::force_clear(RuntimeOrigin::signed(1), 2000.into(), 2001.into()),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(HrmpManager::force_clear(RuntimeOrigin::root(), 2000.into(), 2001.into()));
+		System::assert_last_event(
+			Event::ChannelCleared { sender: 2000.into(), recipient: 2001.into(), status: ChannelStatus::Requested }
+				.into(),
+		);
+		assert_eq!(Channels::<Test>::count(), 0);
+
+		assert_ok!(HrmpManager::request_open_channel(RuntimeOrigin::root(), 2001.into(), 8, 1024));
+		assert_eq!(sent_xcm().len(), 2);
+	});
+}
@@ -0,0 +1,82 @@
+//! Weights for `kine_hrmp`
+//!
+//! Conservative estimates until the pallet is first benchmarked on the reference hardware.
+//! Regenerate with `./scripts/benchmark.sh kine_hrmp`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `kine_hrmp`.
+pub trait WeightInfo {
+	fn request_open_channel() -> Weight;
+	fn accept_open_channel() -> Weight;
+	fn close_channel() -> Weight;
+	fn force_clear() -> Weight;
+}
+
+/// Weights for `kine_hrmp` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `HrmpManager::Channels` (r:1 w:1)
+	/// Storage: `HrmpManager::CounterForChannels` (r:1 w:1)
+	/// Storage: `ParachainSystem::HostConfiguration` (r:1 w:0)
+	/// Storage: `ParachainSystem::PendingUpwardMessages` (r:1 w:1)
+	fn request_open_channel() -> Weight {
+		Weight::from_parts(50_000_000, 3_500)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `HrmpManager::Channels` (r:1 w:1)
+	/// Storage: `HrmpManager::CounterForChannels` (r:1 w:1)
+	/// Storage: `ParachainSystem::HostConfiguration` (r:1 w:0)
+	/// Storage: `ParachainSystem::PendingUpwardMessages` (r:1 w:1)
+	fn accept_open_channel() -> Weight {
+		Weight::from_parts(50_000_000, 3_500)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `HrmpManager::Channels` (r:1 w:1)
+	/// Storage: `ParachainSystem::HostConfiguration` (r:1 w:0)
+	/// Storage: `ParachainSystem::PendingUpwardMessages` (r:1 w:1)
+	fn close_channel() -> Weight {
+		Weight::from_parts(50_000_000, 3_500)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `HrmpManager::Channels` (r:1 w:1)
+	/// Storage: `HrmpManager::CounterForChannels` (r:1 w:1)
+	fn force_clear() -> Weight {
+		Weight::from_parts(15_000_000, 3_500)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	fn request_open_channel() -> Weight {
+		Weight::from_parts(50_000_000, 3_500)
+			.saturating_add(RocksDbWeight::get().reads(4))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	fn accept_open_channel() -> Weight {
+		Weight::from_parts(50_000_000, 3_500)
+			.saturating_add(RocksDbWeight::get().reads(4))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	fn close_channel() -> Weight {
+		Weight::from_parts(50_000_000, 3_500)
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	fn force_clear() -> Weight {
+		Weight::from_parts(15_000_000, 3_500)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+}
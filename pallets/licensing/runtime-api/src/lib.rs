@@ -0,0 +1,29 @@
+//! Runtime API to query the licensing agreements of a film or of an account.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use kine_licensing::AgreementId;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait LicensingApi<AccountId, FilmId, Agreement>
+	where
+		AccountId: Codec,
+		FilmId: Codec,
+		Agreement: Codec,
+	{
+		/// Up to `limit` of the open agreements licensing `film`, at most
+		/// `kine_licensing::MAX_AGREEMENTS_PER_PAGE`. The next page starts `after` the last
+		/// agreement of the previous one.
+		fn agreements_by_film(film: FilmId, after: Option<AgreementId>, limit: u32) -> Vec<(AgreementId, Agreement)>;
+
+		/// Up to `limit` of the open agreements `account` is the buyer or the seller of,
+		/// paginated like `agreements_by_film`.
+		fn agreements_by_account(
+			account: AccountId,
+			after: Option<AgreementId>,
+			limit: u32,
+		) -> Vec<(AgreementId, Agreement)>;
+	}
+}
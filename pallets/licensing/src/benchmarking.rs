@@ -0,0 +1,129 @@
+//! Benchmarking setup for kine-licensing
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Licensing;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::{
+	traits::{fungible::Mutate, EnsureOrigin, Get},
+	BoundedVec,
+};
+use frame_system::RawOrigin;
+use sp_runtime::{
+	traits::{Bounded, Saturating},
+	Perbill,
+};
+use sp_std::vec;
+
+fn funded_buyer<T: Config>() -> T::AccountId {
+	let buyer: T::AccountId = whitelisted_caller();
+	T::Currency::set_balance(&buyer, BalanceOf::<T>::max_value() / 4u32.into());
+	buyer
+}
+
+fn seller<T: Config>() -> T::AccountId {
+	let seller: T::AccountId = account("seller", 0, 0);
+	T::Currency::set_balance(&seller, T::Currency::minimum_balance());
+	seller
+}
+
+fn territory<T: Config>() -> TerritoryOf<T> {
+	BoundedVec::truncate_from(vec![b'A'; T::MaxTerritoryLength::get() as usize])
+}
+
+fn price<T: Config>() -> BalanceOf<T> {
+	T::Currency::minimum_balance().saturating_mul(100u32.into())
+}
+
+/// An offer of the whitelisted buyer, accepted by the seller.
+fn accepted<T: Config>(film: T::FilmId) -> Result<(T::AccountId, T::AccountId), frame_benchmarking::BenchmarkError> {
+	let buyer = funded_buyer::<T>();
+	let seller = seller::<T>();
+	Licensing::<T>::offer(
+		RawOrigin::Signed(buyer.clone()).into(),
+		seller.clone(),
+		film,
+		territory::<T>(),
+		LicenseWindow { start: 0, end: 1 },
+		price::<T>(),
+	)?;
+	Licensing::<T>::accept(RawOrigin::Signed(seller.clone()).into(), 0)?;
+	Ok((buyer, seller))
+}
+
+benchmarks! {
+	where_clause { where T::FilmId: Default }
+
+	offer {
+		let buyer = funded_buyer::<T>();
+		let seller = seller::<T>();
+	}: _(
+		RawOrigin::Signed(buyer),
+		seller,
+		T::FilmId::default(),
+		territory::<T>(),
+		LicenseWindow { start: 0, end: 1 },
+		price::<T>()
+	)
+	verify {
+		assert!(Agreements::<T>::get(0).is_some());
+	}
+
+	accept {
+		let buyer = funded_buyer::<T>();
+		let seller = seller::<T>();
+		Licensing::<T>::offer(
+			RawOrigin::Signed(buyer).into(),
+			seller.clone(),
+			T::FilmId::default(),
+			territory::<T>(),
+			LicenseWindow { start: 0, end: 1 },
+			price::<T>(),
+		)?;
+	}: _(RawOrigin::Signed(seller), 0)
+	verify {
+		assert_eq!(Agreements::<T>::get(0).map(|details| details.state), Some(AgreementState::Accepted));
+	}
+
+	cancel {
+		let buyer = funded_buyer::<T>();
+		let seller = seller::<T>();
+		Licensing::<T>::offer(
+			RawOrigin::Signed(buyer.clone()).into(),
+			seller,
+			T::FilmId::default(),
+			territory::<T>(),
+			LicenseWindow { start: 0, end: 1 },
+			price::<T>(),
+		)?;
+	}: _(RawOrigin::Signed(buyer), 0)
+	verify {
+		assert!(Agreements::<T>::get(0).is_none());
+	}
+
+	dispute {
+		let (buyer, _) = accepted::<T>(T::FilmId::default())?;
+	}: _(RawOrigin::Signed(buyer), 0)
+
+	release {
+		let (_, seller) = accepted::<T>(T::FilmId::default())?;
+		let now = frame_system::Pallet::<T>::block_number();
+		frame_system::Pallet::<T>::set_block_number(now.saturating_add(T::DisputePeriod::get()));
+	}: _(RawOrigin::Signed(seller), 0)
+	verify {
+		assert!(Agreements::<T>::get(0).is_none());
+	}
+
+	arbitrate {
+		let (buyer, _) = accepted::<T>(T::FilmId::default())?;
+		Licensing::<T>::dispute(RawOrigin::Signed(buyer).into(), 0)?;
+		let origin = T::ArbitrationOrigin::try_successful_origin()
+			.map_err(|_| frame_benchmarking::BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(origin, 0, Perbill::from_percent(50))
+	verify {
+		assert!(Agreements::<T>::get(0).is_none());
+	}
+}
+
+impl_benchmark_test_suite!(Licensing, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,499 @@
+//** About **//
+	// Escrow for the distribution deals of films. A distributor offers to license a film
+	// from its rights holder for a territory and a time window at a price, and the price is
+	// held from the distributor's account. Once the rights holder accepts the offer a dispute
+	// period starts; if neither party disputes the deal in that time the held funds can be
+	// released to the rights holder. Disputed deals are settled by the arbitration origin,
+	// usually a governance body, which decides how the held funds are split between the two
+	// parties. Each open offer also holds a deposit from the distributor, returned once the
+	// offer is accepted or cancelled, and a distributor has at most `MaxOpenOffers` open at a
+	// time. Agreements are pruned once settled, their outcome is left in the events. Open
+	// agreements are indexed by film and by account for the paginated `LicensingApi` runtime
+	// API of `kine-licensing-runtime-api`.
+
+
+
+	#![cfg_attr(not(feature = "std"), no_std)]
+
+	pub use pallet::*;
+
+	#[cfg(test)]
+	mod mock;
+
+	#[cfg(test)]
+	mod tests;
+
+	#[cfg(feature = "runtime-benchmarks")]
+	mod benchmarking;
+
+
+
+	#[frame_support::pallet]
+	pub mod pallet {
+
+		//** Config **//
+
+			//* Imports *//
+
+				use frame_support::{
+					dispatch::DispatchResultWithPostInfo,
+					pallet_prelude::*,
+					traits::{
+						fungible::{Inspect, InspectHold, Mutate, MutateHold},
+						tokens::{Fortitude, Precision, Restriction},
+					},
+				};
+				use frame_system::pallet_prelude::*;
+				use sp_runtime::{
+					traits::{Saturating, Zero},
+					Perbill,
+				};
+				use sp_std::vec::Vec;
+
+			//* Config *//
+
+				#[pallet::pallet]
+				pub struct Pallet<T>(_);
+
+				#[pallet::config]
+				pub trait Config: frame_system::Config {
+					type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+					/// The currency prices are paid and held in.
+					type Currency: Inspect<Self::AccountId>
+						+ Mutate<Self::AccountId>
+						+ InspectHold<Self::AccountId>
+						+ MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+					/// The overarching hold reason.
+					type RuntimeHoldReason: From<HoldReason>;
+
+					/// The id of a film in the film registry.
+					type FilmId: Parameter + Member + MaxEncodedLen + Copy;
+
+					/// Settles disputed agreements.
+					type ArbitrationOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+					/// The number of blocks an accepted agreement can be disputed for.
+					#[pallet::constant]
+					type DisputePeriod: Get<BlockNumberFor<Self>>;
+
+					/// Held from the buyer for each open offer.
+					#[pallet::constant]
+					type OfferDeposit: Get<BalanceOf<Self>>;
+
+					/// The maximum number of offers an account has waiting for their sellers.
+					#[pallet::constant]
+					type MaxOpenOffers: Get<u32>;
+
+					/// The maximum length of the territory of a license.
+					#[pallet::constant]
+					type MaxTerritoryLength: Get<u32>;
+				}
+
+
+
+		//** Types **//
+
+			//* Types *//
+
+				pub type AgreementId = u32;
+				pub type BalanceOf<T> =
+					<<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+				pub type TerritoryOf<T> = BoundedVec<u8, <T as Config>::MaxTerritoryLength>;
+				pub type AgreementOf<T> = Agreement<
+					<T as frame_system::Config>::AccountId,
+					<T as Config>::FilmId,
+					BalanceOf<T>,
+					BlockNumberFor<T>,
+					TerritoryOf<T>,
+				>;
+
+			//* Constants *//
+
+				/// The most agreements the runtime API returns at once.
+				pub const MAX_AGREEMENTS_PER_PAGE: u32 = 100;
+
+			//* Enums *//
+
+				#[pallet::composite_enum]
+				pub enum HoldReason {
+					/// The price of a license held until the agreement is settled.
+					Escrow,
+					/// The deposit of an open offer, returned once it's accepted or cancelled.
+					OfferDeposit,
+				}
+
+				#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+				pub enum AgreementState {
+					/// Waiting for the seller to accept.
+					Offered,
+					/// Accepted, the funds are released once the dispute period is over.
+					Accepted,
+					/// Waiting for the arbitration origin.
+					Disputed,
+				}
+
+			//* Structs *//
+
+				/// The period a license is valid for, in unix milliseconds.
+				#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+				pub struct LicenseWindow {
+					pub start: u64,
+					pub end: u64,
+				}
+
+				#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+				pub struct Agreement<AccountId, FilmId, Balance, BlockNumber, Territory> {
+					/// The distributor paying for the license.
+					pub buyer: AccountId,
+					/// The rights holder granting the license.
+					pub seller: AccountId,
+					pub film: FilmId,
+					/// Where the license is valid, such as a list of country codes.
+					pub territory: Territory,
+					pub window: LicenseWindow,
+					pub price: Balance,
+					pub state: AgreementState,
+					/// The end of the dispute period, once accepted.
+					pub release_at: Option<BlockNumber>,
+				}
+
+
+
+		//** Storage **//
+
+			#[pallet::storage]
+			#[pallet::getter(fn next_agreement_id)]
+			pub type NextAgreementId<T> = StorageValue<_, AgreementId, ValueQuery>;
+
+			#[pallet::storage]
+			#[pallet::getter(fn agreement)]
+			pub type Agreements<T: Config> = StorageMap<_, Twox64Concat, AgreementId, AgreementOf<T>>;
+
+			#[pallet::storage]
+			pub type AgreementsByFilm<T: Config> = StorageDoubleMap<
+				_,
+				Blake2_128Concat, T::FilmId,
+				Twox64Concat, AgreementId,
+				(),
+			>;
+
+			/// The agreements of each buyer and seller.
+			#[pallet::storage]
+			pub type AgreementsByAccount<T: Config> = StorageDoubleMap<
+				_,
+				Blake2_128Concat, T::AccountId,
+				Twox64Concat, AgreementId,
+				(),
+			>;
+
+			/// The number of offers of each buyer waiting for their sellers.
+			#[pallet::storage]
+			#[pallet::getter(fn open_offers)]
+			pub type OpenOffers<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+
+
+		//** Events **//
+
+			#[pallet::event]
+			#[pallet::generate_deposit(pub(super) fn deposit_event)]
+			pub enum Event<T: Config> {
+				Offered {
+					agreement: AgreementId,
+					film: T::FilmId,
+					buyer: T::AccountId,
+					seller: T::AccountId,
+					price: BalanceOf<T>,
+				},
+				Accepted { agreement: AgreementId, release_at: BlockNumberFor<T> },
+				Cancelled { agreement: AgreementId },
+				Disputed { agreement: AgreementId, by: T::AccountId },
+				Released { agreement: AgreementId, amount: BalanceOf<T> },
+				Resolved { agreement: AgreementId, to_buyer: BalanceOf<T>, to_seller: BalanceOf<T> },
+			}
+
+
+
+		//** Errors **//
+
+			#[pallet::error]
+			pub enum Error<T> {
+				UnknownAgreement,
+				InvalidWindow,
+				ZeroPrice,
+				SelfDealing,
+				NotBuyer,
+				NotSeller,
+				NotParty,
+				NotOffered,
+				NotAccepted,
+				NotDisputed,
+				DisputePeriodOver,
+				DisputePeriodNotOver,
+				TooManyOpenOffers,
+			}
+
+
+		//** Hooks **//
+		#[pallet::hooks]
+		impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+
+		}
+
+
+
+		//** Extrinsics **//
+
+			#[pallet::call]
+			impl<T: Config> Pallet<T> {
+
+				/// Offer to license `film` from `seller`, holding the price until the agreement
+				/// is settled and the `OfferDeposit` until the offer is accepted or cancelled.
+				#[pallet::call_index(0)]
+				#[pallet::weight(Weight::from_parts(70_000_000, 0) + T::DbWeight::get().reads_writes(4, 7))]
+				pub fn offer(
+					origin: OriginFor<T>,
+					seller: T::AccountId,
+					film: T::FilmId,
+					territory: TerritoryOf<T>,
+					window: LicenseWindow,
+					price: BalanceOf<T>,
+				) -> DispatchResultWithPostInfo {
+					let buyer = ensure_signed(origin)?;
+
+					ensure!(buyer != seller, Error::<T>::SelfDealing);
+					ensure!(window.start < window.end, Error::<T>::InvalidWindow);
+					ensure!(!price.is_zero(), Error::<T>::ZeroPrice);
+					let open_offers = OpenOffers::<T>::get(&buyer);
+					ensure!(open_offers < T::MaxOpenOffers::get(), Error::<T>::TooManyOpenOffers);
+
+					T::Currency::hold(&HoldReason::OfferDeposit.into(), &buyer, T::OfferDeposit::get())?;
+					T::Currency::hold(&HoldReason::Escrow.into(), &buyer, price)?;
+					OpenOffers::<T>::insert(&buyer, open_offers + 1);
+
+					let agreement = NextAgreementId::<T>::get();
+					NextAgreementId::<T>::put(agreement.saturating_add(1));
+
+					AgreementsByFilm::<T>::insert(film, agreement, ());
+					AgreementsByAccount::<T>::insert(&buyer, agreement, ());
+					AgreementsByAccount::<T>::insert(&seller, agreement, ());
+					Agreements::<T>::insert(agreement, Agreement {
+						buyer: buyer.clone(),
+						seller: seller.clone(),
+						film,
+						territory,
+						window,
+						price,
+						state: AgreementState::Offered,
+						release_at: None,
+					});
+
+					Self::deposit_event(Event::Offered { agreement, film, buyer, seller, price });
+					Ok(().into())
+				}
+
+
+				#[pallet::call_index(1)]
+				#[pallet::weight(Weight::from_parts(40_000_000, 0) + T::DbWeight::get().reads_writes(3, 3))]
+				pub fn accept(origin: OriginFor<T>, agreement: AgreementId) -> DispatchResultWithPostInfo {
+					let who = ensure_signed(origin)?;
+
+					let mut details = Agreements::<T>::get(agreement).ok_or(Error::<T>::UnknownAgreement)?;
+					ensure!(details.seller == who, Error::<T>::NotSeller);
+					ensure!(details.state == AgreementState::Offered, Error::<T>::NotOffered);
+
+					Self::close_offer(&details.buyer)?;
+					let release_at = frame_system::Pallet::<T>::block_number().saturating_add(T::DisputePeriod::get());
+					details.state = AgreementState::Accepted;
+					details.release_at = Some(release_at);
+					Agreements::<T>::insert(agreement, details);
+
+					Self::deposit_event(Event::Accepted { agreement, release_at });
+					Ok(().into())
+				}
+
+
+				/// Withdraw an offer, as its buyer, or reject it, as its seller. The held price
+				/// and deposit go back to the buyer and the offer is pruned.
+				#[pallet::call_index(2)]
+				#[pallet::weight(Weight::from_parts(50_000_000, 0) + T::DbWeight::get().reads_writes(3, 7))]
+				pub fn cancel(origin: OriginFor<T>, agreement: AgreementId) -> DispatchResultWithPostInfo {
+					let who = ensure_signed(origin)?;
+
+					let details = Agreements::<T>::get(agreement).ok_or(Error::<T>::UnknownAgreement)?;
+					ensure!(who == details.buyer || who == details.seller, Error::<T>::NotParty);
+					ensure!(details.state == AgreementState::Offered, Error::<T>::NotOffered);
+
+					Self::close_offer(&details.buyer)?;
+					T::Currency::release(&HoldReason::Escrow.into(), &details.buyer, details.price, Precision::BestEffort)?;
+					Self::prune(agreement, &details);
+
+					Self::deposit_event(Event::Cancelled { agreement });
+					Ok(().into())
+				}
+
+
+				/// Dispute an accepted agreement within its dispute period, as either party.
+				#[pallet::call_index(3)]
+				#[pallet::weight(Weight::from_parts(30_000_000, 0) + T::DbWeight::get().reads_writes(1, 1))]
+				pub fn dispute(origin: OriginFor<T>, agreement: AgreementId) -> DispatchResultWithPostInfo {
+					let who = ensure_signed(origin)?;
+
+					Agreements::<T>::try_mutate(agreement, |maybe_agreement| -> DispatchResult {
+						let details = maybe_agreement.as_mut().ok_or(Error::<T>::UnknownAgreement)?;
+						ensure!(who == details.buyer || who == details.seller, Error::<T>::NotParty);
+						ensure!(details.state == AgreementState::Accepted, Error::<T>::NotAccepted);
+						ensure!(
+							details.release_at.map_or(false, |at| frame_system::Pallet::<T>::block_number() < at),
+							Error::<T>::DisputePeriodOver
+						);
+
+						details.state = AgreementState::Disputed;
+						Ok(())
+					})?;
+
+					Self::deposit_event(Event::Disputed { agreement, by: who });
+					Ok(().into())
+				}
+
+
+				/// Release the price of an undisputed agreement to its seller once the dispute
+				/// period is over, and prune the agreement. Anyone can call it.
+				#[pallet::call_index(4)]
+				#[pallet::weight(Weight::from_parts(50_000_000, 0) + T::DbWeight::get().reads_writes(3, 6))]
+				pub fn release(origin: OriginFor<T>, agreement: AgreementId) -> DispatchResultWithPostInfo {
+					ensure_signed(origin)?;
+
+					let details = Agreements::<T>::get(agreement).ok_or(Error::<T>::UnknownAgreement)?;
+					ensure!(details.state == AgreementState::Accepted, Error::<T>::NotAccepted);
+					ensure!(
+						details.release_at.map_or(false, |at| frame_system::Pallet::<T>::block_number() >= at),
+						Error::<T>::DisputePeriodNotOver
+					);
+
+					let amount = Self::pay_seller(&details, details.price)?;
+					Self::prune(agreement, &details);
+
+					Self::deposit_event(Event::Released { agreement, amount });
+					Ok(().into())
+				}
+
+
+				/// Settle a disputed agreement, refunding `buyer_share` of the price to the
+				/// buyer and paying the rest to the seller, and prune it.
+				#[pallet::call_index(5)]
+				#[pallet::weight(Weight::from_parts(60_000_000, 0) + T::DbWeight::get().reads_writes(3, 7))]
+				pub fn arbitrate(
+					origin: OriginFor<T>,
+					agreement: AgreementId,
+					buyer_share: Perbill,
+				) -> DispatchResultWithPostInfo {
+					T::ArbitrationOrigin::ensure_origin(origin)?;
+
+					let details = Agreements::<T>::get(agreement).ok_or(Error::<T>::UnknownAgreement)?;
+					ensure!(details.state == AgreementState::Disputed, Error::<T>::NotDisputed);
+
+					let to_buyer = buyer_share * details.price;
+					let to_seller = Self::pay_seller(&details, details.price.saturating_sub(to_buyer))?;
+					T::Currency::release(&HoldReason::Escrow.into(), &details.buyer, to_buyer, Precision::BestEffort)?;
+
+					Self::prune(agreement, &details);
+
+					Self::deposit_event(Event::Resolved { agreement, to_buyer, to_seller });
+					Ok(().into())
+				}
+
+
+			}
+
+
+
+		//** Helpers **//
+
+			impl<T: Config> Pallet<T> {
+
+				fn pay_seller(details: &AgreementOf<T>, amount: BalanceOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+					if amount.is_zero() {
+						return Ok(amount)
+					}
+
+					T::Currency::transfer_on_hold(
+						&HoldReason::Escrow.into(),
+						&details.buyer,
+						&details.seller,
+						amount,
+						Precision::Exact,
+						Restriction::Free,
+						Fortitude::Polite,
+					)
+				}
+
+
+				/// Return the deposit of an offer that is no longer open.
+				fn close_offer(buyer: &T::AccountId) -> DispatchResult {
+					OpenOffers::<T>::mutate_exists(buyer, |open_offers| {
+						*open_offers = open_offers.map(|count| count.saturating_sub(1)).filter(|count| *count > 0);
+					});
+					T::Currency::release(&HoldReason::OfferDeposit.into(), buyer, T::OfferDeposit::get(), Precision::BestEffort)?;
+					Ok(())
+				}
+
+
+				/// Remove a settled agreement and its index entries.
+				fn prune(agreement: AgreementId, details: &AgreementOf<T>) {
+					Agreements::<T>::remove(agreement);
+					AgreementsByFilm::<T>::remove(details.film, agreement);
+					AgreementsByAccount::<T>::remove(&details.buyer, agreement);
+					AgreementsByAccount::<T>::remove(&details.seller, agreement);
+				}
+
+
+				/// Up to `limit` of the agreements licensing `film`, for the runtime API. Pages
+				/// follow the storage order, the next one starts `after` the last agreement of
+				/// the previous one.
+				pub fn agreements_by_film(
+					film: T::FilmId,
+					after: Option<AgreementId>,
+					limit: u32,
+				) -> Vec<(AgreementId, AgreementOf<T>)> {
+					let agreements = match after {
+						Some(after) => AgreementsByFilm::<T>::iter_key_prefix_from(
+							film,
+							AgreementsByFilm::<T>::hashed_key_for(film, after),
+						),
+						None => AgreementsByFilm::<T>::iter_key_prefix(film),
+					};
+					Self::page(agreements, limit)
+				}
+
+
+				/// Up to `limit` of the agreements `account` buys or sells, for the runtime API,
+				/// paginated like `agreements_by_film`.
+				pub fn agreements_by_account(
+					account: T::AccountId,
+					after: Option<AgreementId>,
+					limit: u32,
+				) -> Vec<(AgreementId, AgreementOf<T>)> {
+					let agreements = match after {
+						Some(after) => AgreementsByAccount::<T>::iter_key_prefix_from(
+							&account,
+							AgreementsByAccount::<T>::hashed_key_for(&account, after),
+						),
+						None => AgreementsByAccount::<T>::iter_key_prefix(&account),
+					};
+					Self::page(agreements, limit)
+				}
+
+
+				fn page(agreements: impl Iterator<Item = AgreementId>, limit: u32) -> Vec<(AgreementId, AgreementOf<T>)> {
+					agreements
+						.take(limit.min(MAX_AGREEMENTS_PER_PAGE) as usize)
+						.filter_map(|agreement| Agreements::<T>::get(agreement).map(|details| (agreement, details)))
+						.collect()
+				}
+
+
+			}
+	}
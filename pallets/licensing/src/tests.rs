@@ -0,0 +1,163 @@
+use crate::{mock::*, AgreementState, Error, Event, HoldReason, LicenseWindow};
+use frame_support::{assert_noop, assert_ok, traits::fungible::InspectHold};
+use sp_runtime::{DispatchError, Perbill};
+
+fn held(who: u64) -> u64 {
+	Balances::balance_on_hold(&HoldReason::Escrow.into(), &who)
+}
+
+fn deposit(who: u64) -> u64 {
+	Balances::balance_on_hold(&HoldReason::OfferDeposit.into(), &who)
+}
+
+fn window() -> LicenseWindow {
+	LicenseWindow { start: 1_700_000_000_000, end: 1_800_000_000_000 }
+}
+
+/// A 300 offer from the distributor to the studio, accepted at block 1.
+fn accepted_offer() -> u32 {
+	assert_ok!(Licensing::offer(
+		RuntimeOrigin::signed(DISTRIBUTOR),
+		STUDIO,
+		FILM,
+		b"PT,ES".to_vec().try_into().unwrap(),
+		window(),
+		300,
+	));
+	assert_ok!(Licensing::accept(RuntimeOrigin::signed(STUDIO), 0));
+	0
+}
+
+#[test]
+fn offer_holds_the_price() {
+	new_test_ext().execute_with(|| {
+		let offer = |seller, window, price| {
+			Licensing::offer(RuntimeOrigin::signed(DISTRIBUTOR), seller, FILM, Default::default(), window, price)
+		};
+
+		assert_noop!(offer(DISTRIBUTOR, window(), 300), Error::<Test>::SelfDealing);
+		assert_noop!(offer(STUDIO, LicenseWindow { start: 5, end: 5 }, 300), Error::<Test>::InvalidWindow);
+		assert_noop!(offer(STUDIO, window(), 0), Error::<Test>::ZeroPrice);
+
+		assert_ok!(offer(STUDIO, window(), 300));
+		assert_eq!(held(DISTRIBUTOR), 300);
+		assert_eq!(deposit(DISTRIBUTOR), 5);
+		assert_eq!(Licensing::agreement(0).unwrap().state, AgreementState::Offered);
+		System::assert_last_event(
+			Event::Offered { agreement: 0, film: FILM, buyer: DISTRIBUTOR, seller: STUDIO, price: 300 }.into(),
+		);
+
+		assert_ok!(offer(OTHER_STUDIO, window(), 100));
+		assert_eq!(Licensing::open_offers(DISTRIBUTOR), 2);
+		assert_noop!(offer(STUDIO, window(), 100), Error::<Test>::TooManyOpenOffers);
+
+		// accepting an offer frees its slot and its deposit
+		assert_ok!(Licensing::accept(RuntimeOrigin::signed(STUDIO), 0));
+		assert_eq!(deposit(DISTRIBUTOR), 5);
+		assert_eq!(Licensing::open_offers(DISTRIBUTOR), 1);
+		assert_ok!(offer(STUDIO, window(), 100));
+	});
+}
+
+#[test]
+fn offers_can_be_cancelled_by_either_party() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Licensing::offer(RuntimeOrigin::signed(DISTRIBUTOR), STUDIO, FILM, Default::default(), window(), 300));
+
+		assert_noop!(Licensing::cancel(RuntimeOrigin::signed(OTHER_STUDIO), 0), Error::<Test>::NotParty);
+		assert_noop!(Licensing::accept(RuntimeOrigin::signed(OTHER_STUDIO), 0), Error::<Test>::NotSeller);
+
+		assert_ok!(Licensing::cancel(RuntimeOrigin::signed(STUDIO), 0));
+		assert_eq!((held(DISTRIBUTOR), deposit(DISTRIBUTOR)), (0, 0));
+		assert_eq!(Licensing::open_offers(DISTRIBUTOR), 0);
+		assert_eq!(Licensing::agreement(0), None);
+		assert_noop!(Licensing::accept(RuntimeOrigin::signed(STUDIO), 0), Error::<Test>::UnknownAgreement);
+	});
+}
+
+#[test]
+fn release_pays_the_seller_after_the_dispute_period() {
+	new_test_ext().execute_with(|| {
+		let agreement = accepted_offer();
+		System::assert_last_event(Event::Accepted { agreement, release_at: 11 }.into());
+
+		assert_noop!(
+			Licensing::release(RuntimeOrigin::signed(OTHER_STUDIO), agreement),
+			Error::<Test>::DisputePeriodNotOver
+		);
+
+		System::set_block_number(11);
+		assert_noop!(
+			Licensing::dispute(RuntimeOrigin::signed(DISTRIBUTOR), agreement),
+			Error::<Test>::DisputePeriodOver
+		);
+		assert_ok!(Licensing::release(RuntimeOrigin::signed(OTHER_STUDIO), agreement));
+
+		assert_eq!(held(DISTRIBUTOR), 0);
+		assert_eq!(Balances::free_balance(DISTRIBUTOR), 700);
+		assert_eq!(Balances::free_balance(STUDIO), 310);
+		assert_eq!(Licensing::agreement(agreement), None);
+		System::assert_last_event(Event::Released { agreement, amount: 300 }.into());
+	});
+}
+
+#[test]
+fn disputes_are_settled_by_the_arbitration_origin() {
+	new_test_ext().execute_with(|| {
+		let agreement = accepted_offer();
+
+		assert_noop!(
+			Licensing::arbitrate(RuntimeOrigin::root(), agreement, Perbill::from_percent(50)),
+			Error::<Test>::NotDisputed
+		);
+		assert_ok!(Licensing::dispute(RuntimeOrigin::signed(DISTRIBUTOR), agreement));
+
+		System::set_block_number(20);
+		assert_noop!(Licensing::release(RuntimeOrigin::signed(STUDIO), agreement), Error::<Test>::NotAccepted);
+		assert_noop!(
+			Licensing::arbitrate(RuntimeOrigin::signed(STUDIO), agreement, Perbill::zero()),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(Licensing::arbitrate(RuntimeOrigin::root(), agreement, Perbill::from_percent(40)));
+		assert_eq!(held(DISTRIBUTOR), 0);
+		assert_eq!(Licensing::agreement(agreement), None);
+		assert_eq!(Balances::free_balance(DISTRIBUTOR), 700 + 120);
+		assert_eq!(Balances::free_balance(STUDIO), 10 + 180);
+		System::assert_last_event(Event::Resolved { agreement, to_buyer: 120, to_seller: 180 }.into());
+	});
+}
+
+#[test]
+fn agreements_are_indexed_by_film_and_account() {
+	new_test_ext().execute_with(|| {
+		accepted_offer();
+		assert_ok!(Licensing::offer(RuntimeOrigin::signed(DISTRIBUTOR), OTHER_STUDIO, 7, Default::default(), window(), 100));
+
+		let ids = |agreements: Vec<(u32, crate::AgreementOf<Test>)>| {
+			let mut ids: Vec<u32> = agreements.into_iter().map(|(id, _)| id).collect();
+			ids.sort();
+			ids
+		};
+
+		assert_eq!(ids(Licensing::agreements_by_film(FILM, None, 10)), vec![0]);
+		assert_eq!(ids(Licensing::agreements_by_film(7, None, 10)), vec![1]);
+		assert_eq!(ids(Licensing::agreements_by_account(DISTRIBUTOR, None, 10)), vec![0, 1]);
+		assert_eq!(ids(Licensing::agreements_by_account(STUDIO, None, 10)), vec![0]);
+
+		// one agreement per page
+		let first = Licensing::agreements_by_account(DISTRIBUTOR, None, 1);
+		assert_eq!(first.len(), 1);
+		let second = Licensing::agreements_by_account(DISTRIBUTOR, Some(first[0].0), 1);
+		assert_eq!(second.len(), 1);
+		assert!(Licensing::agreements_by_account(DISTRIBUTOR, Some(second[0].0), 1).is_empty());
+		assert_eq!(ids([first, second].concat()), vec![0, 1]);
+
+		// settled agreements leave the indexes
+		System::set_block_number(11);
+		assert_ok!(Licensing::release(RuntimeOrigin::signed(STUDIO), 0));
+		assert!(Licensing::agreements_by_film(FILM, None, 10).is_empty());
+		assert_eq!(ids(Licensing::agreements_by_account(DISTRIBUTOR, None, 10)), vec![1]);
+		assert!(Licensing::agreements_by_account(STUDIO, None, 10).is_empty());
+	});
+}
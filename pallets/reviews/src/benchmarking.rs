@@ -0,0 +1,102 @@
+//! Benchmarking setup for kine-reviews
+
+use super::*;
+
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::{
+	traits::{fungible::Mutate, EnsureOrigin, Get},
+	BoundedVec,
+};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+use sp_std::vec;
+
+fn funded_author<T: Config>() -> T::AccountId {
+	let author: T::AccountId = whitelisted_caller();
+	T::Currency::set_balance(&author, BalanceOf::<T>::max_value() / 4u32.into());
+	author
+}
+
+fn longest_text<T: Config>() -> ContentInputOf<T> {
+	ContentInput::Blob(BoundedVec::truncate_from(vec![b'a'; T::MaxBlobLength::get() as usize]))
+}
+
+fn comment_on<T: Config>(author: &T::AccountId, film: T::FilmId) -> Result<(), frame_benchmarking::BenchmarkError> {
+	Pallet::<T>::post_comment(RawOrigin::Signed(author.clone()).into(), film, None, longest_text::<T>())?;
+	Ok(())
+}
+
+benchmarks! {
+	post_review {
+		let author = funded_author::<T>();
+	}: _(RawOrigin::Signed(author.clone()), T::BenchmarkHelper::film(), T::MaxRating::get(), longest_text::<T>())
+	verify {
+		assert!(Reviews::<T>::contains_key(T::BenchmarkHelper::film(), &author));
+	}
+
+	update_review {
+		let author = funded_author::<T>();
+		Pallet::<T>::post_review(
+			RawOrigin::Signed(author.clone()).into(),
+			T::BenchmarkHelper::film(),
+			1,
+			longest_text::<T>(),
+		)?;
+	}: _(RawOrigin::Signed(author), T::BenchmarkHelper::film(), T::MaxRating::get(), longest_text::<T>())
+
+	delete_review {
+		let author = funded_author::<T>();
+		Pallet::<T>::post_review(
+			RawOrigin::Signed(author.clone()).into(),
+			T::BenchmarkHelper::film(),
+			1,
+			longest_text::<T>(),
+		)?;
+	}: _(RawOrigin::Signed(author.clone()), T::BenchmarkHelper::film())
+	verify {
+		assert!(!Reviews::<T>::contains_key(T::BenchmarkHelper::film(), &author));
+	}
+
+	post_comment {
+		let author = funded_author::<T>();
+		comment_on::<T>(&author, T::BenchmarkHelper::film())?;
+	}: _(RawOrigin::Signed(author), T::BenchmarkHelper::film(), Some(0), longest_text::<T>())
+	verify {
+		assert!(Comments::<T>::contains_key(1));
+	}
+
+	delete_comment {
+		let author = funded_author::<T>();
+		comment_on::<T>(&author, T::BenchmarkHelper::film())?;
+	}: _(RawOrigin::Signed(author), 0)
+	verify {
+		assert!(!Comments::<T>::contains_key(0));
+	}
+
+	remove_review {
+		let author = funded_author::<T>();
+		Pallet::<T>::post_review(
+			RawOrigin::Signed(author.clone()).into(),
+			T::BenchmarkHelper::film(),
+			1,
+			longest_text::<T>(),
+		)?;
+		let origin = T::ModerationOrigin::try_successful_origin()
+			.map_err(|_| frame_benchmarking::BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(origin, T::BenchmarkHelper::film(), author.clone())
+	verify {
+		assert!(!Reviews::<T>::contains_key(T::BenchmarkHelper::film(), &author));
+	}
+
+	remove_comment {
+		let author = funded_author::<T>();
+		comment_on::<T>(&author, T::BenchmarkHelper::film())?;
+		let origin = T::ModerationOrigin::try_successful_origin()
+			.map_err(|_| frame_benchmarking::BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(origin, 0)
+	verify {
+		assert!(!Comments::<T>::contains_key(0));
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
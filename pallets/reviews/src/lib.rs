@@ -0,0 +1,499 @@
+//** About **//
+	// Reviews and threaded comments on the films of the film registry. Each account can review
+	// a film once, with a rating and either a short text or the hash of a longer text kept off
+	// chain, and can update or delete its review later. The rating count and total of every
+	// film are kept up to date with each review, so frontends can show a film's score without
+	// indexing the reviews. Comments answer a film or another comment. Reviews and comments
+	// hold a small deposit against spam: the author gets it back when deleting them, but loses
+	// it when the moderation origin removes them. Texts too long for state are written to the
+	// offchain index of nodes running with `--enable-offchain-indexing true`, under the blob
+	// keys of the film registry, and only their hash is kept on chain; nodes serve them with
+	// the `blobs_get` RPC. Blobs stay in the offchain index when their review or comment is
+	// deleted, as other content may have the same hash.
+
+
+
+	#![cfg_attr(not(feature = "std"), no_std)]
+
+	pub use pallet::*;
+
+	#[cfg(test)]
+	mod mock;
+
+	#[cfg(test)]
+	mod tests;
+
+	#[cfg(feature = "runtime-benchmarks")]
+	mod benchmarking;
+
+	/// Registers the films the benchmarks review and comment on.
+	#[cfg(feature = "runtime-benchmarks")]
+	pub trait BenchmarkHelper<FilmId> {
+		fn film() -> FilmId;
+	}
+
+
+
+	#[frame_support::pallet]
+	pub mod pallet {
+
+		//** Config **//
+
+			//* Imports *//
+
+				use frame_support::{
+					dispatch::DispatchResultWithPostInfo,
+					pallet_prelude::*,
+					traits::{
+						fungible::{BalancedHold, Inspect, InspectHold, Mutate, MutateHold},
+						tokens::Precision,
+						Contains, Imbalance,
+					},
+				};
+				use frame_system::pallet_prelude::*;
+				use sp_runtime::{traits::Hash, Perbill};
+
+			//* Config *//
+
+				#[pallet::pallet]
+				pub struct Pallet<T>(_);
+
+				#[pallet::config]
+				pub trait Config: frame_system::Config {
+					type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+					/// The currency review and comment deposits are held in.
+					type Currency: Inspect<Self::AccountId>
+						+ Mutate<Self::AccountId>
+						+ InspectHold<Self::AccountId>
+						+ MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>
+						+ BalancedHold<Self::AccountId>;
+
+					/// The overarching hold reason.
+					type RuntimeHoldReason: From<HoldReason>;
+
+					/// The id of a film in the film registry.
+					type FilmId: Parameter + Member + MaxEncodedLen + Copy;
+
+					/// The films of the film registry, the only ones that can be reviewed and
+					/// commented on.
+					type Films: Contains<Self::FilmId>;
+
+					/// Removes reviews and comments, slashing their deposit.
+					type ModerationOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+					/// The deposit held for every review.
+					#[pallet::constant]
+					type ReviewDeposit: Get<BalanceOf<Self>>;
+
+					/// The deposit held for every comment.
+					#[pallet::constant]
+					type CommentDeposit: Get<BalanceOf<Self>>;
+
+					/// The highest rating, the lowest being 1.
+					#[pallet::constant]
+					type MaxRating: Get<u8>;
+
+					/// The maximum length of the text of a review or a comment kept in state.
+					#[pallet::constant]
+					type MaxTextLength: Get<u32>;
+
+					/// The maximum length of the text of a review or a comment written to the
+					/// offchain index.
+					#[pallet::constant]
+					type MaxBlobLength: Get<u32>;
+
+					#[cfg(feature = "runtime-benchmarks")]
+					type BenchmarkHelper: crate::BenchmarkHelper<Self::FilmId>;
+				}
+
+
+
+		//** Types **//
+
+			//* Types *//
+
+				pub type CommentId = u64;
+				pub type BalanceOf<T> =
+					<<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+				pub type ContentOf<T> = Content<BoundedVec<u8, <T as Config>::MaxTextLength>, <T as frame_system::Config>::Hash>;
+				pub type ContentInputOf<T> = ContentInput<
+					BoundedVec<u8, <T as Config>::MaxTextLength>,
+					BoundedVec<u8, <T as Config>::MaxBlobLength>,
+					<T as frame_system::Config>::Hash,
+				>;
+				pub type ReviewOf<T> = Review<ContentOf<T>, BalanceOf<T>, BlockNumberFor<T>>;
+				pub type CommentOf<T> = Comment<
+					<T as frame_system::Config>::AccountId,
+					<T as Config>::FilmId,
+					ContentOf<T>,
+					BalanceOf<T>,
+				>;
+
+			//* Constants *//
+			//* Enums *//
+
+				#[pallet::composite_enum]
+				pub enum HoldReason {
+					/// The anti-spam deposit of a review.
+					ReviewDeposit,
+					/// The anti-spam deposit of a comment.
+					CommentDeposit,
+				}
+
+				#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+				pub enum Content<Text, Hash> {
+					Text(Text),
+					/// The hash of a text stored off chain, such as on IPFS.
+					Hash(Hash),
+					/// The hash of a text written to the offchain index.
+					Indexed(Hash),
+				}
+
+				/// Content as posted, encoded as `Content` for texts and hashes.
+				#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+				pub enum ContentInput<Text, Blob, Hash> {
+					Text(Text),
+					/// The hash of a text stored off chain, such as on IPFS.
+					Hash(Hash),
+					/// A text written to the offchain index, kept on chain as `Content::Indexed`.
+					Blob(Blob),
+				}
+
+			//* Structs *//
+
+				#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+				pub struct Review<Content, Balance, BlockNumber> {
+					pub rating: u8,
+					pub content: Content,
+					/// The block the review was last posted or updated at.
+					pub updated_at: BlockNumber,
+					pub deposit: Balance,
+				}
+
+				#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+				pub struct Comment<AccountId, FilmId, Content, Balance> {
+					pub author: AccountId,
+					pub film: FilmId,
+					/// The comment this one answers, if any.
+					pub parent: Option<CommentId>,
+					pub content: Content,
+					pub deposit: Balance,
+				}
+
+				/// The ratings of a film, the average being `total / count`.
+				#[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+				pub struct RatingSummary {
+					pub count: u32,
+					pub total: u64,
+				}
+
+
+
+		//** Storage **//
+
+			#[pallet::storage]
+			#[pallet::getter(fn review)]
+			pub type Reviews<T: Config> = StorageDoubleMap<
+				_,
+				Blake2_128Concat, T::FilmId,
+				Blake2_128Concat, T::AccountId,
+				ReviewOf<T>,
+			>;
+
+			#[pallet::storage]
+			#[pallet::getter(fn ratings)]
+			pub type Ratings<T: Config> = StorageMap<_, Blake2_128Concat, T::FilmId, RatingSummary, ValueQuery>;
+
+			#[pallet::storage]
+			#[pallet::getter(fn next_comment_id)]
+			pub type NextCommentId<T> = StorageValue<_, CommentId, ValueQuery>;
+
+			#[pallet::storage]
+			#[pallet::getter(fn comment)]
+			pub type Comments<T: Config> = StorageMap<_, Twox64Concat, CommentId, CommentOf<T>>;
+
+
+
+		//** Events **//
+
+			#[pallet::event]
+			#[pallet::generate_deposit(pub(super) fn deposit_event)]
+			pub enum Event<T: Config> {
+				ReviewPosted { film: T::FilmId, author: T::AccountId, rating: u8 },
+				ReviewUpdated { film: T::FilmId, author: T::AccountId, rating: u8 },
+				ReviewDeleted { film: T::FilmId, author: T::AccountId },
+				/// The moderation origin removed a review and slashed its deposit.
+				ReviewRemoved { film: T::FilmId, author: T::AccountId, slashed: BalanceOf<T> },
+				CommentPosted {
+					comment: CommentId,
+					film: T::FilmId,
+					author: T::AccountId,
+					parent: Option<CommentId>,
+				},
+				CommentDeleted { comment: CommentId },
+				/// The moderation origin removed a comment and slashed its deposit.
+				CommentRemoved { comment: CommentId, slashed: BalanceOf<T> },
+			}
+
+
+
+		//** Errors **//
+
+			#[pallet::error]
+			pub enum Error<T> {
+				/// The film isn't in the film registry.
+				UnknownFilm,
+				InvalidRating,
+				AlreadyReviewed,
+				NoReview,
+				UnknownComment,
+				/// The comment answered is about another film.
+				ParentOnOtherFilm,
+				NotAuthor,
+			}
+
+
+		//** Hooks **//
+		#[pallet::hooks]
+		impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+
+		}
+
+
+
+		//** Extrinsics **//
+
+			#[pallet::call]
+			impl<T: Config> Pallet<T> {
+
+				/// Review a film, holding the review deposit.
+				#[pallet::call_index(0)]
+				#[pallet::weight(
+					Weight::from_parts(45_000_000, 0)
+						+ T::DbWeight::get().reads_writes(4, 4)
+						+ Pallet::<T>::blob_weight(content)
+				)]
+				pub fn post_review(
+					origin: OriginFor<T>,
+					film: T::FilmId,
+					rating: u8,
+					content: ContentInputOf<T>,
+				) -> DispatchResultWithPostInfo {
+					let author = ensure_signed(origin)?;
+
+					ensure!(T::Films::contains(&film), Error::<T>::UnknownFilm);
+					Self::ensure_valid_rating(rating)?;
+					ensure!(!Reviews::<T>::contains_key(film, &author), Error::<T>::AlreadyReviewed);
+
+					let deposit = T::ReviewDeposit::get();
+					T::Currency::hold(&HoldReason::ReviewDeposit.into(), &author, deposit)?;
+
+					Ratings::<T>::mutate(film, |summary| {
+						summary.count = summary.count.saturating_add(1);
+						summary.total = summary.total.saturating_add(rating as u64);
+					});
+					let content = Self::store_content(content);
+					let updated_at = frame_system::Pallet::<T>::block_number();
+					Reviews::<T>::insert(film, &author, Review { rating, content, updated_at, deposit });
+
+					Self::deposit_event(Event::ReviewPosted { film, author, rating });
+					Ok(().into())
+				}
+
+
+				#[pallet::call_index(1)]
+				#[pallet::weight(
+					Weight::from_parts(30_000_000, 0)
+						+ T::DbWeight::get().reads_writes(2, 2)
+						+ Pallet::<T>::blob_weight(content)
+				)]
+				pub fn update_review(
+					origin: OriginFor<T>,
+					film: T::FilmId,
+					rating: u8,
+					content: ContentInputOf<T>,
+				) -> DispatchResultWithPostInfo {
+					let author = ensure_signed(origin)?;
+
+					Self::ensure_valid_rating(rating)?;
+					let previous = Reviews::<T>::get(film, &author).ok_or(Error::<T>::NoReview)?;
+
+					Ratings::<T>::mutate(film, |summary| {
+						summary.total = summary.total.saturating_sub(previous.rating as u64).saturating_add(rating as u64);
+					});
+					let content = Self::store_content(content);
+					let updated_at = frame_system::Pallet::<T>::block_number();
+					Reviews::<T>::insert(film, &author, Review { rating, content, updated_at, deposit: previous.deposit });
+
+					Self::deposit_event(Event::ReviewUpdated { film, author, rating });
+					Ok(().into())
+				}
+
+
+				/// Delete a review as its author, getting the deposit back.
+				#[pallet::call_index(2)]
+				#[pallet::weight(Weight::from_parts(40_000_000, 0) + T::DbWeight::get().reads_writes(3, 3))]
+				pub fn delete_review(origin: OriginFor<T>, film: T::FilmId) -> DispatchResultWithPostInfo {
+					let author = ensure_signed(origin)?;
+
+					let review = Reviews::<T>::take(film, &author).ok_or(Error::<T>::NoReview)?;
+					T::Currency::release(&HoldReason::ReviewDeposit.into(), &author, review.deposit, Precision::BestEffort)?;
+					Self::remove_rating(film, review.rating);
+
+					Self::deposit_event(Event::ReviewDeleted { film, author });
+					Ok(().into())
+				}
+
+
+				/// Comment on a film, or answer another comment about the same film, holding
+				/// the comment deposit.
+				#[pallet::call_index(3)]
+				#[pallet::weight(
+					Weight::from_parts(45_000_000, 0)
+						+ T::DbWeight::get().reads_writes(4, 3)
+						+ Pallet::<T>::blob_weight(content)
+				)]
+				pub fn post_comment(
+					origin: OriginFor<T>,
+					film: T::FilmId,
+					parent: Option<CommentId>,
+					content: ContentInputOf<T>,
+				) -> DispatchResultWithPostInfo {
+					let author = ensure_signed(origin)?;
+
+					ensure!(T::Films::contains(&film), Error::<T>::UnknownFilm);
+					if let Some(parent) = parent {
+						let parent = Comments::<T>::get(parent).ok_or(Error::<T>::UnknownComment)?;
+						ensure!(parent.film == film, Error::<T>::ParentOnOtherFilm);
+					}
+
+					let deposit = T::CommentDeposit::get();
+					T::Currency::hold(&HoldReason::CommentDeposit.into(), &author, deposit)?;
+
+					let comment = NextCommentId::<T>::get();
+					NextCommentId::<T>::put(comment.saturating_add(1));
+					let content = Self::store_content(content);
+					Comments::<T>::insert(comment, Comment { author: author.clone(), film, parent, content, deposit });
+
+					Self::deposit_event(Event::CommentPosted { comment, film, author, parent });
+					Ok(().into())
+				}
+
+
+				/// Delete a comment as its author, getting the deposit back. Answers to the
+				/// comment stay.
+				#[pallet::call_index(4)]
+				#[pallet::weight(Weight::from_parts(40_000_000, 0) + T::DbWeight::get().reads_writes(2, 2))]
+				pub fn delete_comment(origin: OriginFor<T>, comment: CommentId) -> DispatchResultWithPostInfo {
+					let who = ensure_signed(origin)?;
+
+					let details = Comments::<T>::get(comment).ok_or(Error::<T>::UnknownComment)?;
+					ensure!(details.author == who, Error::<T>::NotAuthor);
+
+					T::Currency::release(&HoldReason::CommentDeposit.into(), &who, details.deposit, Precision::BestEffort)?;
+					Comments::<T>::remove(comment);
+
+					Self::deposit_event(Event::CommentDeleted { comment });
+					Ok(().into())
+				}
+
+
+				/// Remove a comment as the moderation origin, burning its deposit.
+				#[pallet::call_index(5)]
+				#[pallet::weight(Weight::from_parts(45_000_000, 0) + T::DbWeight::get().reads_writes(2, 2))]
+				pub fn remove_comment(origin: OriginFor<T>, comment: CommentId) -> DispatchResultWithPostInfo {
+					T::ModerationOrigin::ensure_origin(origin)?;
+
+					let details = Comments::<T>::take(comment).ok_or(Error::<T>::UnknownComment)?;
+					let (credit, _) = T::Currency::slash(&HoldReason::CommentDeposit.into(), &details.author, details.deposit);
+					let slashed = credit.peek();
+					drop(credit);
+
+					Self::deposit_event(Event::CommentRemoved { comment, slashed });
+					Ok(().into())
+				}
+
+
+				/// Remove a review as the moderation origin, burning its deposit.
+				#[pallet::call_index(6)]
+				#[pallet::weight(Weight::from_parts(45_000_000, 0) + T::DbWeight::get().reads_writes(3, 3))]
+				pub fn remove_review(
+					origin: OriginFor<T>,
+					film: T::FilmId,
+					author: T::AccountId,
+				) -> DispatchResultWithPostInfo {
+					T::ModerationOrigin::ensure_origin(origin)?;
+
+					let review = Reviews::<T>::take(film, &author).ok_or(Error::<T>::NoReview)?;
+					let (credit, _) = T::Currency::slash(&HoldReason::ReviewDeposit.into(), &author, review.deposit);
+					let slashed = credit.peek();
+					drop(credit);
+					Self::remove_rating(film, review.rating);
+
+					Self::deposit_event(Event::ReviewRemoved { film, author, slashed });
+					Ok(().into())
+				}
+
+
+			}
+
+
+
+		//** Helpers **//
+
+			impl<T: Config> Pallet<T> {
+
+				fn ensure_valid_rating(rating: u8) -> DispatchResult {
+					ensure!(rating >= 1 && rating <= T::MaxRating::get(), Error::<T>::InvalidRating);
+					Ok(())
+				}
+
+
+				/// Take a deleted review out of the rating summary of `film`.
+				fn remove_rating(film: T::FilmId, rating: u8) {
+					Ratings::<T>::mutate_exists(film, |maybe_summary| {
+						let summary = maybe_summary.get_or_insert_with(Default::default);
+						summary.count = summary.count.saturating_sub(1);
+						summary.total = summary.total.saturating_sub(rating as u64);
+						if summary.count == 0 {
+							*maybe_summary = None;
+						}
+					});
+				}
+
+
+				/// The content stored for `content`, writing blobs to the offchain index.
+				fn store_content(content: ContentInputOf<T>) -> ContentOf<T> {
+					match content {
+						ContentInput::Text(text) => Content::Text(text),
+						ContentInput::Hash(hash) => Content::Hash(hash),
+						ContentInput::Blob(blob) => {
+							let hash = T::Hashing::hash(&blob);
+							sp_io::offchain_index::set(&kine_films::blob_key(hash.as_ref()), &blob);
+							Content::Indexed(hash)
+						},
+					}
+				}
+
+
+				/// The weight of hashing and indexing a blob, on top of the weight of a call. The
+				/// blob is part of the call, so it counts towards the proof size byte for byte.
+				fn blob_weight(content: &ContentInputOf<T>) -> Weight {
+					match content {
+						ContentInput::Blob(blob) => Weight::from_parts(1_000, 1).saturating_mul(blob.len() as u64),
+						_ => Weight::zero(),
+					}
+				}
+
+
+				/// The average rating of `film` as a part of `MaxRating`, if it has any review.
+				pub fn average_rating(film: T::FilmId) -> Option<Perbill> {
+					let RatingSummary { count, total } = Ratings::<T>::get(film);
+					let max = (count as u64).saturating_mul(T::MaxRating::get() as u64);
+					(count > 0).then(|| Perbill::from_rational(total, max))
+				}
+
+
+			}
+	}
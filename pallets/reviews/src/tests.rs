@@ -0,0 +1,161 @@
+use crate::{mock::*, Content, ContentInput, Error, Event, HoldReason, RatingSummary};
+use frame_support::{assert_noop, assert_ok, traits::fungible::InspectHold};
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	DispatchError, Perbill,
+};
+
+fn text(text: &[u8]) -> crate::ContentInputOf<Test> {
+	ContentInput::Text(text.to_vec().try_into().unwrap())
+}
+
+fn held(who: u64) -> u64 {
+	Balances::balance_on_hold(&HoldReason::CommentDeposit.into(), &who)
+}
+
+fn review_deposit(who: u64) -> u64 {
+	Balances::balance_on_hold(&HoldReason::ReviewDeposit.into(), &who)
+}
+
+#[test]
+fn reviews_keep_the_rating_summary_up_to_date() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Reviews::post_review(RuntimeOrigin::signed(ALICE), 1, 4, text(b"who?")), Error::<Test>::UnknownFilm);
+		assert_noop!(Reviews::post_review(RuntimeOrigin::signed(ALICE), FILM, 0, text(b"meh")), Error::<Test>::InvalidRating);
+		assert_noop!(Reviews::post_review(RuntimeOrigin::signed(ALICE), FILM, 6, text(b"wow")), Error::<Test>::InvalidRating);
+
+		assert_ok!(Reviews::post_review(RuntimeOrigin::signed(ALICE), FILM, 4, text(b"Great pacing")));
+		assert_ok!(Reviews::post_review(RuntimeOrigin::signed(BOB), FILM, 2, ContentInput::Hash(H256::repeat_byte(1))));
+		assert_noop!(
+			Reviews::post_review(RuntimeOrigin::signed(BOB), FILM, 5, text(b"again")),
+			Error::<Test>::AlreadyReviewed
+		);
+		assert_eq!(Reviews::ratings(FILM), RatingSummary { count: 2, total: 6 });
+		assert_eq!(Reviews::average_rating(FILM), Some(Perbill::from_percent(60)));
+		assert_eq!(review_deposit(ALICE), 5);
+		System::assert_last_event(Event::ReviewPosted { film: FILM, author: BOB, rating: 2 }.into());
+
+		assert_ok!(Reviews::update_review(RuntimeOrigin::signed(BOB), FILM, 5, text(b"Better the second time")));
+		assert_eq!(Reviews::ratings(FILM), RatingSummary { count: 2, total: 9 });
+		assert_eq!(
+			Reviews::review(FILM, BOB).unwrap().content,
+			Content::Text(b"Better the second time".to_vec().try_into().unwrap())
+		);
+
+		assert_ok!(Reviews::delete_review(RuntimeOrigin::signed(ALICE), FILM));
+		assert_eq!(Reviews::ratings(FILM), RatingSummary { count: 1, total: 5 });
+		assert_eq!(review_deposit(ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), 100);
+		assert_noop!(Reviews::delete_review(RuntimeOrigin::signed(ALICE), FILM), Error::<Test>::NoReview);
+		assert_noop!(
+			Reviews::update_review(RuntimeOrigin::signed(ALICE), FILM, 3, text(b"")),
+			Error::<Test>::NoReview
+		);
+
+		assert_ok!(Reviews::delete_review(RuntimeOrigin::signed(BOB), FILM));
+		assert!(!crate::Ratings::<Test>::contains_key(FILM));
+		assert_eq!(Reviews::average_rating(FILM), None);
+	});
+}
+
+#[test]
+fn comments_are_threaded_per_film() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Reviews::post_comment(RuntimeOrigin::signed(ALICE), 1, None, text(b"who?")),
+			Error::<Test>::UnknownFilm
+		);
+		assert_noop!(
+			Reviews::post_comment(RuntimeOrigin::signed(ALICE), FILM, Some(0), text(b"reply")),
+			Error::<Test>::UnknownComment
+		);
+
+		assert_ok!(Reviews::post_comment(RuntimeOrigin::signed(ALICE), FILM, None, text(b"First!")));
+		assert_ok!(Reviews::post_comment(RuntimeOrigin::signed(BOB), FILM, Some(0), text(b"Indeed")));
+		assert_noop!(
+			Reviews::post_comment(RuntimeOrigin::signed(BOB), OTHER_FILM, Some(0), text(b"wrong thread")),
+			Error::<Test>::ParentOnOtherFilm
+		);
+		assert_noop!(
+			Reviews::post_comment(RuntimeOrigin::signed(POOR), FILM, None, text(b"spam")),
+			sp_runtime::TokenError::FundsUnavailable
+		);
+
+		assert_eq!(Reviews::comment(1).unwrap().parent, Some(0));
+		assert_eq!(held(ALICE), 5);
+		assert_eq!(held(BOB), 5);
+		System::assert_last_event(Event::CommentPosted { comment: 1, film: FILM, author: BOB, parent: Some(0) }.into());
+	});
+}
+
+#[test]
+fn deleting_a_comment_refunds_the_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Reviews::post_comment(RuntimeOrigin::signed(ALICE), FILM, None, text(b"Spoiler-free")));
+
+		assert_noop!(Reviews::delete_comment(RuntimeOrigin::signed(BOB), 0), Error::<Test>::NotAuthor);
+		assert_ok!(Reviews::delete_comment(RuntimeOrigin::signed(ALICE), 0));
+
+		assert_eq!(held(ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), 100);
+		assert!(Reviews::comment(0).is_none());
+		System::assert_last_event(Event::CommentDeleted { comment: 0 }.into());
+	});
+}
+
+#[test]
+fn moderation_slashes_the_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Reviews::post_comment(RuntimeOrigin::signed(BOB), FILM, None, text(b"Buy followers")));
+
+		assert_noop!(Reviews::remove_comment(RuntimeOrigin::signed(ALICE), 0), DispatchError::BadOrigin);
+		assert_ok!(Reviews::remove_comment(RuntimeOrigin::root(), 0));
+
+		assert_eq!(held(BOB), 0);
+		assert_eq!(Balances::free_balance(BOB), 95);
+		assert_eq!(Balances::total_issuance(), 203 - 5);
+		assert!(Reviews::comment(0).is_none());
+		System::assert_last_event(Event::CommentRemoved { comment: 0, slashed: 5 }.into());
+		assert_noop!(Reviews::remove_comment(RuntimeOrigin::root(), 0), Error::<Test>::UnknownComment);
+	});
+}
+
+#[test]
+fn moderation_slashes_the_review_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Reviews::post_review(RuntimeOrigin::signed(ALICE), FILM, 4, text(b"Great pacing")));
+		assert_ok!(Reviews::post_review(RuntimeOrigin::signed(BOB), FILM, 1, text(b"Buy followers")));
+		assert_noop!(
+			Reviews::post_review(RuntimeOrigin::signed(POOR), FILM, 5, text(b"spam")),
+			sp_runtime::TokenError::FundsUnavailable
+		);
+
+		assert_noop!(Reviews::remove_review(RuntimeOrigin::signed(ALICE), FILM, BOB), DispatchError::BadOrigin);
+		assert_ok!(Reviews::remove_review(RuntimeOrigin::root(), FILM, BOB));
+
+		assert_eq!(review_deposit(BOB), 0);
+		assert_eq!(Balances::free_balance(BOB), 95);
+		assert_eq!(Reviews::ratings(FILM), RatingSummary { count: 1, total: 4 });
+		System::assert_last_event(Event::ReviewRemoved { film: FILM, author: BOB, slashed: 5 }.into());
+		assert_noop!(Reviews::remove_review(RuntimeOrigin::root(), FILM, BOB), Error::<Test>::NoReview);
+	});
+}
+
+#[test]
+fn long_texts_go_to_the_offchain_index() {
+	let text = vec![b'a'; 500];
+	let hash = BlakeTwo256::hash(&text);
+	let blob = ContentInput::Blob(text.clone().try_into().unwrap());
+
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		assert_ok!(Reviews::post_review(RuntimeOrigin::signed(ALICE), FILM, 4, blob.clone()));
+		assert_ok!(Reviews::post_comment(RuntimeOrigin::signed(BOB), FILM, None, blob));
+		assert_eq!(Reviews::review(FILM, ALICE).unwrap().content, Content::Indexed(hash));
+		assert_eq!(Reviews::comment(0).unwrap().content, Content::Indexed(hash));
+	});
+
+	ext.persist_offchain_overlay();
+	assert_eq!(ext.offchain_db().get(&kine_films::blob_key(hash.as_bytes())), Some(text));
+}
@@ -0,0 +1,108 @@
+//! Benchmarking setup for kine-royalties
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Royalties;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::{
+	traits::{
+		fungible::{Inspect, Mutate},
+		Get,
+	},
+	BoundedVec,
+};
+use frame_system::RawOrigin;
+use sp_runtime::{traits::Bounded, Perbill};
+use sp_std::{vec, vec::Vec};
+
+/// Equal shares for `payees`, the last one keeping the rounding dust.
+fn equal_shares<T: Config>(payees: Vec<Payee<T::AccountId>>) -> SharesOf<T> {
+	let count = payees.len() as u32;
+	let shares: Vec<_> =
+		payees.into_iter().map(|payee| Share { payee, share: Perbill::from_rational(1, count) }).collect();
+
+	let mut shares = BoundedVec::truncate_from(shares);
+	let total: u32 = shares.iter().map(|share| share.share.deconstruct()).sum();
+	if let Some(last) = shares.last_mut() {
+		last.share = last.share.saturating_add(Perbill::from_parts(Perbill::one().deconstruct() - total));
+	}
+	shares
+}
+
+fn payee<T: Config>(index: u32) -> T::AccountId {
+	let payee: T::AccountId = account("payee", index, 0);
+	T::Currency::set_balance(&payee, T::Currency::minimum_balance());
+	payee
+}
+
+/// Shares paying `count` nested splits of a single payee each, the most reads per payout.
+fn nested_shares<T: Config>(owner: &T::AccountId, count: u32) -> Result<SharesOf<T>, frame_benchmarking::BenchmarkError> {
+	let payees = (0..count)
+		.map(|index| {
+			let shares = equal_shares::<T>(vec![Payee::Account(payee::<T>(index))]);
+			Royalties::<T>::create_split(RawOrigin::Signed(owner.clone()).into(), shares)?;
+			Ok(Payee::Split(NextSplitId::<T>::get() - 1))
+		})
+		.collect::<Result<Vec<_>, frame_benchmarking::BenchmarkError>>()?;
+	Ok(equal_shares::<T>(payees))
+}
+
+fn funded_caller<T: Config>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	T::Currency::set_balance(&caller, BalanceOf::<T>::max_value() / 4u32.into());
+	caller
+}
+
+benchmarks! {
+	create_split {
+		let s in 1 .. T::MaxShares::get();
+		let caller = funded_caller::<T>();
+		let shares = nested_shares::<T>(&caller, s)?;
+		let split = NextSplitId::<T>::get();
+	}: _(RawOrigin::Signed(caller.clone()), shares)
+	verify {
+		assert_eq!(Splits::<T>::get(split).map(|split| split.owner), Some(caller));
+	}
+
+	update_split {
+		let s in 1 .. T::MaxShares::get();
+		let caller = funded_caller::<T>();
+		let previous = nested_shares::<T>(&caller, T::MaxShares::get())?;
+		Royalties::<T>::create_split(RawOrigin::Signed(caller.clone()).into(), previous)?;
+		let split = NextSplitId::<T>::get() - 1;
+		let shares = nested_shares::<T>(&caller, s)?;
+	}: _(RawOrigin::Signed(caller), split, shares)
+
+	transfer_ownership {
+		let caller = funded_caller::<T>();
+		let owner: T::AccountId = account("owner", 0, 0);
+		let shares = equal_shares::<T>(vec![Payee::Account(payee::<T>(0))]);
+		Royalties::<T>::create_split(RawOrigin::Signed(caller.clone()).into(), shares)?;
+	}: _(RawOrigin::Signed(caller), 0, owner.clone())
+	verify {
+		assert_eq!(Splits::<T>::get(0).map(|split| split.owner), Some(owner));
+	}
+
+	remove_split {
+		let s in 1 .. T::MaxShares::get();
+		let caller = funded_caller::<T>();
+		let shares = nested_shares::<T>(&caller, s)?;
+		Royalties::<T>::create_split(RawOrigin::Signed(caller.clone()).into(), shares)?;
+		let split = NextSplitId::<T>::get() - 1;
+	}: _(RawOrigin::Signed(caller), split)
+	verify {
+		assert!(Splits::<T>::get(split).is_none());
+	}
+
+	pay {
+		let p in 1 .. T::MaxShares::get();
+		let caller = funded_caller::<T>();
+		let shares = nested_shares::<T>(&caller, p)?;
+		Royalties::<T>::create_split(RawOrigin::Signed(caller.clone()).into(), shares)?;
+		let split = NextSplitId::<T>::get() - 1;
+		let amount = T::Currency::minimum_balance() * 1_000u32.into();
+	}: _(RawOrigin::Signed(caller), split, PaymentAsset::Native, amount, p)
+}
+
+impl_benchmark_test_suite!(Royalties, crate::mock::new_test_ext(), crate::mock::Test);
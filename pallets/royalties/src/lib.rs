@@ -0,0 +1,477 @@
+//** About **//
+	// Revenue splits for the income of a work, such as the streaming income of a film. The
+	// rights holders of a work register a split table, the share of every payment each
+	// recipient gets, and a recipient can itself be another split so that, say, the
+	// producers' share is split again between the producers. Any payment routed to a split,
+	// in the native token or in a `pallet_assets` token, is paid out to the recipients in the
+	// same extrinsic: either every recipient is paid or none is. Every payout emits an event
+	// naming the split, the recipient, the asset and the amount, for accounting systems.
+	//
+	// Nested splits form no cycles: every split records its height, how many levels of nested
+	// splits it pays through, which is always above the height of the splits it pays to. A
+	// split other splits pay to can be updated without deepening, so the heights stay valid.
+	// Payers give the most payouts they pay for, the weight of a payment growing with them.
+	//
+	// Splits have their own ids, so a film registry only has to remember the id of the split
+	// of each film.
+
+
+
+	#![cfg_attr(not(feature = "std"), no_std)]
+
+	pub use pallet::*;
+
+	pub mod weights;
+	pub use weights::WeightInfo;
+
+	#[cfg(test)]
+	mod mock;
+
+	#[cfg(test)]
+	mod tests;
+
+	#[cfg(feature = "runtime-benchmarks")]
+	mod benchmarking;
+
+
+
+	#[frame_support::pallet]
+	pub mod pallet {
+
+		//** Config **//
+
+			//* Imports *//
+
+				use crate::WeightInfo;
+				use frame_support::{
+					dispatch::DispatchResultWithPostInfo,
+					pallet_prelude::*,
+					storage::with_storage_layer,
+					traits::{
+						fungible::{self, Inspect},
+						fungibles,
+						tokens::Preservation,
+					},
+				};
+				use frame_system::pallet_prelude::*;
+				use sp_runtime::{
+					traits::{Saturating, Zero},
+					Perbill,
+				};
+
+			//* Config *//
+
+				#[pallet::pallet]
+				pub struct Pallet<T>(_);
+
+				#[pallet::config]
+				pub trait Config: frame_system::Config {
+					type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+					/// The native currency.
+					type Currency: fungible::Mutate<Self::AccountId>;
+
+					/// The other assets payments can be made in, usually `pallet_assets`.
+					type Assets: fungibles::Mutate<Self::AccountId, Balance = BalanceOf<Self>>;
+
+					/// The maximum number of recipients of a split.
+					#[pallet::constant]
+					type MaxShares: Get<u32>;
+
+					/// How many levels of nested splits a payment goes through. A nested split
+					/// below the last level is paid to its owner instead of being split again.
+					#[pallet::constant]
+					type MaxDepth: Get<u32>;
+
+					/// Weight information for the extrinsics of this pallet.
+					type WeightInfo: WeightInfo;
+				}
+
+
+
+		//** Types **//
+
+			//* Types *//
+
+				pub type SplitId = u32;
+				pub type BalanceOf<T> =
+					<<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+				pub type AssetIdOf<T> = <<T as Config>::Assets as fungibles::Inspect<
+					<T as frame_system::Config>::AccountId,
+				>>::AssetId;
+				pub type SharesOf<T> = BoundedVec<Share<<T as frame_system::Config>::AccountId>, <T as Config>::MaxShares>;
+				pub type SplitOf<T> = Split<<T as frame_system::Config>::AccountId, SharesOf<T>>;
+
+			//* Constants *//
+			//* Enums *//
+
+				/// Who gets a share of a split.
+				#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+				pub enum Payee<AccountId> {
+					Account(AccountId),
+					/// The share is split again by another split.
+					Split(SplitId),
+				}
+
+				/// The asset of a payment.
+				#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+				pub enum PaymentAsset<AssetId> {
+					Native,
+					Asset(AssetId),
+				}
+
+			//* Structs *//
+
+				#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+				pub struct Share<AccountId> {
+					pub payee: Payee<AccountId>,
+					pub share: Perbill,
+				}
+
+				#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+				pub struct Split<AccountId, Shares> {
+					/// The rights holder that manages the split.
+					pub owner: AccountId,
+					/// The shares of the recipients, adding up to 100%.
+					pub shares: Shares,
+					/// Above the height of every split this one pays to, 0 if it pays to none.
+					pub height: u32,
+				}
+
+
+
+		//** Storage **//
+
+			#[pallet::storage]
+			#[pallet::getter(fn next_split_id)]
+			pub type NextSplitId<T> = StorageValue<_, SplitId, ValueQuery>;
+
+			#[pallet::storage]
+			#[pallet::getter(fn split)]
+			pub type Splits<T: Config> = StorageMap<_, Twox64Concat, SplitId, SplitOf<T>>;
+
+			/// How many shares of other splits pay to each split. A split can't be removed while
+			/// other splits pay to it.
+			#[pallet::storage]
+			#[pallet::getter(fn references)]
+			pub type References<T> = StorageMap<_, Twox64Concat, SplitId, u32, ValueQuery>;
+
+
+
+		//** Events **//
+
+			#[pallet::event]
+			#[pallet::generate_deposit(pub(super) fn deposit_event)]
+			pub enum Event<T: Config> {
+				SplitCreated { split: SplitId, owner: T::AccountId },
+				SplitUpdated { split: SplitId },
+				OwnershipTransferred { split: SplitId, owner: T::AccountId },
+				SplitRemoved { split: SplitId },
+				/// A payment was routed to a split, one `PaidOut` follows for every recipient.
+				Distributed {
+					split: SplitId,
+					payer: T::AccountId,
+					asset: PaymentAsset<AssetIdOf<T>>,
+					amount: BalanceOf<T>,
+				},
+				/// A recipient was paid its share of a split.
+				PaidOut {
+					split: SplitId,
+					payee: T::AccountId,
+					asset: PaymentAsset<AssetIdOf<T>>,
+					amount: BalanceOf<T>,
+				},
+			}
+
+
+
+		//** Errors **//
+
+			#[pallet::error]
+			pub enum Error<T> {
+				UnknownSplit,
+				NotOwner,
+				NoShares,
+				ZeroShare,
+				/// The shares don't add up to 100%.
+				SharesNotWhole,
+				/// A nested split pays back to the split being updated.
+				CyclicSplit,
+				/// Other splits pay to the split, which can't pay to deeper splits than it did.
+				DeeperWhileInUse,
+				SplitInUse,
+				NothingToPay,
+				/// The payment makes more payouts than the payer gave.
+				TooManyPayouts,
+			}
+
+
+		//** Hooks **//
+		#[pallet::hooks]
+		impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+
+		}
+
+
+
+		//** Extrinsics **//
+
+			#[pallet::call]
+			impl<T: Config> Pallet<T> {
+
+				#[pallet::call_index(0)]
+				#[pallet::weight(T::WeightInfo::create_split(shares.len() as u32))]
+				pub fn create_split(origin: OriginFor<T>, shares: SharesOf<T>) -> DispatchResultWithPostInfo {
+					let owner = ensure_signed(origin)?;
+
+					let height = Self::ensure_valid_shares(&shares)?;
+
+					let split = NextSplitId::<T>::get();
+					NextSplitId::<T>::put(split.saturating_add(1));
+
+					Self::add_references(&shares);
+					Splits::<T>::insert(split, Split { owner: owner.clone(), shares, height });
+
+					Self::deposit_event(Event::SplitCreated { split, owner });
+					Ok(().into())
+				}
+
+
+				/// Replace the shares of a split. A split other splits pay to can't pay to deeper
+				/// splits than it did.
+				#[pallet::call_index(1)]
+				#[pallet::weight(T::WeightInfo::update_split(shares.len() as u32))]
+				pub fn update_split(
+					origin: OriginFor<T>,
+					split: SplitId,
+					shares: SharesOf<T>,
+				) -> DispatchResultWithPostInfo {
+					let who = ensure_signed(origin)?;
+
+					let mut current = Splits::<T>::get(split).ok_or(Error::<T>::UnknownSplit)?;
+					ensure!(current.owner == who, Error::<T>::NotOwner);
+
+					ensure!(
+						!shares.iter().any(|share| share.payee == Payee::Split(split)),
+						Error::<T>::CyclicSplit
+					);
+					let height = Self::ensure_valid_shares(&shares)?;
+					// the splits paying to this one stay above it
+					ensure!(
+						height <= current.height || References::<T>::get(split).is_zero(),
+						Error::<T>::DeeperWhileInUse
+					);
+
+					Self::remove_references(&current.shares);
+					Self::add_references(&shares);
+					current.shares = shares;
+					current.height = height;
+					Splits::<T>::insert(split, current);
+
+					Self::deposit_event(Event::SplitUpdated { split });
+					Ok(().into())
+				}
+
+
+				#[pallet::call_index(2)]
+				#[pallet::weight(T::WeightInfo::transfer_ownership())]
+				pub fn transfer_ownership(
+					origin: OriginFor<T>,
+					split: SplitId,
+					owner: T::AccountId,
+				) -> DispatchResultWithPostInfo {
+					let who = ensure_signed(origin)?;
+
+					Splits::<T>::try_mutate(split, |maybe_split| -> DispatchResult {
+						let current = maybe_split.as_mut().ok_or(Error::<T>::UnknownSplit)?;
+						ensure!(current.owner == who, Error::<T>::NotOwner);
+						current.owner = owner.clone();
+						Ok(())
+					})?;
+
+					Self::deposit_event(Event::OwnershipTransferred { split, owner });
+					Ok(().into())
+				}
+
+
+				#[pallet::call_index(3)]
+				#[pallet::weight(T::WeightInfo::remove_split(T::MaxShares::get()))]
+				pub fn remove_split(origin: OriginFor<T>, split: SplitId) -> DispatchResultWithPostInfo {
+					let who = ensure_signed(origin)?;
+
+					let current = Splits::<T>::get(split).ok_or(Error::<T>::UnknownSplit)?;
+					ensure!(current.owner == who, Error::<T>::NotOwner);
+					ensure!(References::<T>::get(split).is_zero(), Error::<T>::SplitInUse);
+
+					Self::remove_references(&current.shares);
+					Splits::<T>::remove(split);
+
+					Self::deposit_event(Event::SplitRemoved { split });
+					Ok(Some(T::WeightInfo::remove_split(current.shares.len() as u32)).into())
+				}
+
+
+				/// Pay `amount` of `asset` to the recipients of `split`, in at most `max_payouts`
+				/// payouts. The weight is refunded down to the payouts actually made.
+				#[pallet::call_index(4)]
+				#[pallet::weight(T::WeightInfo::pay(*max_payouts))]
+				pub fn pay(
+					origin: OriginFor<T>,
+					split: SplitId,
+					asset: PaymentAsset<AssetIdOf<T>>,
+					amount: BalanceOf<T>,
+					max_payouts: u32,
+				) -> DispatchResultWithPostInfo {
+					let payer = ensure_signed(origin)?;
+
+					let payouts = Self::distribute_payment(&payer, split, asset, amount, max_payouts)?;
+
+					Ok(Some(T::WeightInfo::pay(payouts)).into())
+				}
+
+
+			}
+
+
+
+		//** Helpers **//
+
+			impl<T: Config> Pallet<T> {
+
+				/// Pay `amount` of `asset` from `payer` to the recipients of `split` in at most
+				/// `max_payouts` payouts, for other pallets that route income to a split, weighing
+				/// it with `WeightInfo::pay`. Either every recipient is paid or none is. Returns
+				/// the number of payouts made.
+				pub fn distribute_payment(
+					payer: &T::AccountId,
+					split: SplitId,
+					asset: PaymentAsset<AssetIdOf<T>>,
+					amount: BalanceOf<T>,
+					max_payouts: u32,
+				) -> Result<u32, DispatchError> {
+					ensure!(!amount.is_zero(), Error::<T>::NothingToPay);
+
+					with_storage_layer(|| {
+						let mut payouts = 0;
+						Self::distribute(payer, split, asset, amount, 1, max_payouts, &mut payouts)?;
+
+						Self::deposit_event(Event::Distributed { split, payer: payer.clone(), asset, amount });
+						Ok(payouts)
+					})
+				}
+
+
+				/// The most payouts a payment can make, every share of every level going to a
+				/// nested split.
+				pub fn max_payouts() -> u32 {
+					T::MaxShares::get().saturating_pow(T::MaxDepth::get())
+				}
+
+
+				fn distribute(
+					payer: &T::AccountId,
+					split: SplitId,
+					asset: PaymentAsset<AssetIdOf<T>>,
+					amount: BalanceOf<T>,
+					depth: u32,
+					max_payouts: u32,
+					payouts: &mut u32,
+				) -> DispatchResult {
+					let Split { shares, .. } = Splits::<T>::get(split).ok_or(Error::<T>::UnknownSplit)?;
+
+					// the last recipient also gets the rounding dust
+					let last = shares.len().saturating_sub(1);
+					let mut remaining = amount;
+
+					for (index, Share { payee, share }) in shares.into_iter().enumerate() {
+						let part = if index == last { remaining } else { share * amount };
+						remaining = remaining.saturating_sub(part);
+						if part.is_zero() {
+							continue
+						}
+
+						match payee {
+							Payee::Split(nested) if depth < T::MaxDepth::get() =>
+								Self::distribute(payer, nested, asset, part, depth + 1, max_payouts, payouts)?,
+							Payee::Split(nested) => {
+								let owner = Splits::<T>::get(nested).ok_or(Error::<T>::UnknownSplit)?.owner;
+								Self::count_payout(max_payouts, payouts)?;
+								Self::pay_out(payer, split, &owner, asset, part)?;
+							},
+							Payee::Account(who) => {
+								Self::count_payout(max_payouts, payouts)?;
+								Self::pay_out(payer, split, &who, asset, part)?;
+							},
+						}
+					}
+
+					Ok(())
+				}
+
+
+				fn count_payout(max_payouts: u32, payouts: &mut u32) -> DispatchResult {
+					ensure!(*payouts < max_payouts, Error::<T>::TooManyPayouts);
+					*payouts += 1;
+					Ok(())
+				}
+
+
+				fn pay_out(
+					payer: &T::AccountId,
+					split: SplitId,
+					payee: &T::AccountId,
+					asset: PaymentAsset<AssetIdOf<T>>,
+					amount: BalanceOf<T>,
+				) -> DispatchResult {
+					match asset {
+						PaymentAsset::Native =>
+							<T::Currency as fungible::Mutate<_>>::transfer(payer, payee, amount, Preservation::Preserve)?,
+						PaymentAsset::Asset(id) =>
+							<T::Assets as fungibles::Mutate<_>>::transfer(id, payer, payee, amount, Preservation::Preserve)?,
+					};
+
+					Self::deposit_event(Event::PaidOut { split, payee: payee.clone(), asset, amount });
+					Ok(())
+				}
+
+
+				/// Check `shares`, returning the height of a split paying them.
+				fn ensure_valid_shares(shares: &SharesOf<T>) -> Result<u32, DispatchError> {
+					ensure!(!shares.is_empty(), Error::<T>::NoShares);
+
+					let mut total: u64 = 0;
+					let mut height = 0;
+					for Share { payee, share } in shares.iter() {
+						ensure!(!share.is_zero(), Error::<T>::ZeroShare);
+						if let Payee::Split(nested) = payee {
+							let nested = Splits::<T>::get(nested).ok_or(Error::<T>::UnknownSplit)?;
+							height = height.max(nested.height.saturating_add(1));
+						}
+						total = total.saturating_add(share.deconstruct() as u64);
+					}
+					ensure!(total == Perbill::one().deconstruct() as u64, Error::<T>::SharesNotWhole);
+
+					Ok(height)
+				}
+
+
+				fn add_references(shares: &SharesOf<T>) {
+					for Share { payee, .. } in shares.iter() {
+						if let Payee::Split(nested) = payee {
+							References::<T>::mutate(nested, |count| *count = count.saturating_add(1));
+						}
+					}
+				}
+
+
+				fn remove_references(shares: &SharesOf<T>) {
+					for Share { payee, .. } in shares.iter() {
+						if let Payee::Split(nested) = payee {
+							References::<T>::mutate(nested, |count| *count = count.saturating_sub(1));
+						}
+					}
+				}
+
+
+			}
+	}
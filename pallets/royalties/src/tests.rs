@@ -0,0 +1,182 @@
+use crate::{mock::*, Error, Event, Payee, PaymentAsset, Share, SharesOf};
+use frame_support::{assert_noop, assert_ok, traits::fungibles::Inspect};
+use sp_runtime::Perbill;
+
+fn shares(list: Vec<(Payee<u64>, u32)>) -> SharesOf<Test> {
+	list.into_iter()
+		.map(|(payee, percent)| Share { payee, share: Perbill::from_percent(percent) })
+		.collect::<Vec<_>>()
+		.try_into()
+		.unwrap()
+}
+
+/// The producers' split, then a film split paying 50% to the director, 20% to the writer and
+/// 30% to the producers.
+fn film_split() -> u32 {
+	assert_ok!(Royalties::create_split(
+		RuntimeOrigin::signed(PRODUCER_A),
+		shares(vec![(Payee::Account(PRODUCER_A), 50), (Payee::Account(PRODUCER_B), 50)]),
+	));
+	assert_ok!(Royalties::create_split(
+		RuntimeOrigin::signed(DIRECTOR),
+		shares(vec![(Payee::Account(DIRECTOR), 50), (Payee::Account(WRITER), 20), (Payee::Split(0), 30)]),
+	));
+	1
+}
+
+#[test]
+fn create_split_checks_shares() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Royalties::create_split(RuntimeOrigin::signed(DIRECTOR), shares(vec![])), Error::<Test>::NoShares);
+		assert_noop!(
+			Royalties::create_split(
+				RuntimeOrigin::signed(DIRECTOR),
+				shares(vec![(Payee::Account(DIRECTOR), 100), (Payee::Account(WRITER), 0)]),
+			),
+			Error::<Test>::ZeroShare
+		);
+		assert_noop!(
+			Royalties::create_split(
+				RuntimeOrigin::signed(DIRECTOR),
+				shares(vec![(Payee::Account(DIRECTOR), 60), (Payee::Account(WRITER), 30)]),
+			),
+			Error::<Test>::SharesNotWhole
+		);
+		assert_noop!(
+			Royalties::create_split(RuntimeOrigin::signed(DIRECTOR), shares(vec![(Payee::Split(5), 100)])),
+			Error::<Test>::UnknownSplit
+		);
+
+		let split = film_split();
+		System::assert_last_event(Event::SplitCreated { split, owner: DIRECTOR }.into());
+		assert_eq!(Royalties::references(0), 1);
+	});
+}
+
+#[test]
+fn pay_distributes_native_payments_through_nested_splits() {
+	new_test_ext().execute_with(|| {
+		let split = film_split();
+
+		assert_ok!(Royalties::pay(RuntimeOrigin::signed(STREAMER), split, PaymentAsset::Native, 1_000, 4));
+
+		assert_eq!(Balances::free_balance(DIRECTOR), 10 + 500);
+		assert_eq!(Balances::free_balance(WRITER), 10 + 200);
+		assert_eq!(Balances::free_balance(PRODUCER_A), 10 + 150);
+		assert_eq!(Balances::free_balance(PRODUCER_B), 10 + 150);
+		assert_eq!(Balances::free_balance(STREAMER), 10_000 - 1_000);
+		System::assert_has_event(
+			Event::PaidOut { split: 0, payee: PRODUCER_B, asset: PaymentAsset::Native, amount: 150 }.into(),
+		);
+		System::assert_last_event(
+			Event::Distributed { split, payer: STREAMER, asset: PaymentAsset::Native, amount: 1_000 }.into(),
+		);
+	});
+}
+
+#[test]
+fn pay_distributes_asset_payments_and_keeps_the_dust_with_the_last_payee() {
+	new_test_ext().execute_with(|| {
+		let split = film_split();
+
+		assert_ok!(Royalties::pay(RuntimeOrigin::signed(STREAMER), split, PaymentAsset::Asset(USD), 101, 4));
+
+		assert_eq!(Assets::balance(USD, DIRECTOR), 50);
+		assert_eq!(Assets::balance(USD, WRITER), 20);
+		assert_eq!(Assets::balance(USD, PRODUCER_A), 15);
+		assert_eq!(Assets::balance(USD, PRODUCER_B), 16);
+		assert_eq!(Assets::balance(USD, STREAMER), 10_000 - 101);
+	});
+}
+
+#[test]
+fn pay_is_all_or_nothing() {
+	new_test_ext().execute_with(|| {
+		let split = film_split();
+
+		// the last payout would reap the payer
+		assert_noop!(
+			Royalties::pay(RuntimeOrigin::signed(STREAMER), split, PaymentAsset::Native, 10_000, 4),
+			sp_runtime::TokenError::FundsUnavailable
+		);
+		assert_noop!(
+			Royalties::pay(RuntimeOrigin::signed(STREAMER), split, PaymentAsset::Native, 0, 4),
+			Error::<Test>::NothingToPay
+		);
+		// the payer pays for fewer payouts than the split makes
+		assert_noop!(
+			Royalties::pay(RuntimeOrigin::signed(STREAMER), split, PaymentAsset::Native, 1_000, 3),
+			Error::<Test>::TooManyPayouts
+		);
+	});
+}
+
+#[test]
+fn splits_below_the_last_level_pay_their_owner() {
+	new_test_ext().execute_with(|| {
+		let film = film_split();
+		assert_ok!(Royalties::create_split(RuntimeOrigin::signed(WRITER), shares(vec![(Payee::Split(film), 100)])));
+
+		assert_ok!(Royalties::pay(RuntimeOrigin::signed(STREAMER), 2, PaymentAsset::Native, 1_000, 3));
+
+		// the producers' split is the third level, its owner gets its whole share
+		assert_eq!(Balances::free_balance(PRODUCER_A), 10 + 300);
+		assert_eq!(Balances::free_balance(PRODUCER_B), 10);
+	});
+}
+
+#[test]
+fn update_split_rejects_cycles() {
+	new_test_ext().execute_with(|| {
+		let film = film_split();
+		assert_ok!(Royalties::create_split(RuntimeOrigin::signed(WRITER), shares(vec![(Payee::Split(film), 100)])));
+		assert_eq!((Royalties::split(0).unwrap().height, Royalties::split(film).unwrap().height), (0, 1));
+		assert_eq!(Royalties::split(2).unwrap().height, 2);
+
+		assert_noop!(
+			Royalties::update_split(RuntimeOrigin::signed(PRODUCER_A), 0, shares(vec![(Payee::Split(0), 100)])),
+			Error::<Test>::CyclicSplit
+		);
+		assert_noop!(
+			Royalties::update_split(RuntimeOrigin::signed(PRODUCER_A), 0, shares(vec![(Payee::Split(film), 100)])),
+			Error::<Test>::DeeperWhileInUse
+		);
+		// deeper than the levels payments go through
+		assert_noop!(
+			Royalties::update_split(RuntimeOrigin::signed(PRODUCER_A), 0, shares(vec![(Payee::Split(2), 100)])),
+			Error::<Test>::DeeperWhileInUse
+		);
+		assert_noop!(
+			Royalties::update_split(RuntimeOrigin::signed(DIRECTOR), 0, shares(vec![(Payee::Account(DIRECTOR), 100)])),
+			Error::<Test>::NotOwner
+		);
+
+		// splits other splits pay to can still be updated without deepening
+		assert_ok!(Royalties::update_split(
+			RuntimeOrigin::signed(PRODUCER_A),
+			0,
+			shares(vec![(Payee::Account(PRODUCER_A), 100)]),
+		));
+		assert_ok!(Royalties::update_split(
+			RuntimeOrigin::signed(DIRECTOR),
+			film,
+			shares(vec![(Payee::Account(DIRECTOR), 100)]),
+		));
+		assert_eq!(Royalties::references(0), 0);
+		assert_eq!(Royalties::split(film).unwrap().height, 0);
+	});
+}
+
+#[test]
+fn remove_split_requires_no_references() {
+	new_test_ext().execute_with(|| {
+		let film = film_split();
+
+		assert_noop!(Royalties::remove_split(RuntimeOrigin::signed(PRODUCER_A), 0), Error::<Test>::SplitInUse);
+
+		assert_ok!(Royalties::transfer_ownership(RuntimeOrigin::signed(DIRECTOR), film, WRITER));
+		assert_ok!(Royalties::remove_split(RuntimeOrigin::signed(WRITER), film));
+		assert_ok!(Royalties::remove_split(RuntimeOrigin::signed(PRODUCER_A), 0));
+		assert_eq!(Royalties::split(film), None);
+	});
+}
@@ -0,0 +1,118 @@
+//! Weights for `kine_royalties`
+//!
+//! Conservative estimates until the pallet is first benchmarked on the reference hardware.
+//! Regenerate with `./scripts/benchmark.sh kine_royalties`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `kine_royalties`.
+pub trait WeightInfo {
+	fn create_split(s: u32, ) -> Weight;
+	fn update_split(s: u32, ) -> Weight;
+	fn transfer_ownership() -> Weight;
+	fn remove_split(s: u32, ) -> Weight;
+	fn pay(p: u32, ) -> Weight;
+}
+
+/// Weights for `kine_royalties` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `Royalties::Splits` (r:20 w:1)
+	/// Storage: `Royalties::NextSplitId` (r:1 w:1)
+	/// Storage: `Royalties::References` (r:20 w:20)
+	/// The range of component `s` is `[1, 20]`.
+	fn create_split(s: u32, ) -> Weight {
+		Weight::from_parts(25_000_000, 2_000)
+			.saturating_add(Weight::from_parts(6_000_000, 2_700).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(s.into())))
+	}
+	/// Storage: `Royalties::Splits` (r:21 w:1)
+	/// Storage: `Royalties::References` (r:41 w:40)
+	/// The range of component `s` is `[1, 20]`.
+	fn update_split(s: u32, ) -> Weight {
+		Weight::from_parts(35_000_000, 3_500)
+			.saturating_add(Weight::from_parts(9_000_000, 2_700).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(s.into())))
+	}
+	/// Storage: `Royalties::Splits` (r:1 w:1)
+	fn transfer_ownership() -> Weight {
+		Weight::from_parts(20_000_000, 2_700)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Royalties::Splits` (r:1 w:1)
+	/// Storage: `Royalties::References` (r:21 w:20)
+	/// The range of component `s` is `[1, 20]`.
+	fn remove_split(s: u32, ) -> Weight {
+		Weight::from_parts(25_000_000, 3_500)
+			.saturating_add(Weight::from_parts(4_000_000, 2_500).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(s.into())))
+	}
+	/// Storage: `Royalties::Splits` (r:21 w:0)
+	/// Storage: `System::Account` (r:21 w:21)
+	/// The range of component `p` is `[1, 20]`.
+	fn pay(p: u32, ) -> Weight {
+		Weight::from_parts(20_000_000, 3_500)
+			.saturating_add(Weight::from_parts(50_000_000, 5_300).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(p.into())))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	fn create_split(s: u32, ) -> Weight {
+		Weight::from_parts(25_000_000, 2_000)
+			.saturating_add(Weight::from_parts(6_000_000, 2_700).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(s.into())))
+			.saturating_add(RocksDbWeight::get().writes(2))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(s.into())))
+	}
+	fn update_split(s: u32, ) -> Weight {
+		Weight::from_parts(35_000_000, 3_500)
+			.saturating_add(Weight::from_parts(9_000_000, 2_700).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(s.into())))
+			.saturating_add(RocksDbWeight::get().writes(1))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(s.into())))
+	}
+	fn transfer_ownership() -> Weight {
+		Weight::from_parts(20_000_000, 2_700)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn remove_split(s: u32, ) -> Weight {
+		Weight::from_parts(25_000_000, 3_500)
+			.saturating_add(Weight::from_parts(4_000_000, 2_500).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(s.into())))
+			.saturating_add(RocksDbWeight::get().writes(1))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(s.into())))
+	}
+	fn pay(p: u32, ) -> Weight {
+		Weight::from_parts(20_000_000, 3_500)
+			.saturating_add(Weight::from_parts(50_000_000, 5_300).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(1))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(p.into())))
+	}
+}
@@ -0,0 +1,93 @@
+//! The chain extension of ink! contracts: read access to the film registry, so contracts can
+//! check who owns a film and how it is filed before, say, selling tickets for it or splitting
+//! its revenue.
+//!
+//! Functions take their SCALE encoded arguments and write back their SCALE encoded result:
+//!
+//! | id | function        | arguments              | result                   |
+//! |----|-----------------|------------------------|--------------------------|
+//! | 1  | `owner_of`      | `(FilmId)`             | `Option<AccountId>`      |
+//! | 2  | `is_owner`      | `(FilmId, AccountId)`  | `bool`                   |
+//! | 3  | `category_of`   | `(FilmId)`             | `Option<CategoryId>`     |
+//! | 4  | `has_tag`       | `(FilmId, TagId)`      | `bool`                   |
+//! | 5  | `category_name` | `(CategoryId)`         | `Option<Vec<u8>>`        |
+//! | 6  | `tag_name`      | `(TagId)`              | `Option<Vec<u8>>`        |
+
+use core::marker::PhantomData;
+
+use codec::{Encode, MaxEncodedLen};
+use frame_support::{traits::Get, weights::Weight};
+use kine_films::{CategoryId, FilmId, FilmOf, NameOf, TagId};
+use pallet_contracts::chain_extension::{ChainExtension, Environment, Ext, InitState, RetVal};
+use sp_runtime::DispatchError;
+
+const OWNER_OF: u16 = 1;
+const IS_OWNER: u16 = 2;
+const CATEGORY_OF: u16 = 3;
+const HAS_TAG: u16 = 4;
+const CATEGORY_NAME: u16 = 5;
+const TAG_NAME: u16 = 6;
+
+pub struct ContentExtension<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Default for ContentExtension<Runtime> {
+	fn default() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<Runtime> ChainExtension<Runtime> for ContentExtension<Runtime>
+where
+	Runtime: pallet_contracts::Config + kine_films::Config,
+{
+	fn call<E: Ext<T = Runtime>>(&mut self, env: Environment<E, InitState>) -> Result<RetVal, DispatchError> {
+		let func_id = env.func_id();
+		let mut env = env.buf_in_buf_out();
+
+		// Every function reads a single film, category or tag.
+		let read = |max_len: usize| {
+			<Runtime as frame_system::Config>::DbWeight::get()
+				.reads(1)
+				.saturating_add(Weight::from_parts(0, max_len as u64))
+		};
+		let film_read = read(FilmOf::<Runtime>::max_encoded_len());
+		let name_read = read(NameOf::<Runtime>::max_encoded_len());
+
+		let output = match func_id {
+			OWNER_OF => {
+				env.charge_weight(film_read)?;
+				let film: FilmId = env.read_as()?;
+				kine_films::Pallet::<Runtime>::owner_of(film).encode()
+			},
+			IS_OWNER => {
+				env.charge_weight(film_read)?;
+				let (film, who): (FilmId, Runtime::AccountId) = env.read_as()?;
+				(kine_films::Pallet::<Runtime>::owner_of(film) == Some(who)).encode()
+			},
+			CATEGORY_OF => {
+				env.charge_weight(film_read)?;
+				let film: FilmId = env.read_as()?;
+				kine_films::Pallet::<Runtime>::film(film).map(|details| details.category).encode()
+			},
+			HAS_TAG => {
+				env.charge_weight(film_read)?;
+				let (film, tag): (FilmId, TagId) = env.read_as()?;
+				kine_films::Pallet::<Runtime>::has_tag(film, tag).encode()
+			},
+			CATEGORY_NAME => {
+				env.charge_weight(name_read)?;
+				let category: CategoryId = env.read_as()?;
+				kine_films::Pallet::<Runtime>::category(category).encode()
+			},
+			TAG_NAME => {
+				env.charge_weight(name_read)?;
+				let tag: TagId = env.read_as()?;
+				kine_films::Pallet::<Runtime>::tag(tag).encode()
+			},
+			_ => return Err(DispatchError::Other("Unknown chain extension function")),
+		};
+
+		env.write(&output, false, None)?;
+		Ok(RetVal::Converging(0))
+	}
+}
@@ -0,0 +1,64 @@
+//! Exposes the native token to Solidity with an ERC-20 like interface, at `0x800`.
+
+use core::marker::PhantomData;
+
+use fp_evm::PrecompileHandle;
+use frame_support::dispatch::{GetDispatchInfo, PostDispatchInfo};
+use pallet_evm::AddressMapping;
+use precompile_utils::prelude::*;
+use sp_core::U256;
+use sp_runtime::traits::{Dispatchable, StaticLookup};
+
+/// `System::Account` entries: the Blake2_128Concat key of an `AccountId32` and the account info.
+const ACCOUNT_ENTRY_SIZE: usize = 16 + 32 + 80;
+/// The `Balances::TotalIssuance` value.
+const TOTAL_ISSUANCE_SIZE: usize = 16;
+
+pub struct BalancesPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> BalancesPrecompile<Runtime>
+where
+	Runtime: pallet_balances::Config + pallet_evm::Config,
+	Runtime::RuntimeCall:
+		Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo + From<pallet_balances::Call<Runtime>>,
+	<Runtime::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<Runtime::AccountId>>,
+	Runtime::Balance: TryFrom<U256> + Into<U256>,
+{
+	#[precompile::public("totalSupply()")]
+	#[precompile::view]
+	fn total_supply(handle: &mut impl PrecompileHandle) -> EvmResult<U256> {
+		handle.record_db_read::<Runtime>(TOTAL_ISSUANCE_SIZE)?;
+
+		Ok(pallet_balances::Pallet::<Runtime>::total_issuance().into())
+	}
+
+	#[precompile::public("balanceOf(address)")]
+	#[precompile::view]
+	fn balance_of(handle: &mut impl PrecompileHandle, owner: Address) -> EvmResult<U256> {
+		handle.record_db_read::<Runtime>(ACCOUNT_ENTRY_SIZE)?;
+
+		let owner = Runtime::AddressMapping::into_account_id(owner.into());
+		Ok(pallet_balances::Pallet::<Runtime>::usable_balance(&owner).into())
+	}
+
+	#[precompile::public("transfer(address,uint256)")]
+	fn transfer(handle: &mut impl PrecompileHandle, to: Address, value: U256) -> EvmResult<bool> {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let dest = Runtime::AddressMapping::into_account_id(to.into());
+		let value: Runtime::Balance = value
+			.try_into()
+			.map_err(|_| RevertReason::value_is_too_large("balance type").in_field("value"))?;
+
+		RuntimeHelper::<Runtime>::try_dispatch(
+			handle,
+			Some(origin).into(),
+			pallet_balances::Call::<Runtime>::transfer_allow_death {
+				dest: Runtime::Lookup::unlookup(dest),
+				value,
+			},
+		)?;
+
+		Ok(true)
+	}
+}
@@ -0,0 +1,79 @@
+//! Exposes the film registry and the ratings of films to Solidity, at `0x801`.
+
+use core::marker::PhantomData;
+
+use codec::MaxEncodedLen;
+use fp_evm::PrecompileHandle;
+use kine_films::{CategoryId, FilmId, FilmOf, TagId};
+use kine_reviews::RatingSummary;
+use pallet_evm::AddressMapping;
+use precompile_utils::prelude::*;
+use sp_core::H256;
+
+/// `Films::Films` entries: the Twox64Concat key of a film id and the film.
+fn film_entry_size<Runtime: kine_films::Config>() -> usize {
+	8 + 4 + FilmOf::<Runtime>::max_encoded_len()
+}
+/// `Reviews::Ratings` entries: the Blake2_128Concat key of a film id and the rating summary.
+const RATINGS_ENTRY_SIZE: usize = 16 + 4 + 12;
+
+pub struct ContentPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> ContentPrecompile<Runtime>
+where
+	Runtime: kine_films::Config + kine_reviews::Config<FilmId = FilmId> + pallet_evm::Config,
+	Runtime::AccountId: Into<[u8; 32]>,
+{
+	/// Whether `film` is registered, and the native account owning it.
+	#[precompile::public("ownerOf(uint32)")]
+	#[precompile::view]
+	fn owner_of(handle: &mut impl PrecompileHandle, film: u32) -> EvmResult<(bool, H256)> {
+		handle.record_db_read::<Runtime>(film_entry_size::<Runtime>())?;
+
+		Ok(match kine_films::Pallet::<Runtime>::owner_of(film) {
+			Some(owner) => (true, H256(owner.into())),
+			None => (false, H256::zero()),
+		})
+	}
+
+	/// Whether `who`, through the account it maps to, owns `film`.
+	#[precompile::public("isOwner(uint32,address)")]
+	#[precompile::view]
+	fn is_owner(handle: &mut impl PrecompileHandle, film: u32, who: Address) -> EvmResult<bool> {
+		handle.record_db_read::<Runtime>(film_entry_size::<Runtime>())?;
+
+		let who = Runtime::AddressMapping::into_account_id(who.into());
+		Ok(kine_films::Pallet::<Runtime>::owner_of(film) == Some(who))
+	}
+
+	/// Whether `film` is registered, and its category.
+	#[precompile::public("categoryOf(uint32)")]
+	#[precompile::view]
+	fn category_of(handle: &mut impl PrecompileHandle, film: u32) -> EvmResult<(bool, CategoryId)> {
+		handle.record_db_read::<Runtime>(film_entry_size::<Runtime>())?;
+
+		Ok(match kine_films::Pallet::<Runtime>::film(film) {
+			Some(details) => (true, details.category),
+			None => (false, 0),
+		})
+	}
+
+	#[precompile::public("hasTag(uint32,uint32)")]
+	#[precompile::view]
+	fn has_tag(handle: &mut impl PrecompileHandle, film: u32, tag: TagId) -> EvmResult<bool> {
+		handle.record_db_read::<Runtime>(film_entry_size::<Runtime>())?;
+
+		Ok(kine_films::Pallet::<Runtime>::has_tag(film, tag))
+	}
+
+	/// The number of ratings of `film` and their total, the average being `total / count`.
+	#[precompile::public("ratingOf(uint32)")]
+	#[precompile::view]
+	fn rating_of(handle: &mut impl PrecompileHandle, film: u32) -> EvmResult<(u32, u64)> {
+		handle.record_db_read::<Runtime>(RATINGS_ENTRY_SIZE)?;
+
+		let RatingSummary { count, total } = kine_reviews::Pallet::<Runtime>::ratings(film);
+		Ok((count, total))
+	}
+}
@@ -0,0 +1,33 @@
+//! The precompiles available to EVM contracts: the standard Ethereum ones, plus the ones
+//! exposing this runtime's pallets to Solidity.
+
+use precompile_utils::precompile_set::*;
+
+use pallet_evm_precompile_modexp::Modexp;
+use pallet_evm_precompile_sha3fips::Sha3FIPS256;
+use pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256};
+
+mod balances;
+mod content;
+
+pub use balances::BalancesPrecompile;
+pub use content::ContentPrecompile;
+
+/// The checks of the precompiles of the Ethereum mainnet.
+type EthereumPrecompilesChecks = (AcceptDelegateCall, CallableByContract, CallableByPrecompile);
+
+/// Precompiles at `0x01..0x05` follow Ethereum, `0x400` (1024) and up are Frontier specific
+/// and `0x800` (2048) and up belong to this runtime.
+type KinePrecompilesAt<R> = (
+	PrecompileAt<AddressU64<1>, ECRecover, EthereumPrecompilesChecks>,
+	PrecompileAt<AddressU64<2>, Sha256, EthereumPrecompilesChecks>,
+	PrecompileAt<AddressU64<3>, Ripemd160, EthereumPrecompilesChecks>,
+	PrecompileAt<AddressU64<4>, Identity, EthereumPrecompilesChecks>,
+	PrecompileAt<AddressU64<5>, Modexp, EthereumPrecompilesChecks>,
+	PrecompileAt<AddressU64<1024>, Sha3FIPS256, (CallableByContract, CallableByPrecompile)>,
+	PrecompileAt<AddressU64<1025>, ECRecoverPublicKey, (CallableByContract, CallableByPrecompile)>,
+	PrecompileAt<AddressU64<2048>, BalancesPrecompile<R>, (CallableByContract, CallableByPrecompile)>,
+	PrecompileAt<AddressU64<2049>, ContentPrecompile<R>, (CallableByContract, CallableByPrecompile)>,
+);
+
+pub type KinePrecompiles<R> = PrecompileSetBuilder<R, KinePrecompilesAt<R>>;
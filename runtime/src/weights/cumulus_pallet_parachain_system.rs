@@ -0,0 +1,30 @@
+//! Weights for `cumulus_pallet_parachain_system`
+//!
+//! Not benchmarked on this runtime yet: until then these are the reference values of the
+//! upstream benchmarks of the same pallet version, which do not account for the database or
+//! the hardware of this chain. Replace them with the measured weights with `./scripts/benchmark.sh cumulus_pallet_parachain_system`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `cumulus_pallet_parachain_system`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> cumulus_pallet_parachain_system::WeightInfo for WeightInfo<T> {
+	/// Storage: `ParachainSystem::LastDmqMqcHead` (r:1 w:1)
+	/// Storage: `MessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `MessageQueue::ServiceHead` (r:1 w:1)
+	/// Storage: `ParachainSystem::ProcessedDownwardMessages` (r:0 w:1)
+	/// Storage: `MessageQueue::Pages` (r:0 w:1000)
+	/// The range of component `n` is `[0, 1000]`.
+	fn enqueue_inbound_downward_messages(n: u32) -> Weight {
+		Weight::from_parts(2_220_000, 3517)
+			.saturating_add(Weight::from_parts(23_750_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+}
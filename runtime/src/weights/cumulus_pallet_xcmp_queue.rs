@@ -0,0 +1,66 @@
+//! Weights for `cumulus_pallet_xcmp_queue`
+//!
+//! Not benchmarked on this runtime yet: until then these are the reference values of the
+//! upstream benchmarks of the same pallet version, which do not account for the database or
+//! the hardware of this chain. Replace them with the measured weights with `./scripts/benchmark.sh cumulus_pallet_xcmp_queue`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `cumulus_pallet_xcmp_queue`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> cumulus_pallet_xcmp_queue::WeightInfo for WeightInfo<T> {
+	/// Storage: `XcmpQueue::QueueConfig` (r:1 w:1)
+	fn set_config_with_u32() -> Weight {
+		Weight::from_parts(5_936_000, 1561)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `XcmpQueue::QueueConfig` (r:1 w:0)
+	/// Storage: `MessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `MessageQueue::ServiceHead` (r:1 w:1)
+	/// Storage: `MessageQueue::Pages` (r:0 w:1)
+	fn enqueue_xcmp_message() -> Weight {
+		Weight::from_parts(17_430_000, 5487)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `XcmpQueue::OutboundXcmpStatus` (r:1 w:1)
+	fn suspend_channel() -> Weight {
+		Weight::from_parts(3_475_000, 2767)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `XcmpQueue::OutboundXcmpStatus` (r:1 w:1)
+	fn resume_channel() -> Weight {
+		Weight::from_parts(4_742_000, 2767)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn take_first_concatenated_xcm() -> Weight {
+		Weight::from_parts(6_021_000, 0)
+	}
+	/// Storage: `XcmpQueue::InboundXcmpMessages` (r:1 w:1)
+	/// Storage: `XcmpQueue::QueueConfig` (r:1 w:0)
+	/// Storage: `MessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `MessageQueue::ServiceHead` (r:1 w:1)
+	fn on_idle_good_msg() -> Weight {
+		Weight::from_parts(112_476_000, 69050)
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	/// Storage: `XcmpQueue::InboundXcmpMessages` (r:1 w:1)
+	/// Storage: `XcmpQueue::QueueConfig` (r:1 w:0)
+	/// Storage: `MessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `MessageQueue::ServiceHead` (r:1 w:1)
+	fn on_idle_large_msg() -> Weight {
+		Weight::from_parts(59_313_000, 69050)
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+}
@@ -0,0 +1,7 @@
+//! Weights for `frame_system`
+//!
+//! Not benchmarked on this runtime yet: until then these are the upstream `SubstrateWeight` of
+//! the pallet. `./scripts/benchmark.sh frame_system` replaces this file with the measured weights.
+
+/// Weight functions for `frame_system`.
+pub type WeightInfo<T> = frame_system::weights::SubstrateWeight<T>;
@@ -18,10 +18,29 @@
 //! Expose the auto generated weight files.
 
 pub mod block_weights;
+pub mod cumulus_pallet_parachain_system;
+pub mod cumulus_pallet_xcmp_queue;
 pub mod extrinsic_weights;
+pub mod frame_system;
+pub mod pallet_assets;
+pub mod pallet_balances;
+pub mod pallet_collator_selection;
+pub mod pallet_collective;
+pub mod pallet_contracts;
+pub mod pallet_evm;
+pub mod pallet_message_queue;
+pub mod pallet_preimage;
+pub mod pallet_safe_mode;
+pub mod pallet_scheduler;
+pub mod pallet_session;
+pub mod pallet_sudo;
+pub mod pallet_timestamp;
+pub mod pallet_tx_pause;
+pub mod pallet_xcm;
 pub mod paritydb_weights;
 pub mod rocksdb_weights;
+pub mod xcm;
 
 pub use block_weights::constants::BlockExecutionWeight;
 pub use extrinsic_weights::constants::ExtrinsicBaseWeight;
-pub use rocksdb_weights::constants::RocksDbWeight;
+pub use paritydb_weights::constants::ParityDbWeight;
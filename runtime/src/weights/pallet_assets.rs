@@ -0,0 +1,7 @@
+//! Weights for `pallet_assets`
+//!
+//! Not benchmarked on this runtime yet: until then these are the upstream `SubstrateWeight` of
+//! the pallet. `./scripts/benchmark.sh pallet_assets` replaces this file with the measured weights.
+
+/// Weight functions for `pallet_assets`.
+pub type WeightInfo<T> = pallet_assets::weights::SubstrateWeight<T>;
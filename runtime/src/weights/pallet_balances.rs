@@ -0,0 +1,7 @@
+//! Weights for `pallet_balances`
+//!
+//! Not benchmarked on this runtime yet: until then these are the upstream `SubstrateWeight` of
+//! the pallet. `./scripts/benchmark.sh pallet_balances` replaces this file with the measured weights.
+
+/// Weight functions for `pallet_balances`.
+pub type WeightInfo<T> = pallet_balances::weights::SubstrateWeight<T>;
@@ -0,0 +1,146 @@
+//! Weights for `pallet_collator_selection`
+//!
+//! Not benchmarked on this runtime yet: until then these are the reference values of the
+//! upstream benchmarks of the same pallet version, which do not account for the database or
+//! the hardware of this chain. Replace them with the measured weights with `./scripts/benchmark.sh pallet_collator_selection`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_collator_selection`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_collator_selection::WeightInfo for WeightInfo<T> {
+	/// Storage: `Session::NextKeys` (r:20 w:0)
+	/// Storage: `CollatorSelection::Invulnerables` (r:0 w:1)
+	/// The range of component `b` is `[0, 20]`.
+	fn set_invulnerables(b: u32) -> Weight {
+		Weight::from_parts(11_482_000, 1194)
+			.saturating_add(Weight::from_parts(3_792_000, 0).saturating_mul(b.into()))
+			.saturating_add(Weight::from_parts(0, 2554).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:1)
+	/// Storage: `Session::NextKeys` (r:1 w:0)
+	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// The range of component `b` is `[0, 19]`.
+	/// The range of component `c` is `[0, 99]`.
+	fn add_invulnerable(b: u32, c: u32) -> Weight {
+		Weight::from_parts(47_266_000, 6287)
+			.saturating_add(Weight::from_parts(84_000, 0).saturating_mul(b.into()))
+			.saturating_add(Weight::from_parts(0, 37).saturating_mul(b.into()))
+			.saturating_add(Weight::from_parts(145_000, 0).saturating_mul(c.into()))
+			.saturating_add(Weight::from_parts(0, 53).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:1)
+	/// The range of component `b` is `[0, 20]`.
+	fn remove_invulnerable(b: u32) -> Weight {
+		Weight::from_parts(14_107_000, 6287)
+			.saturating_add(Weight::from_parts(71_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `CollatorSelection::DesiredCandidates` (r:0 w:1)
+	fn set_desired_candidates() -> Weight {
+		Weight::from_parts(5_877_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `CollatorSelection::CandidacyBond` (r:1 w:1)
+	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
+	/// Storage: `System::Account` (r:100 w:100)
+	/// The range of component `c` is `[0, 100]`.
+	/// The range of component `k` is `[0, 100]`.
+	fn set_candidacy_bond(c: u32, k: u32) -> Weight {
+		Weight::from_parts(11_810_000, 0)
+			.saturating_add(Weight::from_parts(123_000, 0).saturating_mul(c.into()))
+			.saturating_add(Weight::from_parts(0, 2602).saturating_mul(c.into()))
+			.saturating_add(Weight::from_parts(119_000, 0).saturating_mul(k.into()))
+			.saturating_add(Weight::from_parts(0, 2635).saturating_mul(k.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(c.into())))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(k.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(c.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(k.into())))
+	}
+	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
+	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:0)
+	/// Storage: `Session::NextKeys` (r:1 w:0)
+	/// Storage: `CollatorSelection::CandidacyBond` (r:1 w:0)
+	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:0 w:1)
+	/// The range of component `c` is `[0, 99]`.
+	fn register_as_candidate(c: u32) -> Weight {
+		Weight::from_parts(45_126_000, 6287)
+			.saturating_add(Weight::from_parts(214_000, 0).saturating_mul(c.into()))
+			.saturating_add(Weight::from_parts(0, 49).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
+	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:0)
+	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:0 w:1)
+	/// The range of component `c` is `[0, 100]`.
+	fn leave_intent(c: u32) -> Weight {
+		Weight::from_parts(36_094_000, 6287)
+			.saturating_add(Weight::from_parts(147_000, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `CollatorSelection::CandidacyBond` (r:1 w:0)
+	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// The range of component `c` is `[0, 100]`.
+	fn update_bond(c: u32) -> Weight {
+		Weight::from_parts(40_071_000, 6287)
+			.saturating_add(Weight::from_parts(151_000, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `CollatorSelection::CandidacyBond` (r:1 w:0)
+	/// Storage: `CollatorSelection::CandidateList` (r:1 w:1)
+	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:0)
+	/// Storage: `Session::NextKeys` (r:1 w:0)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:0 w:2)
+	/// The range of component `c` is `[0, 100]`.
+	fn take_candidate_slot(c: u32) -> Weight {
+		Weight::from_parts(62_310_000, 6287)
+			.saturating_add(Weight::from_parts(232_000, 0).saturating_mul(c.into()))
+			.saturating_add(Weight::from_parts(0, 49).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:0 w:1)
+	/// Storage: `System::BlockWeight` (r:1 w:1)
+	fn note_author() -> Weight {
+		Weight::from_parts(39_481_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	/// Storage: `CollatorSelection::CandidateList` (r:1 w:0)
+	/// Storage: `CollatorSelection::LastAuthoredBlock` (r:100 w:0)
+	/// Storage: `CollatorSelection::Invulnerables` (r:1 w:0)
+	/// Storage: `CollatorSelection::DesiredCandidates` (r:1 w:0)
+	/// Storage: `System::Account` (r:97 w:98)
+	/// The range of component `r` is `[0, 100]`.
+	/// The range of component `c` is `[0, 100]`.
+	fn new_session(_r: u32, c: u32) -> Weight {
+		Weight::from_parts(20_338_000, 6287)
+			.saturating_add(Weight::from_parts(13_817_000, 0).saturating_mul(c.into()))
+			.saturating_add(Weight::from_parts(0, 2602).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(c.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(c.into())))
+	}
+}
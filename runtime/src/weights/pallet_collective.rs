@@ -0,0 +1,164 @@
+//! Weights for `pallet_collective`
+//!
+//! Not benchmarked on this runtime yet: until then these are the reference values of the
+//! upstream benchmarks of the same pallet version, which do not account for the database or
+//! the hardware of this chain. Replace them with the measured weights with `./scripts/benchmark.sh pallet_collective`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_collective`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_collective::WeightInfo for WeightInfo<T> {
+	/// Storage: `TechnicalCommittee::Members` (r:1 w:1)
+	/// Storage: `TechnicalCommittee::Proposals` (r:1 w:0)
+	/// Storage: `TechnicalCommittee::Voting` (r:100 w:100)
+	/// Storage: `TechnicalCommittee::Prime` (r:0 w:1)
+	/// The range of component `m` is `[0, 100]`.
+	/// The range of component `n` is `[0, 100]`.
+	/// The range of component `p` is `[0, 100]`.
+	fn set_members(m: u32, _n: u32, p: u32, ) -> Weight {
+		Weight::from_parts(15_790_000, 15894)
+			.saturating_add(Weight::from_parts(3_452_836, 0).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(6_418_298, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 7809).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(0, 4664).saturating_mul(p.into()))
+	}
+	/// Storage: `TechnicalCommittee::Members` (r:1 w:0)
+	/// The range of component `b` is `[2, 1024]`.
+	/// The range of component `m` is `[1, 100]`.
+	fn execute(b: u32, m: u32, ) -> Weight {
+		Weight::from_parts(15_284_000, 1518)
+			.saturating_add(Weight::from_parts(1_573, 0).saturating_mul(b.into()))
+			.saturating_add(Weight::from_parts(14_830, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(Weight::from_parts(0, 32).saturating_mul(m.into()))
+	}
+	/// Storage: `TechnicalCommittee::Members` (r:1 w:0)
+	/// Storage: `TechnicalCommittee::ProposalOf` (r:1 w:0)
+	/// The range of component `b` is `[2, 1024]`.
+	/// The range of component `m` is `[1, 100]`.
+	fn propose_execute(b: u32, m: u32, ) -> Weight {
+		Weight::from_parts(17_676_000, 3498)
+			.saturating_add(Weight::from_parts(1_526, 0).saturating_mul(b.into()))
+			.saturating_add(Weight::from_parts(24_237, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(Weight::from_parts(0, 32).saturating_mul(m.into()))
+	}
+	/// Storage: `TechnicalCommittee::Members` (r:1 w:0)
+	/// Storage: `TechnicalCommittee::ProposalOf` (r:1 w:1)
+	/// Storage: `TechnicalCommittee::Proposals` (r:1 w:1)
+	/// Storage: `TechnicalCommittee::ProposalCount` (r:1 w:1)
+	/// Storage: `TechnicalCommittee::Voting` (r:0 w:1)
+	/// The range of component `b` is `[2, 1024]`.
+	/// The range of component `m` is `[2, 100]`.
+	/// The range of component `p` is `[1, 100]`.
+	fn propose_proposed(b: u32, m: u32, p: u32, ) -> Weight {
+		Weight::from_parts(23_264_000, 3953)
+			.saturating_add(Weight::from_parts(3_576, 0).saturating_mul(b.into()))
+			.saturating_add(Weight::from_parts(25_120, 0).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(243_811, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
+			.saturating_add(Weight::from_parts(0, 33).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(0, 36).saturating_mul(p.into()))
+	}
+	/// Storage: `TechnicalCommittee::Members` (r:1 w:0)
+	/// Storage: `TechnicalCommittee::Voting` (r:1 w:1)
+	/// The range of component `m` is `[5, 100]`.
+	fn vote(m: u32, ) -> Weight {
+		Weight::from_parts(29_074_000, 4995)
+			.saturating_add(Weight::from_parts(43_014, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(Weight::from_parts(0, 64).saturating_mul(m.into()))
+	}
+	/// Storage: `TechnicalCommittee::Voting` (r:1 w:1)
+	/// Storage: `TechnicalCommittee::Members` (r:1 w:0)
+	/// Storage: `TechnicalCommittee::Proposals` (r:1 w:1)
+	/// Storage: `TechnicalCommittee::ProposalOf` (r:0 w:1)
+	/// The range of component `m` is `[4, 100]`.
+	/// The range of component `p` is `[1, 100]`.
+	fn close_early_disapproved(m: u32, p: u32, ) -> Weight {
+		Weight::from_parts(30_566_000, 4187)
+			.saturating_add(Weight::from_parts(32_541, 0).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(207_117, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+			.saturating_add(Weight::from_parts(0, 65).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(0, 36).saturating_mul(p.into()))
+	}
+	/// Storage: `TechnicalCommittee::Voting` (r:1 w:1)
+	/// Storage: `TechnicalCommittee::Members` (r:1 w:0)
+	/// Storage: `TechnicalCommittee::ProposalOf` (r:1 w:1)
+	/// Storage: `TechnicalCommittee::Proposals` (r:1 w:1)
+	/// The range of component `b` is `[2, 1024]`.
+	/// The range of component `m` is `[4, 100]`.
+	/// The range of component `p` is `[1, 100]`.
+	fn close_early_approved(b: u32, m: u32, p: u32, ) -> Weight {
+		Weight::from_parts(42_998_000, 4401)
+			.saturating_add(Weight::from_parts(3_302, 0).saturating_mul(b.into()))
+			.saturating_add(Weight::from_parts(34_407, 0).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(232_105, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(3))
+			.saturating_add(Weight::from_parts(0, 1).saturating_mul(b.into()))
+			.saturating_add(Weight::from_parts(0, 66).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(0, 40).saturating_mul(p.into()))
+	}
+	/// Storage: `TechnicalCommittee::Voting` (r:1 w:1)
+	/// Storage: `TechnicalCommittee::Members` (r:1 w:0)
+	/// Storage: `TechnicalCommittee::Prime` (r:1 w:0)
+	/// Storage: `TechnicalCommittee::Proposals` (r:1 w:1)
+	/// Storage: `TechnicalCommittee::ProposalOf` (r:0 w:1)
+	/// The range of component `m` is `[4, 100]`.
+	/// The range of component `p` is `[1, 100]`.
+	fn close_disapproved(m: u32, p: u32, ) -> Weight {
+		Weight::from_parts(33_040_000, 4206)
+			.saturating_add(Weight::from_parts(38_010, 0).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(208_512, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(3))
+			.saturating_add(Weight::from_parts(0, 65).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(0, 36).saturating_mul(p.into()))
+	}
+	/// Storage: `TechnicalCommittee::Voting` (r:1 w:1)
+	/// Storage: `TechnicalCommittee::Members` (r:1 w:0)
+	/// Storage: `TechnicalCommittee::Prime` (r:1 w:0)
+	/// Storage: `TechnicalCommittee::ProposalOf` (r:1 w:1)
+	/// Storage: `TechnicalCommittee::Proposals` (r:1 w:1)
+	/// The range of component `b` is `[2, 1024]`.
+	/// The range of component `m` is `[4, 100]`.
+	/// The range of component `p` is `[1, 100]`.
+	fn close_approved(b: u32, m: u32, p: u32, ) -> Weight {
+		Weight::from_parts(45_515_000, 4420)
+			.saturating_add(Weight::from_parts(3_260, 0).saturating_mul(b.into()))
+			.saturating_add(Weight::from_parts(36_224, 0).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(235_088, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(3))
+			.saturating_add(Weight::from_parts(0, 1).saturating_mul(b.into()))
+			.saturating_add(Weight::from_parts(0, 66).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(0, 40).saturating_mul(p.into()))
+	}
+	/// Storage: `TechnicalCommittee::Proposals` (r:1 w:1)
+	/// Storage: `TechnicalCommittee::Voting` (r:0 w:1)
+	/// Storage: `TechnicalCommittee::ProposalOf` (r:0 w:1)
+	/// The range of component `p` is `[1, 100]`.
+	fn disapprove_proposal(p: u32, ) -> Weight {
+		Weight::from_parts(17_193_000, 1711)
+			.saturating_add(Weight::from_parts(193_681, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(3))
+			.saturating_add(Weight::from_parts(0, 32).saturating_mul(p.into()))
+	}
+}
@@ -0,0 +1,7 @@
+//! Weights for `pallet_contracts`
+//!
+//! Not benchmarked on this runtime yet: until then these are the upstream `SubstrateWeight` of
+//! the pallet. `./scripts/benchmark.sh pallet_contracts` replaces this file with the measured weights.
+
+/// Weight functions for `pallet_contracts`.
+pub type WeightInfo<T> = pallet_contracts::weights::SubstrateWeight<T>;
@@ -0,0 +1,7 @@
+//! Weights for `pallet_evm`
+//!
+//! Not benchmarked on this runtime yet: until then these are the upstream `SubstrateWeight` of
+//! the pallet. `./scripts/benchmark.sh pallet_evm` replaces this file with the measured weights.
+
+/// Weight functions for `pallet_evm`.
+pub type WeightInfo<T> = pallet_evm::weights::SubstrateWeight<T>;
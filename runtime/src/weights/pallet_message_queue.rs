@@ -0,0 +1,81 @@
+//! Weights for `pallet_message_queue`
+//!
+//! Not benchmarked on this runtime yet: until then these are the reference values of the
+//! upstream benchmarks of the same pallet version, which do not account for the database or
+//! the hardware of this chain. Replace them with the measured weights with `./scripts/benchmark.sh pallet_message_queue`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_message_queue`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_message_queue::WeightInfo for WeightInfo<T> {
+	/// Storage: `MessageQueue::ServiceHead` (r:1 w:0)
+	/// Storage: `MessageQueue::BookStateFor` (r:2 w:2)
+	fn ready_ring_knit() -> Weight {
+		Weight::from_parts(10_781_000, 6212)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `MessageQueue::BookStateFor` (r:2 w:2)
+	/// Storage: `MessageQueue::ServiceHead` (r:1 w:1)
+	fn ready_ring_unknit() -> Weight {
+		Weight::from_parts(9_686_000, 6212)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `MessageQueue::BookStateFor` (r:1 w:1)
+	fn service_queue_base() -> Weight {
+		Weight::from_parts(3_441_000, 3517)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `MessageQueue::Pages` (r:1 w:1)
+	fn service_page_base_completion() -> Weight {
+		Weight::from_parts(5_543_000, 69050)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `MessageQueue::Pages` (r:1 w:1)
+	fn service_page_base_no_completion() -> Weight {
+		Weight::from_parts(5_768_000, 69050)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn service_page_item() -> Weight {
+		Weight::from_parts(163_321_000, 0)
+	}
+	/// Storage: `MessageQueue::ServiceHead` (r:1 w:1)
+	/// Storage: `MessageQueue::BookStateFor` (r:1 w:0)
+	fn bump_service_head() -> Weight {
+		Weight::from_parts(6_163_000, 3517)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `MessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `MessageQueue::Pages` (r:1 w:1)
+	fn reap_page() -> Weight {
+		Weight::from_parts(53_014_000, 69050)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `MessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `MessageQueue::Pages` (r:1 w:1)
+	fn execute_overweight_page_removed() -> Weight {
+		Weight::from_parts(69_917_000, 69050)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `MessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `MessageQueue::Pages` (r:1 w:1)
+	fn execute_overweight_page_updated() -> Weight {
+		Weight::from_parts(80_449_000, 69050)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+}
@@ -0,0 +1,127 @@
+//! Weights for `pallet_preimage`
+//!
+//! Not benchmarked on this runtime yet: until then these are the reference values of the
+//! upstream benchmarks of the same pallet version, which do not account for the database or
+//! the hardware of this chain. Replace them with the measured weights with `./scripts/benchmark.sh pallet_preimage`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_preimage`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_preimage::WeightInfo for WeightInfo<T> {
+	/// Storage: `Preimage::StatusFor` (r:1 w:0)
+	/// Storage: `Preimage::RequestStatusFor` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `Preimage::PreimageFor` (r:0 w:1)
+	/// The range of component `s` is `[0, 4194304]`.
+	fn note_preimage(s: u32, ) -> Weight {
+		Weight::from_parts(42_010_000, 3556)
+			.saturating_add(Weight::from_parts(1_748, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `Preimage::StatusFor` (r:1 w:0)
+	/// Storage: `Preimage::RequestStatusFor` (r:1 w:1)
+	/// Storage: `Preimage::PreimageFor` (r:0 w:1)
+	/// The range of component `s` is `[0, 4194304]`.
+	fn note_requested_preimage(s: u32, ) -> Weight {
+		Weight::from_parts(13_074_000, 3556)
+			.saturating_add(Weight::from_parts(1_749, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Preimage::StatusFor` (r:1 w:0)
+	/// Storage: `Preimage::RequestStatusFor` (r:1 w:1)
+	/// Storage: `Preimage::PreimageFor` (r:0 w:1)
+	/// The range of component `s` is `[0, 4194304]`.
+	fn note_no_deposit_preimage(s: u32, ) -> Weight {
+		Weight::from_parts(12_711_000, 3556)
+			.saturating_add(Weight::from_parts(1_749, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Preimage::StatusFor` (r:1 w:0)
+	/// Storage: `Preimage::RequestStatusFor` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Storage: `Preimage::PreimageFor` (r:0 w:1)
+	fn unnote_preimage() -> Weight {
+		Weight::from_parts(53_498_000, 3556)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `Preimage::StatusFor` (r:1 w:0)
+	/// Storage: `Preimage::RequestStatusFor` (r:1 w:1)
+	/// Storage: `Preimage::PreimageFor` (r:0 w:1)
+	fn unnote_no_deposit_preimage() -> Weight {
+		Weight::from_parts(27_617_000, 3556)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Preimage::StatusFor` (r:1 w:0)
+	/// Storage: `Preimage::RequestStatusFor` (r:1 w:1)
+	fn request_preimage() -> Weight {
+		Weight::from_parts(25_014_000, 3556)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Preimage::StatusFor` (r:1 w:0)
+	/// Storage: `Preimage::RequestStatusFor` (r:1 w:1)
+	fn request_no_deposit_preimage() -> Weight {
+		Weight::from_parts(16_023_000, 3556)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Preimage::StatusFor` (r:1 w:0)
+	/// Storage: `Preimage::RequestStatusFor` (r:1 w:1)
+	fn request_unnoted_preimage() -> Weight {
+		Weight::from_parts(13_972_000, 3556)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Preimage::StatusFor` (r:1 w:0)
+	/// Storage: `Preimage::RequestStatusFor` (r:1 w:1)
+	fn request_requested_preimage() -> Weight {
+		Weight::from_parts(10_148_000, 3556)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Preimage::StatusFor` (r:1 w:0)
+	/// Storage: `Preimage::RequestStatusFor` (r:1 w:1)
+	/// Storage: `Preimage::PreimageFor` (r:0 w:1)
+	fn unrequest_preimage() -> Weight {
+		Weight::from_parts(24_239_000, 3556)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `Preimage::StatusFor` (r:1 w:0)
+	/// Storage: `Preimage::RequestStatusFor` (r:1 w:1)
+	fn unrequest_unnoted_preimage() -> Weight {
+		Weight::from_parts(10_009_000, 3556)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Preimage::StatusFor` (r:1 w:0)
+	/// Storage: `Preimage::RequestStatusFor` (r:1 w:1)
+	fn unrequest_multi_referenced_preimage() -> Weight {
+		Weight::from_parts(9_982_000, 3556)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `Preimage::StatusFor` (r:1023 w:1023)
+	/// Storage: `System::Account` (r:1023 w:1023)
+	/// Storage: `Preimage::RequestStatusFor` (r:0 w:1023)
+	/// The range of component `n` is `[1, 1024]`.
+	fn ensure_updated(n: u32, ) -> Weight {
+		Weight::from_parts(0, 990)
+			.saturating_add(Weight::from_parts(58_913_520, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(n.into()))
+	}
+}
@@ -0,0 +1,85 @@
+//! Weights for `pallet_safe_mode`
+//!
+//! Not benchmarked on this runtime yet: until then these are the reference values of the
+//! upstream benchmarks of the same pallet version, which do not account for the database or
+//! the hardware of this chain. Replace them with the measured weights with `./scripts/benchmark.sh pallet_safe_mode`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_safe_mode`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_safe_mode::WeightInfo for WeightInfo<T> {
+	/// Storage: `SafeMode::EnteredUntil` (r:1 w:0)
+	fn on_initialize_noop() -> Weight {
+		Weight::from_parts(2_500_000, 1489)
+			.saturating_add(T::DbWeight::get().reads(1))
+	}
+	/// Storage: `SafeMode::EnteredUntil` (r:1 w:1)
+	fn on_initialize_exit() -> Weight {
+		Weight::from_parts(8_697_000, 1489)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `SafeMode::EnteredUntil` (r:1 w:1)
+	/// Storage: `SafeMode::Deposits` (r:0 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	fn enter() -> Weight {
+		Weight::from_parts(50_040_000, 3550)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `SafeMode::EnteredUntil` (r:1 w:1)
+	fn force_enter() -> Weight {
+		Weight::from_parts(10_068_000, 1489)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `SafeMode::EnteredUntil` (r:1 w:1)
+	/// Storage: `SafeMode::Deposits` (r:0 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	fn extend() -> Weight {
+		Weight::from_parts(50_923_000, 3550)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `SafeMode::EnteredUntil` (r:1 w:1)
+	fn force_extend() -> Weight {
+		Weight::from_parts(10_364_000, 1489)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `SafeMode::EnteredUntil` (r:1 w:1)
+	fn force_exit() -> Weight {
+		Weight::from_parts(10_349_000, 1489)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `SafeMode::Deposits` (r:1 w:1)
+	/// Storage: `SafeMode::EnteredUntil` (r:1 w:0)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	fn release_deposit() -> Weight {
+		Weight::from_parts(48_105_000, 3550)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `SafeMode::Deposits` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	fn force_release_deposit() -> Weight {
+		Weight::from_parts(44_908_000, 3550)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `SafeMode::Deposits` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	fn force_slash_deposit() -> Weight {
+		Weight::from_parts(52_336_000, 3550)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+}
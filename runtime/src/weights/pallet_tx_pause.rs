@@ -0,0 +1,30 @@
+//! Weights for `pallet_tx_pause`
+//!
+//! Not benchmarked on this runtime yet: until then these are the reference values of the
+//! upstream benchmarks of the same pallet version, which do not account for the database or
+//! the hardware of this chain. Replace them with the measured weights with `./scripts/benchmark.sh pallet_tx_pause`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_tx_pause`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_tx_pause::WeightInfo for WeightInfo<T> {
+	/// Storage: `TxPause::PausedCalls` (r:1 w:1)
+	fn pause() -> Weight {
+		Weight::from_parts(15_096_000, 3997)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `TxPause::PausedCalls` (r:1 w:1)
+	fn unpause() -> Weight {
+		Weight::from_parts(21_268_000, 3997)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+}
@@ -0,0 +1,130 @@
+//! Weights for `pallet_xcm`
+//!
+//! Not benchmarked on this runtime yet: until then these are the reference values of the
+//! upstream benchmarks of the same pallet version, which do not account for the database or
+//! the hardware of this chain. Replace them with the measured weights with `./scripts/benchmark.sh pallet_xcm`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_xcm`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_xcm::WeightInfo for WeightInfo<T> {
+	/// Storage: `ParachainSystem::UpwardDeliveryFeeFactor` (r:1 w:0)
+	/// Storage: `PolkadotXcm::SupportedVersion` (r:1 w:0)
+	/// Storage: `ParachainSystem::HostConfiguration` (r:1 w:0)
+	/// Storage: `ParachainSystem::PendingUpwardMessages` (r:1 w:1)
+	fn send() -> Weight {
+		Weight::from_parts(25_163_000, 3610)
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `ParachainInfo::ParachainId` (r:1 w:0)
+	fn teleport_assets() -> Weight {
+		Weight::from_parts(21_457_000, 1489)
+			.saturating_add(T::DbWeight::get().reads(1))
+	}
+	fn reserve_transfer_assets() -> Weight {
+		Weight::from_parts(18_446_744_073_709_551_000, 0)
+	}
+	fn transfer_assets() -> Weight {
+		Weight::from_parts(18_446_744_073_709_551_000, 0)
+	}
+	fn execute() -> Weight {
+		Weight::from_parts(18_446_744_073_709_551_000, 0)
+	}
+	/// Storage: `PolkadotXcm::SupportedVersion` (r:0 w:1)
+	fn force_xcm_version() -> Weight {
+		Weight::from_parts(8_542_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `PolkadotXcm::SafeXcmVersion` (r:0 w:1)
+	fn force_default_xcm_version() -> Weight {
+		Weight::from_parts(2_560_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `PolkadotXcm::VersionNotifiers` (r:1 w:1)
+	/// Storage: `PolkadotXcm::QueryCounter` (r:1 w:1)
+	/// Storage: `PolkadotXcm::SupportedVersion` (r:1 w:0)
+	/// Storage: `ParachainSystem::PendingUpwardMessages` (r:1 w:1)
+	fn force_subscribe_version_notify() -> Weight {
+		Weight::from_parts(29_852_000, 3610)
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	/// Storage: `PolkadotXcm::VersionNotifiers` (r:1 w:1)
+	/// Storage: `PolkadotXcm::SupportedVersion` (r:1 w:0)
+	/// Storage: `ParachainSystem::PendingUpwardMessages` (r:1 w:1)
+	fn force_unsubscribe_version_notify() -> Weight {
+		Weight::from_parts(31_294_000, 3834)
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	/// Storage: `PolkadotXcm::XcmExecutionSuspended` (r:0 w:1)
+	fn force_suspension() -> Weight {
+		Weight::from_parts(2_437_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `PolkadotXcm::SupportedVersion` (r:4 w:2)
+	fn migrate_supported_version() -> Weight {
+		Weight::from_parts(15_976_000, 13387)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `PolkadotXcm::VersionNotifiers` (r:4 w:2)
+	fn migrate_version_notifiers() -> Weight {
+		Weight::from_parts(16_207_000, 13391)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `PolkadotXcm::VersionNotifyTargets` (r:5 w:0)
+	fn already_notified_target() -> Weight {
+		Weight::from_parts(17_351_000, 15880)
+			.saturating_add(T::DbWeight::get().reads(6))
+	}
+	/// Storage: `PolkadotXcm::VersionNotifyTargets` (r:2 w:1)
+	/// Storage: `PolkadotXcm::SupportedVersion` (r:1 w:0)
+	/// Storage: `ParachainSystem::PendingUpwardMessages` (r:1 w:1)
+	fn notify_current_targets() -> Weight {
+		Weight::from_parts(28_019_000, 6046)
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: `PolkadotXcm::VersionNotifyTargets` (r:3 w:0)
+	fn notify_target_migration_fail() -> Weight {
+		Weight::from_parts(8_959_000, 10959)
+			.saturating_add(T::DbWeight::get().reads(3))
+	}
+	/// Storage: `PolkadotXcm::VersionNotifyTargets` (r:4 w:2)
+	fn migrate_version_notify_targets() -> Weight {
+		Weight::from_parts(16_404_000, 13405)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `PolkadotXcm::VersionNotifyTargets` (r:4 w:2)
+	/// Storage: `PolkadotXcm::SupportedVersion` (r:1 w:0)
+	/// Storage: `ParachainSystem::PendingUpwardMessages` (r:1 w:1)
+	fn migrate_and_notify_old_targets() -> Weight {
+		Weight::from_parts(35_046_000, 13471)
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	/// Storage: `PolkadotXcm::QueryCounter` (r:1 w:1)
+	/// Storage: `PolkadotXcm::Queries` (r:0 w:1)
+	fn new_query() -> Weight {
+		Weight::from_parts(3_002_000, 1485)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `PolkadotXcm::Queries` (r:1 w:1)
+	fn take_response() -> Weight {
+		Weight::from_parts(19_139_000, 3555)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+}
@@ -22,8 +22,8 @@ pub mod constants {
 	};
 
 	parameter_types! {
-		/// `ParityDB` can be enabled with a feature flag, but is still experimental. These weights
-		/// are available for brave runtime engineers who may want to try this out as default.
+		/// `ParityDB` is the default database of the node, so this will be the weight used
+		/// throughout the runtime.
 		pub const ParityDbWeight: RuntimeDbWeight = RuntimeDbWeight {
 			read: 8_000 * constants::WEIGHT_REF_TIME_PER_NANOS,
 			write: 50_000 * constants::WEIGHT_REF_TIME_PER_NANOS,
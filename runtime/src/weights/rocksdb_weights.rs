@@ -22,8 +22,8 @@ pub mod constants {
 	};
 
 	parameter_types! {
-		/// By default, Substrate uses `RocksDB`, so this will be the weight used throughout
-		/// the runtime.
+		/// By default, Substrate uses `RocksDB`. The node runs on `ParityDB` instead, so these
+		/// weights are only kept for reference.
 		pub const RocksDbWeight: RuntimeDbWeight = RuntimeDbWeight {
 			read: 25_000 * constants::WEIGHT_REF_TIME_PER_NANOS,
 			write: 100_000 * constants::WEIGHT_REF_TIME_PER_NANOS,
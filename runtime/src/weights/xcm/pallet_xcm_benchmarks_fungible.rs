@@ -0,0 +1,59 @@
+//! Weights for `pallet_xcm_benchmarks::fungible`
+//!
+//! Not benchmarked on this runtime yet: until then these are the reference values of the
+//! upstream benchmarks of the same pallet version, which do not account for the database or
+//! the hardware of this chain. Replace them with the measured weights with `./scripts/benchmark.sh pallet_xcm_benchmarks::fungible`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_xcm_benchmarks::fungible`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo<T> {
+	pub(crate) fn withdraw_asset() -> Weight {
+		Weight::from_parts(26_312_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	pub(crate) fn transfer_asset() -> Weight {
+		Weight::from_parts(52_221_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	pub(crate) fn transfer_reserve_asset() -> Weight {
+		Weight::from_parts(77_380_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	pub(crate) fn reserve_asset_deposited() -> Weight {
+		Weight::from_parts(18_446_744_073_709_551_000, 0)
+	}
+	pub(crate) fn initiate_reserve_withdraw() -> Weight {
+		Weight::from_parts(474_801_000, 3610)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	pub(crate) fn receive_teleported_asset() -> Weight {
+		Weight::from_parts(3_539_000, 0)
+	}
+	pub(crate) fn deposit_asset() -> Weight {
+		Weight::from_parts(18_486_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	pub(crate) fn deposit_reserve_asset() -> Weight {
+		Weight::from_parts(54_118_000, 3610)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	pub(crate) fn initiate_teleport() -> Weight {
+		Weight::from_parts(33_201_000, 3610)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+}
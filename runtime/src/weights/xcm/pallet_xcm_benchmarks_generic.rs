@@ -0,0 +1,125 @@
+//! Weights for `pallet_xcm_benchmarks::generic`
+//!
+//! Not benchmarked on this runtime yet: until then these are the reference values of the
+//! upstream benchmarks of the same pallet version, which do not account for the database or
+//! the hardware of this chain. Replace them with the measured weights with `./scripts/benchmark.sh pallet_xcm_benchmarks::generic`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_xcm_benchmarks::generic`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo<T> {
+	pub(crate) fn report_holding() -> Weight {
+		Weight::from_parts(449_237_000, 3610)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	pub(crate) fn buy_execution() -> Weight {
+		Weight::from_parts(4_156_000, 0)
+	}
+	pub(crate) fn query_response() -> Weight {
+		Weight::from_parts(10_599_000, 3568)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	pub(crate) fn transact() -> Weight {
+		Weight::from_parts(10_853_000, 0)
+	}
+	pub(crate) fn refund_surplus() -> Weight {
+		Weight::from_parts(4_349_000, 0)
+	}
+	pub(crate) fn set_error_handler() -> Weight {
+		Weight::from_parts(3_223_000, 0)
+	}
+	pub(crate) fn set_appendix() -> Weight {
+		Weight::from_parts(3_094_000, 0)
+	}
+	pub(crate) fn clear_error() -> Weight {
+		Weight::from_parts(3_090_000, 0)
+	}
+	pub(crate) fn descend_origin() -> Weight {
+		Weight::from_parts(3_849_000, 0)
+	}
+	pub(crate) fn clear_origin() -> Weight {
+		Weight::from_parts(3_066_000, 0)
+	}
+	pub(crate) fn report_error() -> Weight {
+		Weight::from_parts(28_542_000, 3610)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	pub(crate) fn claim_asset() -> Weight {
+		Weight::from_parts(15_164_000, 3568)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	pub(crate) fn trap() -> Weight {
+		Weight::from_parts(3_111_000, 0)
+	}
+	pub(crate) fn subscribe_version() -> Weight {
+		Weight::from_parts(24_847_000, 3610)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	pub(crate) fn unsubscribe_version() -> Weight {
+		Weight::from_parts(5_040_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	pub(crate) fn initiate_reserve_withdraw() -> Weight {
+		Weight::from_parts(546_836_000, 3610)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	pub(crate) fn burn_asset() -> Weight {
+		Weight::from_parts(134_201_000, 0)
+	}
+	pub(crate) fn expect_asset() -> Weight {
+		Weight::from_parts(13_693_000, 0)
+	}
+	pub(crate) fn expect_origin() -> Weight {
+		Weight::from_parts(3_137_000, 0)
+	}
+	pub(crate) fn expect_error() -> Weight {
+		Weight::from_parts(3_067_000, 0)
+	}
+	pub(crate) fn expect_transact_status() -> Weight {
+		Weight::from_parts(3_270_000, 0)
+	}
+	pub(crate) fn query_pallet() -> Weight {
+		Weight::from_parts(32_012_000, 3610)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	pub(crate) fn expect_pallet() -> Weight {
+		Weight::from_parts(5_520_000, 0)
+	}
+	pub(crate) fn report_transact_status() -> Weight {
+		Weight::from_parts(28_348_000, 3610)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	pub(crate) fn clear_transact_status() -> Weight {
+		Weight::from_parts(3_156_000, 0)
+	}
+	pub(crate) fn set_topic() -> Weight {
+		Weight::from_parts(3_051_000, 0)
+	}
+	pub(crate) fn clear_topic() -> Weight {
+		Weight::from_parts(3_041_000, 0)
+	}
+	pub(crate) fn set_fees_mode() -> Weight {
+		Weight::from_parts(3_055_000, 0)
+	}
+	pub(crate) fn unpaid_execution() -> Weight {
+		Weight::from_parts(3_126_000, 0)
+	}
+	pub(crate) fn universal_origin() -> Weight {
+		Weight::from_parts(3_139_000, 0)
+	}
+}
@@ -1,27 +1,31 @@
 use super::{
 	AccountId, AllPalletsWithSystem, Balances, ParachainInfo, ParachainSystem, PolkadotXcm,
-	Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, WeightToFee, XcmpQueue,
+	Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, TransactionByteFee, TreasuryAccount,
+	WeightToFee, XcmpQueue, MILLIUNIT,
 };
+use cumulus_primitives_core::ParaId;
 use frame_support::{
 	match_types, parameter_types,
-	traits::{ConstU32, Everything, Nothing},
+	traits::{ConstU32, Equals, Everything, Nothing},
 	weights::Weight,
 };
 use frame_system::EnsureRoot;
 use pallet_xcm::XcmPassthrough;
 use polkadot_parachain_primitives::primitives::Sibling;
-use polkadot_runtime_common::impls::ToAuthor;
+use polkadot_runtime_common::{impls::ToAuthor, xcm_sender::ExponentialPrice};
 use xcm::latest::prelude::*;
 #[allow(deprecated)]
 use xcm_builder::CurrencyAdapter;
 use xcm_builder::{
 	AccountId32Aliases, AllowExplicitUnpaidExecutionFrom, AllowTopLevelPaidExecutionFrom,
-	DenyReserveTransferToRelayChain, DenyThenTry, EnsureXcmOrigin, FixedWeightBounds, IsConcrete,
+	DenyReserveTransferToRelayChain, DenyThenTry, EnsureXcmOrigin, IsConcrete,
 	NativeAsset, ParentIsPreset, RelayChainAsNative, SiblingParachainAsNative,
 	SiblingParachainConvertsVia, SignedAccountId32AsNative, SignedToAccountId32,
 	SovereignSignedViaLocation, TakeWeightCredit, TrailingSetTopicAsId, UsingComponents,
-	WithComputedOrigin, WithUniqueTopic,
+	WeightInfoBounds, WithComputedOrigin, WithUniqueTopic, XcmFeeManagerFromComponents,
+	XcmFeeToAccount,
 };
+use sp_std::prelude::*;
 use xcm_executor::XcmExecutor;
 
 parameter_types! {
@@ -79,9 +83,11 @@ pub type XcmOriginToTransactDispatchOrigin = (
 	XcmPassthrough<RuntimeOrigin>,
 );
 
+/// Weighs XCM programs with the benchmarked weight of each instruction.
+pub type XcmWeigher =
+	WeightInfoBounds<crate::weights::xcm::KineXcmWeight<RuntimeCall>, RuntimeCall, MaxInstructions>;
+
 parameter_types! {
-	// One XCM operation is 1_000_000_000 weight - almost certainly a conservative estimate.
-	pub UnitWeightCost: Weight = Weight::from_parts(1_000_000_000, 64 * 1024);
 	pub const MaxInstructions: u32 = 100;
 	pub const MaxAssetsIntoHolding: u32 = 64;
 }
@@ -111,6 +117,26 @@ pub type Barrier = TrailingSetTopicAsId<
 	>,
 >;
 
+parameter_types! {
+	pub const RootLocation: MultiLocation = MultiLocation::here();
+	/// Delivery fees are paid in the same asset as execution fees.
+	pub FeeAssetId: AssetId = Concrete(RelayLocation::get());
+	/// The base fee of a message, before the per-byte fee and the congestion factor apply.
+	pub const BaseDeliveryFee: u128 = MILLIUNIT.saturating_mul(3);
+}
+
+/// Delivery fees for UMP messages grow exponentially with the congestion of the upward queue.
+pub type PriceForParentDelivery =
+	ExponentialPrice<FeeAssetId, BaseDeliveryFee, TransactionByteFee, ParachainSystem>;
+
+/// Delivery fees for XCMP messages grow exponentially with the congestion of each sibling
+/// channel.
+pub type PriceForSiblingParachainDelivery =
+	ExponentialPrice<FeeAssetId, BaseDeliveryFee, TransactionByteFee, XcmpQueue>;
+
+/// Locations that never pay delivery fees: local root and the relay chain governance.
+pub type WaivedLocations = (Equals<RootLocation>, ParentOrParentsExecutivePlurality);
+
 pub struct XcmConfig;
 impl xcm_executor::Config for XcmConfig {
 	type RuntimeCall = RuntimeCall;
@@ -122,7 +148,7 @@ impl xcm_executor::Config for XcmConfig {
 	type IsTeleporter = (); // Teleporting is disabled.
 	type UniversalLocation = UniversalLocation;
 	type Barrier = Barrier;
-	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+	type Weigher = XcmWeigher;
 	type Trader =
 		UsingComponents<WeightToFee, RelayLocation, AccountId, Balances, ToAuthor<Runtime>>;
 	type ResponseHandler = PolkadotXcm;
@@ -133,7 +159,11 @@ impl xcm_executor::Config for XcmConfig {
 	type MaxAssetsIntoHolding = MaxAssetsIntoHolding;
 	type AssetLocker = ();
 	type AssetExchanger = ();
-	type FeeManager = ();
+	// Delivery fees are routed to the treasury account instead of the block author.
+	type FeeManager = XcmFeeManagerFromComponents<
+		WaivedLocations,
+		XcmFeeToAccount<Self::AssetTransactor, AccountId, TreasuryAccount>,
+	>;
 	type MessageExporter = ();
 	type UniversalAliases = Nothing;
 	type CallDispatcher = RuntimeCall;
@@ -148,7 +178,7 @@ pub type LocalOriginToLocation = SignedToAccountId32<RuntimeOrigin, AccountId, R
 /// queues.
 pub type XcmRouter = WithUniqueTopic<(
 	// Two routers - use UMP to communicate with the relay chain:
-	cumulus_primitives_utility::ParentAsUmp<ParachainSystem, (), ()>,
+	cumulus_primitives_utility::ParentAsUmp<ParachainSystem, (), PriceForParentDelivery>,
 	// ..and XCMP to communicate with the sibling chains.
 	XcmpQueue,
 )>;
@@ -164,7 +194,7 @@ impl pallet_xcm::Config for Runtime {
 	type XcmExecutor = XcmExecutor<XcmConfig>;
 	type XcmTeleportFilter = Everything;
 	type XcmReserveTransferFilter = Nothing;
-	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+	type Weigher = XcmWeigher;
 	type UniversalLocation = UniversalLocation;
 	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeCall = RuntimeCall;
@@ -177,7 +207,7 @@ impl pallet_xcm::Config for Runtime {
 	type TrustedLockers = ();
 	type SovereignAccountOf = LocationToAccountId;
 	type MaxLockers = ConstU32<8>;
-	type WeightInfo = pallet_xcm::TestWeightInfo;
+	type WeightInfo = crate::weights::pallet_xcm::WeightInfo<Runtime>;
 	type AdminOrigin = EnsureRoot<AccountId>;
 	type MaxRemoteLockConsumers = ConstU32<0>;
 	type RemoteLockConsumerIdentifier = ();
@@ -187,3 +217,40 @@ impl cumulus_pallet_xcm::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type XcmExecutor = XcmExecutor<XcmConfig>;
 }
+
+parameter_types! {
+	// Index of the `Hrmp` pallet in the Rococo, Kusama and Polkadot runtimes.
+	pub const RelayHrmpPalletIndex: u8 = 60;
+	// One relay chain token (12 decimals) to pay for each HRMP `Transact`.
+	pub const RelayHrmpFee: u128 = 1_000_000_000_000;
+	pub RelayHrmpTransactWeight: Weight = Weight::from_parts(1_000_000_000, 64 * 1024);
+}
+
+/// Reads the HRMP channels of this chain from the messaging state reported by the relay chain.
+pub struct RelayMessagingChannels;
+impl kine_hrmp::HrmpChannelSource for RelayMessagingChannels {
+	fn ingress() -> Vec<ParaId> {
+		ParachainSystem::relevant_messaging_state()
+			.map(|state| state.ingress_channels.into_iter().map(|(sender, _)| sender).collect())
+			.unwrap_or_default()
+	}
+
+	fn egress() -> Vec<ParaId> {
+		ParachainSystem::relevant_messaging_state()
+			.map(|state| state.egress_channels.into_iter().map(|(recipient, _)| recipient).collect())
+			.unwrap_or_default()
+	}
+}
+
+impl kine_hrmp::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ChannelManagerOrigin = EnsureRoot<AccountId>;
+	type SelfParaId = ParachainInfo;
+	// Channel management only ever talks to the relay chain.
+	type XcmSender = cumulus_primitives_utility::ParentAsUmp<ParachainSystem, (), ()>;
+	type ChannelSource = RelayMessagingChannels;
+	type RelayHrmpPalletIndex = RelayHrmpPalletIndex;
+	type RelayFee = RelayHrmpFee;
+	type RelayTransactWeight = RelayHrmpTransactWeight;
+	type WeightInfo = kine_hrmp::weights::SubstrateWeight<Runtime>;
+}
@@ -0,0 +1,78 @@
+#!/usr/bin/env bash
+# Regenerate the runtime weight files under `runtime/src/weights/` and the `weights.rs` of the
+# local pallets.
+#
+# Usage: ./scripts/benchmark.sh [pallet ...]
+# Without arguments every benchmarked pallet of the runtime is regenerated. Run it on the
+# reference hardware, never on a laptop or a shared CI runner.
+
+set -euo pipefail
+
+cd "$(dirname "$0")/.."
+
+NODE=./target/release/kine-node
+WEIGHTS=runtime/src/weights
+STEPS=${STEPS:-50}
+REPEAT=${REPEAT:-20}
+
+PALLETS=(
+	frame_system
+	pallet_balances
+	pallet_assets
+	pallet_timestamp
+	pallet_sudo
+	pallet_session
+	pallet_message_queue
+	pallet_collator_selection
+	pallet_collective
+	pallet_tx_pause
+	pallet_safe_mode
+	pallet_scheduler
+	pallet_preimage
+	pallet_contracts
+	pallet_evm
+	pallet_xcm
+	cumulus_pallet_parachain_system
+	cumulus_pallet_xcmp_queue
+	pallet_xcm_benchmarks::fungible
+	pallet_xcm_benchmarks::generic
+	kine_hrmp
+	kine_films
+	kine_royalties
+)
+
+if [ "$#" -gt 0 ]; then
+	PALLETS=("$@")
+fi
+
+cargo build --release --features runtime-benchmarks
+
+for pallet in "${PALLETS[@]}"; do
+	case "$pallet" in
+		kine_*)
+			# Local pallets ship their own `WeightInfo` trait and weights.
+			crate=${pallet#kine_}
+			output="pallets/${crate//_/-}/src/weights.rs"
+			template=scripts/pallet-weight-template.hbs
+			;;
+		pallet_xcm_benchmarks::*)
+			output="$WEIGHTS/xcm/${pallet//::/_}.rs"
+			template=scripts/xcm-weight-template.hbs
+			;;
+		*)
+			output="$WEIGHTS/$pallet.rs"
+			template=scripts/weight-template.hbs
+			;;
+	esac
+
+	echo "Benchmarking $pallet into $output"
+	"$NODE" benchmark pallet \
+		--chain dev \
+		--wasm-execution compiled \
+		--pallet "$pallet" \
+		--extrinsic '*' \
+		--steps "$STEPS" \
+		--repeat "$REPEAT" \
+		--template "$template" \
+		--output "$output"
+done
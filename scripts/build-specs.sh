@@ -0,0 +1,24 @@
+#!/usr/bin/env bash
+# Generate the raw chain specs `node/res/<chain>-raw.json` from their genesis files
+# `node/res/<chain>.toml`, then check them with `validate-spec`. The nodes of a network start
+# with `--chain node/res/<chain>-raw.json`.
+#
+# The specs hold the runtime wasm of the node built here, so generate them from the commit the
+# network is launched from.
+
+set -euo pipefail
+
+cd "$(dirname "$0")/.."
+
+if [ $# -gt 0 ]; then CHAINS=("$@"); else CHAINS=(kinera-staging kinera); fi
+NODE=./target/release/kine-node
+
+cargo build --release -p kine-node
+
+for chain in "${CHAINS[@]}"; do
+	echo "Generating the $chain chain spec"
+	"$NODE" build-spec --chain "node/res/$chain.toml" --disable-default-bootnode --raw \
+		> "node/res/$chain-raw.json.tmp"
+	mv "node/res/$chain-raw.json.tmp" "node/res/$chain-raw.json"
+	"$NODE" validate-spec "node/res/$chain-raw.json"
+done